@@ -13,27 +13,77 @@ pub fn main() {
 
 	let mut builder = KeyWordsBuilder::default();
 	builder.push_keys([
+		"--also-lossy",
+		"--append-tracks",
 		"--backward", "--backwards",
+		"--batch",
+		"--bwf",
+		"--cache-test",
+		"--clean",
+		"--clean-all",
+		"--confused-only",
+		"--ctdb-single",
+		"--dump-c2",
+		"--durable-saves",
 		"--flip-flop",
+		"--interpolate",
+		"--log-files",
 		"-h", "--help",
+		"--no-accuraterip",
+		"--no-ctdb",
+		"--no-overread",
 		"--no-resume",
 		"--no-rip",
 		"--no-summary",
+		"--offset-report",
+		"--paranoid",
+		"--probe-offset",
 		"--reset",
+		"--retry-shitlisted",
+		"--sha256",
+		"--single-file",
+		"--skip-corrupt",
 		"--status",
+		"--status-oneline",
 		"--strict",
+		"--submit-file",
 		"--sync",
+		"--sync-combined",
+		"--toc",
+		"--trim-htoa",
+		"--unlock",
 		"-v", "--verbose",
+		"--verified-only",
 		"-V", "--version",
+		"--wav-info",
+		"--whole-disc",
+		"-y", "--yes",
 	]);
 	builder.push_keys_with_values([
+		"--bits",
 		"-c", "--cache",
+		"--cache-bust-mode",
+		"--c2-granularity",
 		"-d", "--dev",
 		"--confidence",
+		"--ctdb-shift",
+		"--decay",
+		"--first-sector",
+		"--from-track",
+		"--htoa",
+		"--last-sector",
+		"--merge-state",
+		"--min-passes",
+		"--min-track-sectors",
+		"--speed",
 		"-o", "--offset",
+		"--on-existing",
 		"-p", "--pass", "--passes",
 		"-r", "--reread", "--rereads",
+		"--strict-after",
 		"-t", "--track", "--tracks",
+		"--verify-existing",
+		"--verify-toc",
 	]);
 	builder.save(out_path("argyle.rs"));
 }
@@ -5,12 +5,20 @@
 use argyle::Argument;
 use dactyl::traits::BytesToUnsigned;
 use riprip_core::{
+	C2Granularity,
+	CacheBustMode,
+	ExistingMode,
 	Disc,
 	DriveVendorModel,
+	HtoaMode,
 	ReadOffset,
 	RipRipError,
 	RipOptions,
 };
+use std::{
+	ops::Range,
+	path::PathBuf,
+};
 
 
 
@@ -21,64 +29,155 @@ pub(super) type Parsed = (
 	RipOptions,
 	Disc,
 	Option<DriveVendorModel>,
+	Option<Range<i32>>,
+	Option<bool>,
 	bool,
 	bool,
+	Option<bool>,
 	bool,
 );
 
 
 
+#[expect(clippy::too_many_lines, reason = "It's a big match.")]
 /// # Parse Options.
 pub(super) fn parse() -> Result<Parsed, RipRipError> {
 	let args = argyle::args()
 		.with_keywords(include!(concat!(env!("OUT_DIR"), "/argyle.rs")));
 
 	let mut opts = RipOptions::default();
+	let mut clean = None;
 	let mut no_rip = false;
 	let mut no_summary = false;
-	let mut status = false;
+	let mut status = None;
+	let mut yes = false;
 	let mut cache = None;
-	let mut dev = None;
-	let mut offset = None;
+	let mut devs = Vec::new();
+	let mut offsets = Vec::new();
+	let (mut first_sector, mut last_sector) = (None, None);
 	let mut tracks = String::new();
 	for arg in args {
 		match arg {
+			Argument::Key("--also-lossy") => { opts = opts.with_lossy(true); },
+			Argument::Key("--append-tracks") => { opts = opts.with_append_tracks(true); },
 			Argument::Key("--backward" | "--backwards") => {
 				opts = opts.with_backwards(true);
 			},
+			Argument::Key("--bwf") => { opts = opts.with_bwf(true); },
+			Argument::Key("--clean") => { clean.get_or_insert(false); },
+			Argument::Key("--clean-all") => { clean.replace(true); },
+			Argument::Key("--confused-only") => { opts = opts.with_confused_only(true); },
+			Argument::Key("--ctdb-single") => { opts = opts.with_ctdb_single(true); },
+			Argument::Key("--dump-c2") => { opts = opts.with_dump_c2(true); },
+			Argument::Key("--durable-saves") => { opts = opts.with_durable_saves(true); },
 			Argument::Key("--flip-flop") => {
 				opts = opts.with_flip_flop(true);
 			},
 			Argument::Key("-h" | "--help") => return Err(RipRipError::PrintHelp),
+			Argument::Key("--interpolate") => { opts = opts.with_interpolate(true); },
+			Argument::Key("--log-files") => { opts = opts.with_log_files(true); },
+			Argument::Key("--no-accuraterip") => { opts = opts.with_no_accuraterip(true); },
+			Argument::Key("--no-ctdb") => { opts = opts.with_no_ctdb(true); },
+			Argument::Key("--no-overread") => { opts = opts.with_no_overread(true); },
 			Argument::Key("--no-resume") => { opts = opts.with_resume(false); },
 			Argument::Key("--no-rip") => { no_rip = true; },
 			Argument::Key("--no-summary") => { no_summary = true; },
+			Argument::Key("--paranoid") => { opts = opts.with_paranoid(true); },
 			Argument::Key("--reset") => { opts = opts.with_reset(true); },
-			Argument::Key("--status") => { status = true; },
+			Argument::Key("--retry-shitlisted") => { opts = opts.with_retry_shitlisted(true); },
+			Argument::Key("--sha256") => { opts = opts.with_sha256(true); },
+			Argument::Key("--single-file") => { opts = opts.with_single_file(true); },
+			Argument::Key("--skip-corrupt") => { opts = opts.with_skip_corrupt(true); },
+			Argument::Key("--status") => { status.get_or_insert(false); },
+			Argument::Key("--status-oneline") => { status.replace(true); },
 			Argument::Key("--strict") => { opts = opts.with_strict(true); },
 			Argument::Key("--sync") => { opts = opts.with_sync(true); },
+			Argument::Key("--submit-file") => { opts = opts.with_submit_file(true); },
+			Argument::Key("--sync-combined") => { opts = opts.with_sync(true).with_sync_combined(true); },
+			Argument::Key("--toc") => { opts = opts.with_cdrdao_toc(true); },
+			Argument::Key("--trim-htoa") => { opts = opts.with_trim_htoa(true); },
+			Argument::Key("--unlock") => { opts = opts.with_unlock(true); },
 			Argument::Key("-v" | "--verbose") => { opts = opts.with_verbose(true); },
 			Argument::Key("-V" | "--version") => return Err(RipRipError::PrintVersion),
+			Argument::Key("--verified-only") => { opts = opts.with_verified_only(true); },
+			Argument::Key("--wav-info") => { opts = opts.with_wav_info(true); },
+			Argument::Key("--whole-disc") => { opts = opts.with_whole_disc(true); },
+			Argument::Key("-y" | "--yes") => { yes = true; },
 
+			Argument::KeyWithValue("--bits", s) => {
+				let s = parse_rip_option_u8(&s, "--bits").ok()
+					.filter(|b| matches!(b, 16 | 24 | 32))
+					.ok_or(RipRipError::CliParse("--bits"))?;
+				opts = opts.with_bits(s);
+			},
 			Argument::KeyWithValue("-c" | "--cache", s) => {
 				let s = parse_rip_option_cache(s)?;
 				cache.replace(s);
 			},
-			Argument::KeyWithValue("-d" | "--dev", s) => { dev.replace(s); },
+			Argument::KeyWithValue("--cache-bust-mode", s) => {
+				let s = parse_rip_option_cache_bust_mode(&s)?;
+				opts = opts.with_cache_bust_mode(s);
+			},
+			Argument::KeyWithValue("--c2-granularity", s) => {
+				let s = parse_rip_option_c2_granularity(&s)?;
+				opts = opts.with_c2_granularity(s);
+			},
+			Argument::KeyWithValue("--ctdb-shift", s) => {
+				let s = parse_rip_option_u16(&s, "--ctdb-shift")?;
+				opts = opts.with_ctdb_shift(s);
+			},
+			Argument::KeyWithValue("-d" | "--dev", s) => { devs.push(s); },
+			Argument::KeyWithValue("--decay", s) => {
+				let s = parse_rip_option_u8(&s, "--decay")?;
+				opts = opts.with_decay(s);
+			},
+			Argument::KeyWithValue("--first-sector", s) => {
+				let s = parse_rip_option_sector(&s, "--first-sector")?;
+				first_sector.replace(s);
+			},
+			Argument::KeyWithValue("--from-track", s) => {
+				let s = parse_rip_option_u8(&s, "--from-track")?;
+				opts = opts.with_from_track(s);
+			},
+			Argument::KeyWithValue("--htoa", s) => {
+				let s = parse_rip_option_htoa(&s)?;
+				opts = opts.with_htoa_mode(s);
+			},
+			Argument::KeyWithValue("--last-sector", s) => {
+				let s = parse_rip_option_sector(&s, "--last-sector")?;
+				last_sector.replace(s);
+			},
+			Argument::KeyWithValue("--min-passes", s) => {
+				let s = parse_rip_option_u8(&s, "--min-passes")?;
+				opts = opts.with_min_passes(s);
+			},
+			Argument::KeyWithValue("--min-track-sectors", s) => {
+				let s = parse_rip_option_u16(&s, "--min-track-sectors")?;
+				opts = opts.with_min_sectors(s);
+			},
 			Argument::KeyWithValue("-o" | "--offset", s) => {
-				let s = ReadOffset::try_from(s.trim().as_bytes())
-					.map_err(|_| RipRipError::CliParse("-o/--offset"))?;
-				offset.replace(s);
+				let s = parse_rip_option_offset(&s)?;
+				offsets.push(s);
+			},
+			Argument::KeyWithValue("--on-existing", s) => {
+				let s = parse_rip_option_existing(&s)?;
+				opts = opts.with_existing(s);
 			},
 			Argument::KeyWithValue("-p" | "--pass" | "--passes", s) => {
-				let s = u8::btou(s.trim().as_bytes())
-					.ok_or(RipRipError::CliParse("-p/--passes"))?;
-				opts = opts.with_passes(s);
+				opts = parse_rip_option_passes(opts, &s)?;
 			},
 			Argument::KeyWithValue("-r" | "--reread" | "--rereads", s) => {
 				let (a, b) = parse_rip_option_reread(s.as_bytes())?;
 				opts = opts.with_rereads(a, b);
 			},
+			Argument::KeyWithValue("--speed", s) => {
+				let s = parse_rip_option_u16(&s, "--speed")?;
+				opts = opts.with_speed(s);
+			},
+			Argument::KeyWithValue("--strict-after", s) => {
+				let s = parse_rip_option_u8(&s, "--strict-after")?;
+				opts = opts.with_strict_after(s);
+			},
 			Argument::KeyWithValue("-t" | "--track" | "--tracks", s) => {
 				if ! tracks.is_empty() { tracks.push(','); }
 				tracks.push_str(&s);
@@ -88,39 +187,260 @@ pub(super) fn parse() -> Result<Parsed, RipRipError> {
 		}
 	}
 
-	// Figure out the disc and drive.
-	let disc = Disc::new(dev)?;
+	// Make sure the options get along with each other.
+	parse_rip_option_conflicts(&opts, &devs)?;
+
+	// Figure out the disc and drive(s).
+	let disc = parse_rip_option_disc(&devs, &offsets)?;
 	let drivevendormodel = disc.drive_vendor_model();
 
 	// Set up some drive-dependent things.
-	if let Some(v) = cache.or_else(|| drivevendormodel.and_then(|vm| vm.detect_cache())) {
-		opts = opts.with_cache(v);
-	}
-	if let Some(v) = offset.or_else(|| drivevendormodel.and_then(|vm| vm.detect_offset())) {
-		opts = opts.with_offset(v);
-	}
+	opts = parse_rip_option_drive(opts, &disc, cache, offsets.first().copied());
+
+	// A manual sector range replaces the track list.
+	let sectors = parse_rip_option_sectors(&disc, &opts, first_sector, last_sector, &tracks)?;
 
 	// If we just want the status or didn't receive any -t, add everything.
-	if status || tracks.is_empty() {
-		let toc = disc.toc();
-		if toc.htoa().is_some() { opts = opts.with_track(0); }
-		for t in toc.audio_tracks() { opts = opts.with_track(t.number()); }
-	}
 	// Otherwise parse what we gathered earlier.
-	else { opts = parse_rip_option_tracks(&disc, opts, &tracks)?; }
+	opts =
+		if status.is_some() || tracks.is_empty() { parse_rip_option_all_tracks(&disc, opts) }
+		else { parse_rip_option_tracks(&disc, opts, &tracks)? };
 
 	Ok((
 		opts,
 		disc,
 		drivevendormodel,
+		sectors,
+		clean,
 		no_rip,
 		no_summary,
 		status,
+		yes,
 	))
 }
 
 
 
+/// # Check Option Conflicts.
+///
+/// A trimmed (separate) HTOA would throw off every track position in the
+/// single-file album's cue sheet, so the two can't be used together.
+///
+/// Drives alternate by pass, so a second one is pointless without at least
+/// two of them.
+fn parse_rip_option_conflicts(opts: &RipOptions, devs: &[String])
+-> Result<(), RipRipError> {
+	if
+		opts.single_file() &&
+		opts.trim_htoa() &&
+		matches!(opts.htoa_mode(), HtoaMode::Separate)
+	{
+		Err(RipRipError::CliArg("--trim-htoa cannot be combined with --single-file".to_owned()))
+	}
+	else if 1 < devs.len() && opts.passes() < 2 {
+		Err(RipRipError::CliArg("a second -d/--dev requires at least two -p/--passes".to_owned()))
+	}
+	else { Ok(()) }
+}
+
+/// # Parse Disc.
+///
+/// Connect to the drive — or drives, if -d/--dev was given twice — and load
+/// the disc. The second drive uses the second -o/--offset, if any.
+fn parse_rip_option_disc(devs: &[String], offsets: &[ReadOffset])
+-> Result<Disc, RipRipError> {
+	if 2 < devs.len() { return Err(RipRipError::CliParse("-d/--dev")); }
+	if 2 < offsets.len() { return Err(RipRipError::CliParse("-o/--offset")); }
+
+	let disc = Disc::new(devs.first())?;
+	if let Some(dev) = devs.get(1) {
+		disc.with_second_drive(dev, offsets.get(1).copied())
+	}
+	else { Ok(disc) }
+}
+
+/// # Parse Drive-Dependent Options.
+///
+/// Apply the cache size and read offset, falling back to the detected values
+/// for the drive if not explicitly set (and noting whether the offset was).
+/// When there are two drives, the larger detected cache wins.
+fn parse_rip_option_drive(
+	mut opts: RipOptions,
+	disc: &Disc,
+	cache: Option<u16>,
+	offset: Option<ReadOffset>,
+) -> RipOptions {
+	let drivevendormodel = disc.drive_vendor_model();
+	if let Some(v) = cache.or_else(||
+		[drivevendormodel, disc.second_drive_vendor_model()].into_iter()
+			.filter_map(|vm| vm.and_then(|vm| vm.detect_cache()))
+			.max()
+	) {
+		opts = opts.with_cache(v);
+	}
+	if let Some(v) = offset.or_else(|| drivevendormodel.and_then(|vm| vm.detect_offset())) {
+		opts = opts.with_offset(v);
+	}
+	opts.with_manual_offset(offset.is_some())
+}
+
+/// # Parse U8.
+///
+/// This is used for simple numeric options like --decay.
+fn parse_rip_option_u8(src: &str, key: &'static str) -> Result<u8, RipRipError> {
+	u8::btou(src.trim().as_bytes()).ok_or(RipRipError::CliParse(key))
+}
+
+/// # Parse U16.
+///
+/// Same as `parse_rip_option_u8`, but for bigger numbers like --ctdb-shift.
+fn parse_rip_option_u16(src: &str, key: &'static str) -> Result<u16, RipRipError> {
+	u16::btou(src.trim().as_bytes()).ok_or(RipRipError::CliParse(key))
+}
+
+/// # Parse Offset.
+fn parse_rip_option_offset(src: &str) -> Result<ReadOffset, RipRipError> {
+	ReadOffset::try_from(src.trim().as_bytes())
+		.map_err(|_| RipRipError::CliParse("-o/--offset"))
+}
+
+/// # Parse Passes.
+///
+/// This is either a number or "auto".
+fn parse_rip_option_passes(opts: RipOptions, src: &str) -> Result<RipOptions, RipRipError> {
+	let src = src.trim();
+	if src.eq_ignore_ascii_case("auto") { Ok(opts.with_auto_passes(true)) }
+	else {
+		let passes = u8::btou(src.as_bytes()).ok_or(RipRipError::CliParse("-p/--passes"))?;
+		Ok(opts.with_passes(passes).with_auto_passes(false))
+	}
+}
+
+/// # Parse Sector.
+fn parse_rip_option_sector(src: &str, key: &'static str) -> Result<i32, RipRipError> {
+	u32::btou(src.trim().as_bytes())
+		.and_then(|n| i32::try_from(n).ok())
+		.ok_or(RipRipError::CliParse(key))
+}
+
+/// # Parse Sector Range.
+///
+/// Convert the inclusive `--first-sector`/`--last-sector` values into an
+/// exclusive range, filling in any blanks with the disc boundaries.
+///
+/// This returns `None` if neither option was set.
+fn parse_rip_option_sectors(
+	disc: &Disc,
+	opts: &RipOptions,
+	first: Option<i32>,
+	last: Option<i32>,
+	tracks: &str,
+) -> Result<Option<Range<i32>>, RipRipError> {
+	if first.is_none() && last.is_none() { return Ok(None); }
+	if ! tracks.is_empty() {
+		return Err(RipRipError::CliArg(
+			"-t/--tracks cannot be combined with --first-sector/--last-sector".to_owned()
+		));
+	}
+	if opts.whole_disc() {
+		return Err(RipRipError::CliArg(
+			"--whole-disc cannot be combined with --first-sector/--last-sector".to_owned()
+		));
+	}
+
+	let leadout = i32::try_from(disc.toc().audio_leadout_normalized())
+		.map_err(|_| RipRipError::SectorRange)?;
+	let first = first.unwrap_or(0);
+	let last = last.unwrap_or(leadout - 1);
+	if first <= last && last < leadout { Ok(Some(first..last + 1)) }
+	else { Err(RipRipError::SectorRange) }
+}
+
+/// # Parse Archive Verification.
+///
+/// Return the `--verify-toc` value, if any, along with the
+/// `--verify-existing` directory and `-o/--offset`, if specified. This is
+/// handled separately from everything else because it doesn't involve a
+/// disc.
+///
+/// ## Errors
+///
+/// This will return an error if `--verify-existing` is passed without
+/// `--verify-toc`, or the offset is invalid.
+pub(super) fn parse_verify()
+-> Result<Option<(String, Option<String>, ReadOffset)>, RipRipError> {
+	let mut toc = None;
+	let mut dir = None;
+	let mut offset = ReadOffset::default();
+	for arg in argyle::args().with_keywords(include!(concat!(env!("OUT_DIR"), "/argyle.rs"))) {
+		match arg {
+			Argument::KeyWithValue("--verify-toc", s) => { toc.replace(s); },
+			Argument::KeyWithValue("--verify-existing", s) => { dir.replace(s); },
+			Argument::KeyWithValue("-o" | "--offset", s) => {
+				offset = ReadOffset::try_from(s.trim().as_bytes())
+					.map_err(|_| RipRipError::CliParse("-o/--offset"))?;
+			},
+			_ => {},
+		}
+	}
+
+	match (toc, dir) {
+		(Some(toc), dir) => Ok(Some((toc, dir, offset))),
+		(None, Some(_)) => Err(RipRipError::CliArg(
+			"--verify-existing requires --verify-toc".to_owned()
+		)),
+		(None, None) => Ok(None),
+	}
+}
+
+/// # Parse Merge.
+///
+/// Like `parse_verify`, `--merge-state` is handled separately. This returns
+/// the directory to merge states from, if any.
+///
+/// ## Errors
+///
+/// This will return an error if the path is not a directory.
+pub(super) fn parse_merge() -> Result<Option<PathBuf>, RipRipError> {
+	let mut dir = None;
+	for arg in argyle::args().with_keywords(include!(concat!(env!("OUT_DIR"), "/argyle.rs"))) {
+		if let Argument::KeyWithValue("--merge-state", s) = arg { dir.replace(s); }
+	}
+
+	match dir.map(PathBuf::from) {
+		Some(dir) if ! dir.is_dir() => Err(RipRipError::CliArg(
+			"--merge-state requires a directory".to_owned()
+		)),
+		dir => Ok(dir),
+	}
+}
+
+/// # Parse Batch.
+///
+/// Like `parse_probe`, `--batch` is handled separately. This returns whether
+/// or not it was passed.
+pub(super) fn parse_batch() -> bool {
+	argyle::args()
+		.with_keywords(include!(concat!(env!("OUT_DIR"), "/argyle.rs")))
+		.any(|arg| matches!(arg, Argument::Key("--batch")))
+}
+
+/// # Parse Probes.
+///
+/// Like `parse_merge`, `--probe-offset`, `--cache-test`, and
+/// `--offset-report` are handled separately. This returns whether or not each
+/// was passed, respectively.
+pub(super) fn parse_probe() -> (bool, bool, bool) {
+	argyle::args()
+		.with_keywords(include!(concat!(env!("OUT_DIR"), "/argyle.rs")))
+		.fold((false, false, false), |(probe, cache, report), arg| match arg {
+			Argument::Key("--probe-offset") => (true, cache, report),
+			Argument::Key("--cache-test") => (probe, true, report),
+			Argument::Key("--offset-report") => (probe, cache, true),
+			_ => (probe, cache, report),
+		})
+}
+
 /// # Parse Cache Size.
 fn parse_rip_option_cache(cache: String) -> Result<u16, RipRipError> {
 	let cache = cache.into_bytes();
@@ -133,6 +453,44 @@ fn parse_rip_option_cache(cache: String) -> Result<u16, RipRipError> {
 		.ok_or(RipRipError::CliParse("-c/--cache"))
 }
 
+/// # Parse Cache Bust Mode.
+fn parse_rip_option_cache_bust_mode(mode: &str) -> Result<CacheBustMode, RipRipError> {
+	match mode.trim() {
+		"fill" => Ok(CacheBustMode::Fill),
+		"seek" => Ok(CacheBustMode::Seek),
+		_ => Err(RipRipError::CliParse("--cache-bust-mode")),
+	}
+}
+
+/// # Parse C2 Granularity.
+fn parse_rip_option_c2_granularity(mode: &str) -> Result<C2Granularity, RipRipError> {
+	match mode.trim() {
+		"sample" => Ok(C2Granularity::Sample),
+		"sector" => Ok(C2Granularity::Sector),
+		_ => Err(RipRipError::CliParse("--c2-granularity")),
+	}
+}
+
+/// # Parse Existing File Mode.
+fn parse_rip_option_existing(mode: &str) -> Result<ExistingMode, RipRipError> {
+	match mode.trim() {
+		"overwrite" => Ok(ExistingMode::Overwrite),
+		"skip" => Ok(ExistingMode::Skip),
+		"suffix" => Ok(ExistingMode::Suffix),
+		_ => Err(RipRipError::CliParse("--on-existing")),
+	}
+}
+
+/// # Parse HTOA Mode.
+fn parse_rip_option_htoa(mode: &str) -> Result<HtoaMode, RipRipError> {
+	match mode.trim() {
+		"separate" => Ok(HtoaMode::Separate),
+		"merge" => Ok(HtoaMode::Merge),
+		"ignore" => Ok(HtoaMode::Ignore),
+		_ => Err(RipRipError::CliParse("--htoa")),
+	}
+}
+
 /// # Parse Re-read Option.
 fn parse_rip_option_reread(v: &[u8]) -> Result<(u8, u8), RipRipError> {
 	// Default.
@@ -161,6 +519,26 @@ fn parse_rip_option_reread(v: &[u8]) -> Result<(u8, u8), RipRipError> {
 	Ok((a, b))
 }
 
+/// # Parse Rip Track.
+///
+/// Decode a single track number. Anything past 99 can't exist on an audio
+/// CD, so is rejected outright rather than silently ignored.
+fn parse_rip_option_track(v: &[u8]) -> Result<u8, RipRipError> {
+	let v = u8::btou(v).ok_or(RipRipError::CliParse("-t/--tracks"))?;
+	if 99 < v { Err(RipRipError::NoTrack(v)) }
+	else { Ok(v) }
+}
+
+/// # All Rip Tracks.
+///
+/// Add the HTOA (if any) and every audio track.
+fn parse_rip_option_all_tracks(disc: &Disc, mut opts: RipOptions) -> RipOptions {
+	let toc = disc.toc();
+	if toc.htoa().is_some() && ! matches!(opts.htoa_mode(), HtoaMode::Ignore) { opts = opts.with_track(0); }
+	for t in toc.audio_tracks() { opts = opts.with_track(t.number()); }
+	opts
+}
+
 /// # Parse Rip Tracks.
 fn parse_rip_option_tracks(disc: &Disc, mut opts: RipOptions, tracks: &str)
 -> Result<RipOptions, RipRipError> {
@@ -179,8 +557,8 @@ fn parse_rip_option_tracks(disc: &Disc, mut opts: RipOptions, tracks: &str)
 			}
 
 			// Decode.
-			let a = u8::btou(a).ok_or(RipRipError::CliParse("-t/--tracks"))?;
-			let b = u8::btou(b).ok_or(RipRipError::CliParse("-t/--tracks"))?;
+			let a = parse_rip_option_track(a)?;
+			let b = parse_rip_option_track(b)?;
 
 			// Add them all!
 			if a <= b {
@@ -190,8 +568,7 @@ fn parse_rip_option_tracks(disc: &Disc, mut opts: RipOptions, tracks: &str)
 		}
 		// Otherwise it should be a single index.
 		else {
-			let v = u8::btou(v).ok_or(RipRipError::CliParse("-t/--tracks"))?;
-			opts = opts.with_track(v);
+			opts = opts.with_track(parse_rip_option_track(v)?);
 		}
 	}
 
@@ -208,7 +585,9 @@ fn parse_rip_option_tracks(disc: &Disc, mut opts: RipOptions, tracks: &str)
 	}
 	// If no tracks were specified, DO IT ALL.
 	else {
-		if toc.htoa().is_some() { opts = opts.with_track(0); }
+		if toc.htoa().is_some() && ! matches!(opts.htoa_mode(), HtoaMode::Ignore) {
+			opts = opts.with_track(0);
+		}
 		for t in toc.audio_tracks() { opts = opts.with_track(t.number()); }
 	}
 
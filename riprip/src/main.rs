@@ -57,21 +57,40 @@
 
 mod cli;
 
-use dactyl::NiceU16;
+use dactyl::{
+	NiceU16,
+	NiceU32,
+	NiceU64,
+};
 use fyi_msg::{
 	Msg,
 	Progless,
 };
+use nix::sys::signal::{
+	Signal,
+	SigSet,
+};
 use oxford_join::JoinFmt;
 use riprip_core::{
 	Disc,
+	DriveVendorModel,
+	HtoaMode,
 	KillSwitch,
+	ReadOffset,
 	RipRipError,
 	RipOptions,
+	RipTrackResult,
+	user_offsets_path,
 };
 use std::{
 	borrow::Cow,
+	collections::BTreeSet,
 	fmt,
+	ops::Range,
+	path::{
+		Path,
+		PathBuf,
+	},
 	sync::{
 		atomic::{
 			AtomicBool,
@@ -82,6 +101,10 @@ use std::{
 		},
 		Arc,
 	},
+	time::{
+		Duration,
+		Instant,
+	},
 };
 use utc2k::FmtUtc2k;
 
@@ -89,9 +112,16 @@ use utc2k::FmtUtc2k;
 
 /// # A Divider Line.
 ///
-/// This is used to encase the drive vendor/model during summary. We'll slice
-/// it to match the length rather than `"-".repeat()` or whatever.
-const DIVIDER: &str = "------------------------";
+/// This is used to encase the drive vendor/model (and firmware revision)
+/// during summary. We'll slice it to match the length rather than
+/// `"-".repeat()` or whatever.
+const DIVIDER: &str = "----------------------------------------";
+
+/// # Batch Polling: Initial Delay.
+const BATCH_POLL_MIN: Duration = Duration::from_secs(1);
+
+/// # Batch Polling: Maximum Delay.
+const BATCH_POLL_MAX: Duration = Duration::from_secs(16);
 
 
 
@@ -104,9 +134,15 @@ fn main() {
 		Err(e @ (RipRipError::PrintHelp | RipRipError::PrintVersion)) => {
 			println!("{e}");
 		},
+		Err(e @ RipRipError::Unverified) => {
+			let code = e.exit_code();
+			Msg::warning(e.to_string()).eprint();
+			std::process::exit(i32::from(code));
+		},
 		Err(e) => {
+			let code = e.exit_code();
 			Msg::from(e).eprint();
-			std::process::exit(1);
+			std::process::exit(i32::from(code));
 		},
 	}
 }
@@ -116,62 +152,371 @@ fn main() {
 ///
 /// This does all the stuff.
 fn main__() -> Result<(), RipRipError> {
+	// Verifying an archived rip doesn't involve a disc at all.
+	if let Some((toc, dir, offset)) = cli::parse_verify()? {
+		return dir.map_or_else(
+			|| riprip_core::verify_archive(&toc),
+			|dir| riprip_core::verify_existing(&toc, dir, offset),
+		);
+	}
+
+	let merge = cli::parse_merge()?;
+	let (probe, cache_test, report) = cli::parse_probe();
+	let batch = cli::parse_batch();
 	let (
 		opts,
 		disc,
 		drivevendormodel,
+		sectors,
+		clean,
 		no_rip,
 		no_summary,
 		status,
+		yes,
 	) = cli::parse()?;
 
-	// Quiet?
-	if ! no_summary {
-		if let Some(vm) = drivevendormodel {
-			let vm = vm.to_string();
-			if ! vm.is_empty() {
-				eprintln!(
-					"\x1b[2;36m{}\n\x1b[0;1;36m{vm}\n\x1b[0;2;36m{}\n\x1b[0m",
-					&DIVIDER[..vm.len()],
-					&DIVIDER[..vm.len()],
-				);
-			}
-		}
-
-		eprintln!("{disc}");
+	// Just reporting the drive settings?
+	if report {
+		offset_report(&disc, &opts);
+		return Ok(());
 	}
 
+	// Quiet? (The one-line status is always quiet.)
+	if ! no_summary && status != Some(true) { disc_summary(&disc, drivevendormodel); }
+
 	// Go ahead and leave if there's no ripping to do.
 	if no_rip { return Ok(()); }
 
+	// Let the user know if the files are going somewhere unexpected.
+	if status != Some(true) {
+		if let Some(dir) = riprip_core::output_dir_fallback() {
+			Msg::warning(format!(
+				"The current working directory is missing; using {} instead. (Set RIPRIP_OUTPUT_DIR to choose a different location.)",
+				dir.display(),
+			)).eprint();
+		}
+	}
+
+	// Cleaning up instead?
+	if let Some(all) = clean { return clean_scratch(&disc, all, yes); }
+
 	// Set up progress and killswitch in case they're needed.
 	let progress = Progless::default();
 	let killed = KillSwitch::default();
+	sigusr1(killed.inner_paused());
 	sigint(killed.inner(), Some(progress.clone()));
 
 	// Just checking the status?
-	if status { return disc.status(&opts, &progress, &killed); }
+	match status {
+		Some(false) => return disc.status(&opts, &progress, &killed),
+		Some(true) => return disc.status_oneline(&opts, &progress, &killed),
+		None => {},
+	}
+
+	// Probing the offset or cache instead?
+	if probe { return disc.probe_offset(&opts, &progress, &killed); }
+	if cache_test { return disc.cache_test(&killed); }
+
+	// Rip and rip and rip!
+	if batch {
+		return batch_rip(disc, opts, sectors, merge, no_summary, &progress, &killed);
+	}
+	rip_disc(&disc, &opts, sectors, merge.as_deref(), true, &progress, &killed)
+}
+
+/// # Disc Summary.
+///
+/// Print the drive vendor/model (and firmware revision), any second drive,
+/// and the basic disc details.
+fn disc_summary(disc: &Disc, drivevendormodel: Option<DriveVendorModel>) {
+	if let Some(vm) = drivevendormodel {
+		let vm = vm.to_string();
+		if ! vm.is_empty() {
+			let rev = disc.drive_revision()
+				.map_or_else(String::new, |r| format!(" (rev. {r})"));
+			let len = usize::min(DIVIDER.len(), vm.len() + rev.len());
+			eprintln!(
+				"\x1b[2;36m{}\n\x1b[0;1;36m{vm}\x1b[0;36m{rev}\n\x1b[0;2;36m{}\n\x1b[0m",
+				&DIVIDER[..len],
+				&DIVIDER[..len],
+			);
+		}
+	}
+	if let Some(offset) = disc.second_offset() {
+		Msg::info(format!(
+			"Passes will alternate with a second drive ({}) at offset {:+}.",
+			disc.second_drive_vendor_model().map_or_else(|| "unknown".to_owned(), |vm| vm.to_string()),
+			offset.samples(),
+		)).eprint();
+	}
+
+	eprintln!("{disc}");
+}
+
+/// # Rip Disc.
+///
+/// Merge in any outside state, summarize the plan — prompting for
+/// confirmation if `prompt` — and rip the disc.
+///
+/// ## Errors
+///
+/// In addition to any ripping errors, this will return an error if the rip
+/// was aborted, or if any of the tracks remain unverified at the end.
+fn rip_disc(
+	disc: &Disc,
+	opts: &RipOptions,
+	sectors: Option<Range<i32>>,
+	merge: Option<&Path>,
+	prompt: bool,
+	progress: &Progless,
+	killed: &KillSwitch,
+) -> Result<(), RipRipError> {
+	// Merge in another rip's states first?
+	if let Some(src) = merge {
+		let merged = disc.merge_state(src, opts, progress, killed)?;
+		Msg::success(format!(
+			"Merged the state data for {merged} track{}.",
+			if merged == 1 { "" } else { "s" },
+		)).eprint();
+	}
+
+	// Nothing left to do?
+	if sectors.is_none() && disc.all_confirmed(opts)? {
+		Msg::success("Nothing to do; the requested tracks are already confirmed.").eprint();
+		return Ok(());
+	}
 
 	// Parse the options.
-	rip_summary(&disc, &opts)?;
+	rip_summary(disc, opts, sectors.as_ref(), prompt)?;
 
 	// Log header.
-	if opts.verbose() { log_header(&disc, &opts); }
+	if opts.verbose() { log_header(disc, opts, sectors.as_ref()); }
 
 	// Rip and rip and rip!
 	let hide_cursor = HideCursor::new();
-	disc.rip(&opts, &progress, &killed)?;
+	// (Sector rips can't be verified, so don't hold that against them.)
+	let verified =
+		if let Some(rng) = sectors { disc.rip_sectors(rng, opts, progress, killed)?; true }
+		else { disc.rip(opts, progress, killed)?.iter().all(verified) };
 	drop(hide_cursor);
 
 	if killed.killed() { Err(RipRipError::Killed) }
-	else { Ok(()) }
+	else if verified { Ok(()) }
+	else { Err(RipRipError::Unverified) }
+}
+
+/// # Batch Rip.
+///
+/// Rip the disc, eject it, wait for the next one, and repeat until
+/// interrupted. Each new disc is loaded with the same CLI options as the
+/// first, and gets its own state files like any other.
+///
+/// Only the first disc's summary needs to be confirmed; the rest proceed
+/// automatically. Discs that fail or can't be fully verified are noted, but
+/// don't stop the batch, unless the failure is one every subsequent disc
+/// would run into too, like a full disk; see `batch_fatal`.
+///
+/// ## Errors
+///
+/// This will return an error if a second drive is in play, if the batch is
+/// aborted mid-rip, or if a fatal error is encountered. Otherwise, if any
+/// disc failed, `RipRipError::BatchFailed` is returned at the end, or if any
+/// were merely left unverified, `RipRipError::Unverified`, so the exit code
+/// still says as much.
+fn batch_rip(
+	mut disc: Disc,
+	mut opts: RipOptions,
+	mut sectors: Option<Range<i32>>,
+	mut merge: Option<PathBuf>,
+	no_summary: bool,
+	progress: &Progless,
+	killed: &KillSwitch,
+) -> Result<(), RipRipError> {
+	if disc.second_offset().is_some() {
+		return Err(RipRipError::CliArg("--batch only works with a single drive.".to_owned()));
+	}
+
+	let dev = disc.dev().map(Path::to_path_buf);
+	let mut seen = BTreeSet::new();
+	let mut done = 0_usize;
+	let mut unverified = 0_usize;
+	let mut failed = 0_usize;
+	loop {
+		let id = disc.toc().cddb_id().to_string();
+		match rip_disc(&disc, &opts, sectors.take(), merge.take().as_deref(), done == 0, progress, killed) {
+			Ok(()) => {},
+			Err(e @ RipRipError::Killed) => return Err(e),
+			Err(RipRipError::Unverified) => {
+				unverified += 1;
+				Msg::warning(format!("Disc {id} could not be fully verified; moving on.")).eprint();
+			},
+			Err(e) if batch_fatal(&e) => return Err(e),
+			Err(e) => {
+				failed += 1;
+				Msg::warning(format!("Disc {id} could not be ripped: {e} Moving on.")).eprint();
+			},
+		}
+		done += 1;
+
+		// Out with the old.
+		seen.insert(id);
+		batch_eject(disc);
+
+		// In with the new.
+		Msg::info(format!(
+			"Waiting for the next disc… \x1b[2m({done} done; press CTRL+C to stop.)\x1b[0m",
+		)).eprint();
+		let Some(next) = batch_next(dev.as_deref(), &mut seen, &mut failed, killed)? else {
+			Msg::success(format!(
+				"Batch stopped after {done} disc{}.",
+				if done == 1 { "" } else { "s" },
+			)).eprint();
+			return
+				if failed != 0 { Err(RipRipError::BatchFailed(failed)) }
+				else if unverified != 0 { Err(RipRipError::Unverified) }
+				else { Ok(()) };
+		};
+
+		let drivevendormodel;
+		(opts, disc, drivevendormodel, sectors, ..) = next;
+		if ! no_summary { disc_summary(&disc, drivevendormodel); }
+	}
+}
+
+/// # Batch: Fatal Error?
+///
+/// Returns `true` for errors that would doom every subsequent disc too —
+/// full disks, cache/write problems, and locks — so there's no point
+/// continuing the batch.
+const fn batch_fatal(err: &RipRipError) -> bool {
+	matches!(
+		err,
+		RipRipError::Cache |
+		RipRipError::CachePath(_) |
+		RipRipError::Delete(_) |
+		RipRipError::DiskFull(_, _) |
+		RipRipError::Locked |
+		RipRipError::Write(_)
+	)
+}
+
+/// # Batch: Eject.
+///
+/// Eject the disc, or ask the user to remove it if that doesn't work.
+fn batch_eject(disc: Disc) {
+	if let Err(e) = disc.eject() {
+		Msg::warning(format!("{e} Please remove it manually.")).eprint();
+	}
+}
+
+/// # Batch: Next Disc.
+///
+/// Wait for a disc that hasn't been `seen` yet and load it with the CLI
+/// options. Discs that can't be loaded that way are ejected and skipped (and
+/// added to the `failed` count). Returns `None` if interrupted first.
+///
+/// ## Errors
+///
+/// This will return any fatal errors encountered while loading the disc;
+/// see `batch_fatal`.
+fn batch_next(
+	dev: Option<&Path>,
+	seen: &mut BTreeSet<String>,
+	failed: &mut usize,
+	killed: &KillSwitch,
+) -> Result<Option<cli::Parsed>, RipRipError> {
+	loop {
+		if ! batch_wait(dev, seen, killed) { return Ok(None); }
+		match cli::parse() {
+			Ok(next) => return Ok(Some(next)),
+			Err(e) if batch_fatal(&e) => return Err(e),
+			Err(e) => {
+				*failed += 1;
+				Msg::warning(format!("The new disc could not be loaded: {e} Skipping it.")).eprint();
+				if let Ok(disc) = Disc::new(dev) {
+					seen.insert(disc.toc().cddb_id().to_string());
+					batch_eject(disc);
+				}
+			},
+		}
+	}
+}
+
+/// # Batch: Wait for Disc.
+///
+/// Poll the drive until a disc that hasn't been `seen` yet is ready, backing
+/// off a little more each time it comes up short. Returns `false` if
+/// interrupted first.
+///
+/// Discs already handled earlier in the batch — whether the previous one
+/// failed to eject, or an earlier one was put back in by mistake — are
+/// ignored until swapped for something else.
+fn batch_wait(dev: Option<&Path>, seen: &BTreeSet<String>, killed: &KillSwitch) -> bool {
+	let mut delay = BATCH_POLL_MIN;
+	loop {
+		// Sleep in small chunks so CTRL+C needn't wait for us.
+		let start = Instant::now();
+		while start.elapsed() < delay {
+			if killed.killed() { return false; }
+			std::thread::sleep(Duration::from_millis(100));
+		}
+
+		// (No disc, tray open, still spinning up, etc., are all just not
+		// ready yet.)
+		if Disc::new(dev).is_ok_and(|disc| ! seen.contains(&disc.toc().cddb_id().to_string())) {
+			return true;
+		}
+
+		delay = Duration::min(delay.saturating_mul(2), BATCH_POLL_MAX);
+	}
+}
+
+/// # Verified?
+///
+/// Returns `true` if the track was confirmed, or is an HTOA with nothing
+/// worse than likely samples (since that's the best it can ever do).
+const fn verified(res: &RipTrackResult) -> bool {
+	res.is_confirmed() || (res.track() == 0 && res.bad() == 0 && res.maybe() == 0)
+}
+
+/// # Clean Scratch.
+///
+/// Remove the rip state files for the current disc — or all discs — after
+/// confirming the user really wants that, unless -y/--yes.
+fn clean_scratch(disc: &Disc, all: bool, yes: bool) -> Result<(), RipRipError> {
+	let files = disc.scratch_files(all)?;
+	if files.is_empty() {
+		Msg::info("There are no state files to remove.").eprint();
+		return Ok(());
+	}
+
+	let len = files.len();
+	let noun = if len == 1 { "file" } else { "files" };
+	if
+		! yes &&
+		! Msg::plain(format!(
+			"Remove {len} state {noun}{}?",
+			if all { " (for all discs)" } else { "" },
+		)).eprompt_with_default(false)
+	{
+		return Err(RipRipError::Killed);
+	}
+
+	for file in files {
+		std::fs::remove_file(&file)
+			.map_err(|_| RipRipError::Delete(file.to_string_lossy().into_owned()))?;
+	}
+
+	Msg::success(format!("Removed {len} state {noun}.")).eprint();
+	Ok(())
 }
 
 /// # Log Header.
 ///
-/// Print a few basic setup details for the log. Only applies when -v/--verbose
-/// is set, and we're ripping something.
-fn log_header(disc: &Disc, opts: &RipOptions) {
+/// Print a few basic setup details — including how much work is planned —
+/// for the log. Only applies when -v/--verbose is set, and we're
+/// ripping something.
+fn log_header(disc: &Disc, opts: &RipOptions, sectors: Option<&Range<i32>>) {
 	use std::io::Write;
 
 	let writer = std::io::stdout();
@@ -199,6 +544,22 @@ fn log_header(disc: &Disc, opts: &RipOptions) {
 		}
 	}
 
+	// Scope. Sector range rips have no tracks to speak of.
+	let (tracks, total) = sectors.map_or_else(
+		|| disc.rip_scope(opts),
+		|rng| (0, rng.len() as u64),
+	);
+	let _res = writeln!(
+		&mut handle,
+		"## Scope: {} track{}, {} sectors × {}{} pass{}",
+		tracks,
+		if tracks == 1 { "" } else { "s" },
+		NiceU64::from(total),
+		if opts.auto_passes() { "up to " } else { "" },
+		opts.passes(),
+		if opts.passes() == 1 { "" } else { "es" },
+	);
+
 	// Everything else!
 	let _res = writeln!(
 		&mut handle,
@@ -214,8 +575,8 @@ fn log_header(disc: &Disc, opts: &RipOptions) {
 ##       * BAD:      values returned with C2 errors
 ##       * CONFUSED: many contradictory \"good\" values
 #####",
-		FmtUtc2k::now(),
 		disc.toc().cddb_id(),
+		FmtUtc2k::now(),
 	);
 
 	let _res = handle.flush();
@@ -223,32 +584,36 @@ fn log_header(disc: &Disc, opts: &RipOptions) {
 
 
 
+#[expect(clippy::too_many_lines, reason = "There are a lot of settings.")]
 /// # Rip Summary.
 ///
 /// Summarize and confirm the chosen settings before proceeding.
-fn rip_summary(disc: &Disc, opts: &RipOptions) -> Result<(), RipRipError> {
+fn rip_summary(
+	disc: &Disc,
+	opts: &RipOptions,
+	sectors: Option<&Range<i32>>,
+	prompt: bool,
+) -> Result<(), RipRipError> {
 	// Build up all the messy values.
-	let nice_c2 = Cow::Borrowed(
-		if opts.strict() { "C2 Error Pointers \x1b[0;2m(\x1b[0;1;93mSector\x1b[0;2m)" }
-		else { "C2 Error Pointers \x1b[0;2m(\x1b[0;1mSample\x1b[0;2m)" }
-	);
-	let nice_cache = opts.cache().map_or(
-		Cow::Borrowed("Disabled"),
-		|c| Cow::Owned(format!("{} KiB", NiceU16::from(c.get())))
-	);
-	let nice_chk = Cow::Owned(format!(
-		"AccurateRip/CTDB cf. {}+",
-		opts.confidence(),
-	));
+	let nice_c2 = rip_summary_c2(opts);
+	let nice_cache = rip_summary_cache(opts);
+	let nice_chk = rip_summary_chk(opts);
+	let nice_ctdb1 = Cow::Borrowed("Single CTDB Match \x1b[0;2m(\x1b[0;1;93mLow Confidence\x1b[0;2m)");
 	let nice_offset = Cow::Owned(format!("{}", opts.offset().samples()));
+	let nice_access = Cow::Owned(rip_summary_access(disc, opts));
+	let out_dir = rip_summary_output_dir()?;
 	let nice_output = Cow::Owned(format!(
-		"./{}/{}_\x1b[0;2m##\x1b[0;1m.wav",
-		riprip_core::CACHE_BASE,
+		"{out_dir}/{}_\x1b[0;2m##\x1b[0;1m.wav",
+		disc.toc().cddb_id(),
+	));
+	let nice_lossy = Cow::Owned(format!(
+		"{out_dir}/{}_\x1b[0;2m##\x1b[0;1m.ogg",
 		disc.toc().cddb_id(),
 	));
 	let nice_passes = Cow::Owned(format!(
 		"{}{}",
-		opts.passes(),
+		if opts.auto_passes() { format!("Auto (≤{})", opts.passes()) }
+		else { opts.passes().to_string() },
 		if opts.resume() {
 			if opts.reset() { " \x1b[0;2m(\x1b[0;1;93mReset Counts\x1b[0;2m)" }
 			else { "" }
@@ -267,25 +632,70 @@ fn rip_summary(disc: &Disc, opts: &RipOptions) -> Result<(), RipRipError> {
 	let nice_rereads2 =
 		if rr_b == 1 { Cow::Borrowed("Re-Read Contention") }
 		else { Cow::Owned(format!("Re-Read Contention {rr_b}×")) };
-	let nice_sync = Cow::Borrowed("Subchannel Sync");
-	let nice_tracks = Cow::Owned(rip_summary_tracks(opts));
+	let nice_decay = Cow::Owned(opts.decay().map_or_else(
+		|| "Count Decay".to_owned(),
+		|n| format!("Count Decay After {n} Passes"),
+	));
+	let nice_sync = Cow::Borrowed(
+		if opts.sync_combined() { "Subchannel Sync \x1b[0;2m(Combined Reads)" }
+		else { "Subchannel Sync" }
+	);
+	let nice_confused = Cow::Borrowed("Confused Sectors Only");
+	let nice_paranoid = Cow::Borrowed("Paranoid Re-Read");
+	let nice_whole = Cow::Borrowed("Whole Disc Stream");
+	let nice_trim = Cow::Borrowed("Trim HTOA Silence");
+	let nice_htoa = Cow::Borrowed(
+		if matches!(opts.htoa_mode(), HtoaMode::Ignore) { "Ignore HTOA" }
+		else { "Merge HTOA Into Track 01" }
+	);
+	let nice_dump = Cow::Borrowed("Dump C2 Error Maps");
+	let nice_durable = Cow::Borrowed("Durable Saves");
+	let nice_corrupt = Cow::Borrowed("Skip Corrupt States");
+	let nice_shitlisted = Cow::Borrowed("Retry Shitlisted Sectors");
+	let nice_overread = Cow::Borrowed("Track Overread");
+	let nice_speed = Cow::Owned(format!("{}x", opts.speed()));
+	let nice_single = Cow::Borrowed("Single-File Album");
+	let nice_bwf = Cow::Borrowed("Broadcast Wave (bext)");
+	let nice_info = Cow::Borrowed("RIFF INFO Comments");
+	let (label_tracks, nice_tracks) = sectors.map_or_else(
+		|| ("Tracks:", Cow::Owned(rip_summary_tracks(opts))),
+		|rng| ("Sectors:", Cow::Owned(format!("{}–{}", rng.start, rng.end - 1))),
+	);
 	let nice_verbose = Cow::Borrowed(if opts.verbose() { "Yes" } else { "No" });
 
 	// Combine the values with labels so we can at least somewhat cleanly
 	// display everything. Haha.
 	let set = [
-		("Tracks:", nice_tracks, true),
+		(label_tracks, nice_tracks, true),
 		("Read Offset:", nice_offset, 0 != opts.offset().samples_abs()),
+		("Accessible:", nice_access, true),
 		("Cache Bust:", nice_cache, opts.cache().is_some()),
-		("Verification:", nice_chk, true),
+		("Verification:", nice_chk, sectors.is_none()),
+		("", nice_ctdb1, opts.ctdb_single() && ! opts.no_ctdb()),
 		("", nice_c2, true),
 		("", nice_rereads1, 1 != rr_a),
 		("", nice_rereads2, 1 != rr_b),
+		("", nice_decay, opts.decay().is_some()),
 		("", nice_sync, opts.sync()),
+		("", nice_confused, opts.confused_only()),
+		("", nice_paranoid, opts.paranoid()),
+		("", nice_whole, opts.whole_disc()),
+		("", nice_trim, opts.trim_htoa()),
+		("", nice_htoa, ! matches!(opts.htoa_mode(), HtoaMode::Separate)),
+		("", nice_dump, opts.dump_c2()),
+		("", nice_durable, opts.durable_saves()),
+		("", nice_corrupt, opts.skip_corrupt()),
+		("", nice_shitlisted, opts.retry_shitlisted()),
+		("", nice_overread, ! opts.no_overread()),
 		("Rip Passes:", nice_passes, true),
 		("Read Order:", nice_read_order, true),
+		("Read Speed:", nice_speed, 0 != opts.speed()),
 		("Verbose:", nice_verbose, opts.verbose()),
 		("Destination:", nice_output, true),
+		("", nice_lossy, opts.lossy()),
+		("", nice_single, opts.single_file()),
+		("", nice_bwf, opts.bwf()),
+		("", nice_info, opts.wav_info()),
 	];
 	let max_label = set.iter().map(|(k, _, _)| k.len()).max().unwrap_or(0);
 
@@ -304,7 +714,7 @@ fn rip_summary(disc: &Disc, opts: &RipOptions) -> Result<(), RipRipError> {
 	}
 
 	// One last chance to bail!
-	if Msg::plain("\x1b[1;38;5;199m…Hooray?\x1b[0m").eprompt_with_default(true) {
+	if ! prompt || Msg::plain("\x1b[1;38;5;199m…Hooray?\x1b[0m").eprompt_with_default(true) {
 		eprintln!("\n");
 		Ok(())
 	}
@@ -314,6 +724,183 @@ fn rip_summary(disc: &Disc, opts: &RipOptions) -> Result<(), RipRipError> {
 	}
 }
 
+/// # Offset Report.
+///
+/// Print the drive's vendor/model along with the read offset and cache size
+/// that would be used to rip with it, and where each came from.
+fn offset_report(disc: &Disc, opts: &RipOptions) {
+	let vm = disc.drive_vendor_model();
+	let nice_drive = offset_report_drive(vm, disc.drive_revision());
+	let nice_offset = offset_report_offset(vm, opts.offset(), opts.manual_offset());
+
+	let detected = [vm, disc.second_drive_vendor_model()].into_iter()
+		.filter_map(|vm| vm.and_then(|vm| vm.detect_cache()))
+		.max();
+	let nice_cache = match (opts.cache().map(std::num::NonZeroU16::get), detected) {
+		(Some(c), Some(d)) if c == d => format!(
+			"{} KiB \x1b[0;2m(Drive Database)",
+			NiceU16::from(c),
+		),
+		(Some(c), Some(d)) => format!(
+			"{} KiB \x1b[0;2m(\x1b[0;1;93m-c/--cache Override\x1b[0;2m; detected {} KiB)",
+			NiceU16::from(c),
+			NiceU16::from(d),
+		),
+		(Some(c), None) => format!(
+			"{} KiB \x1b[0;2m(\x1b[0;1;93m-c/--cache\x1b[0;2m; not in the database)",
+			NiceU16::from(c),
+		),
+		(None, Some(d)) => format!(
+			"Disabled \x1b[0;2m(\x1b[0;1;93m-c/--cache Override\x1b[0;2m; detected {} KiB)",
+			NiceU16::from(d),
+		),
+		(None, None) => "Disabled \x1b[0;2m(not in the database)".to_owned(),
+	};
+
+	eprintln!("\x1b[1;38;5;199mOffset Report\x1b[0m");
+	eprintln!("  Drive:        \x1b[1m{nice_drive}\x1b[0m");
+	eprintln!("  Read Offset:  \x1b[1m{nice_offset}\x1b[0m");
+	eprintln!("  Cache Bust:   \x1b[1m{nice_cache}\x1b[0m");
+	if let Some(offset) = disc.second_offset() {
+		let vm = disc.second_drive_vendor_model();
+		eprintln!(
+			"  Second Drive: \x1b[1m{}\x1b[0m\n                \x1b[1m{}\x1b[0m",
+			offset_report_drive(vm, None),
+			offset_report_offset(vm, offset, disc.second_offset_manual()),
+		);
+	}
+}
+
+/// # Offset Report: Drive.
+fn offset_report_drive(vm: Option<DriveVendorModel>, rev: Option<String>) -> String {
+	let vm = vm.map_or_else(String::new, |vm| vm.to_string());
+	if vm.is_empty() { return "Unknown".to_owned(); }
+	rev.map_or_else(|| vm.clone(), |r| format!("{vm} \x1b[0;2m(rev. {r})"))
+}
+
+/// # Offset Report: Read Offset.
+///
+/// Format the offset in effect, noting whether it came from the user's
+/// `drive-offsets.txt`, the built-in offset list, or the command line.
+fn offset_report_offset(vm: Option<DriveVendorModel>, offset: ReadOffset, manual: bool)
+-> String {
+	let detected = vm.and_then(|vm| vm.detect_offset());
+	let user = vm.and_then(|vm| vm.user_offset());
+	match detected {
+		Some(d) if ! manual => format!(
+			"{:+} \x1b[0;2m({})",
+			offset.samples(),
+			if user == Some(d) {
+				user_offsets_path().map_or_else(
+					|_| Cow::Borrowed("drive-offsets.txt"),
+					|p| Cow::Owned(p.to_string_lossy().into_owned()),
+				)
+			}
+			else { Cow::Borrowed("AccurateRip Database") },
+		),
+		Some(d) => format!(
+			"{:+} \x1b[0;2m(\x1b[0;1;93m-o/--offset Override\x1b[0;2m; detected {:+})",
+			offset.samples(),
+			d.samples(),
+		),
+		None if ! manual => "+0 \x1b[0;2m(Default; not in the database)".to_owned(),
+		None => format!(
+			"{:+} \x1b[0;2m(\x1b[0;1;93m-o/--offset\x1b[0;2m; not in the database)",
+			offset.samples(),
+		),
+	}
+}
+
+/// # Rip Summary Accessible Range.
+///
+/// Format the (normalized) range of samples the drive can actually reach,
+/// noting any truncation caused by the read offset.
+///
+/// Note: this value assumes ASCII bold and clear codes will be appended to
+/// either end prior to print.
+fn rip_summary_access(disc: &Disc, opts: &RipOptions) -> String {
+	let offset = opts.offset();
+	let Some(rng) = disc.accessible_range(offset) else {
+		return "\x1b[0;91mNone?!".to_owned();
+	};
+
+	let start = NiceU32::from(u32::try_from(rng.start).unwrap_or_default());
+	let end = NiceU32::from(u32::try_from(rng.end - 1).unwrap_or_default());
+	let lost = offset.samples_abs();
+	if lost == 0 { format!("Samples {start}–{end}") }
+	else {
+		format!(
+			"Samples {start}–{end} \x1b[0;2m(\x1b[0;1;93m{} lost at the {}\x1b[0;2m)",
+			NiceU16::from(lost),
+			if offset.is_negative() { "start" } else { "end" },
+		)
+	}
+}
+
+/// # Rip Summary: Cache Bust.
+fn rip_summary_cache(opts: &RipOptions) -> Cow<'static, str> {
+	opts.cache().map_or(
+		Cow::Borrowed("Disabled"),
+		|c| Cow::Owned(format!(
+			"{} KiB \x1b[0;2m({})",
+			NiceU16::from(c.get()),
+			opts.cache_bust_mode().as_str(),
+		))
+	)
+}
+
+/// # Rip Summary: Verification.
+///
+/// List the checksum databases that will be consulted, if any.
+fn rip_summary_chk(opts: &RipOptions) -> Cow<'static, str> {
+	match (opts.no_accuraterip(), opts.no_ctdb()) {
+		(false, false) => Cow::Owned(format!(
+			"AccurateRip/CTDB cf. {}+ \x1b[0;2m(CTDB ±{})",
+			opts.confidence(),
+			opts.ctdb_shift(),
+		)),
+		(false, true) => Cow::Owned(format!(
+			"AccurateRip cf. {}+ \x1b[0;2m(CTDB skipped)",
+			opts.confidence(),
+		)),
+		(true, false) => Cow::Owned(format!(
+			"CTDB cf. {}+ \x1b[0;2m(±{}; AccurateRip skipped)",
+			opts.confidence(),
+			opts.ctdb_shift(),
+		)),
+		(true, true) => Cow::Borrowed("\x1b[0;91mNone\x1b[0;2m (AccurateRip and CTDB skipped)"),
+	}
+}
+
+/// # Rip Summary C2.
+///
+/// Describe the C2 error pointer handling, noting when strict mode only kicks
+/// in after a certain pass.
+fn rip_summary_c2(opts: &RipOptions) -> Cow<'static, str> {
+	if opts.strict() { Cow::Borrowed("C2 Error Pointers \x1b[0;2m(\x1b[0;1;93mSector\x1b[0;2m)") }
+	else if let Some(n) = opts.strict_after() {
+		Cow::Owned(format!("C2 Error Pointers \x1b[0;2m(\x1b[0;1mSample\x1b[0;2m, \x1b[0;1;93mSector\x1b[0;2m from pass #{n})"))
+	}
+	else { Cow::Borrowed("C2 Error Pointers \x1b[0;2m(\x1b[0;1mSample\x1b[0;2m)") }
+}
+
+/// # Rip Summary Output Directory.
+///
+/// Format the output directory, relative to the current working directory
+/// if possible.
+///
+/// ## Errors
+///
+/// This will return an error if the directory cannot be determined.
+fn rip_summary_output_dir() -> Result<String, RipRipError> {
+	let dir = riprip_core::output_dir()?;
+	Ok(
+		std::env::current_dir().ok()
+			.and_then(|cwd| dir.strip_prefix(cwd).ok().map(|d| format!("./{}", d.to_string_lossy())))
+			.unwrap_or_else(|| dir.to_string_lossy().into_owned())
+	)
+}
+
 /// # Rip Summary Tracks.
 ///
 /// Format the desired tracks into a compact string.
@@ -377,6 +964,23 @@ fn sigint(killed: Arc<AtomicBool>, progress: Option<Progless>) {
 	);
 }
 
+/// # Hook Up SIGUSR1.
+///
+/// Each SIGUSR1 toggles the pause state. Rather than messing with (unsafe)
+/// signal handlers, the signal is blocked and fielded by a dedicated thread.
+///
+/// Note: this needs to be called before any other threads are spawned so they
+/// inherit the block.
+fn sigusr1(paused: Arc<AtomicBool>) {
+	let mut set = SigSet::empty();
+	set.add(Signal::SIGUSR1);
+	if set.thread_block().is_ok() {
+		std::thread::spawn(move || while set.wait().is_ok() {
+			paused.fetch_xor(true, SeqCst);
+		});
+	}
+}
+
 /// # Hide Cursor.
 ///
 /// This helps control the hiding and showing of the cursor during progress
@@ -2,16 +2,26 @@
 # Rip Rip Hooray: Kill Switch
 */
 
-use std::sync::{
-	Arc,
-	atomic::{
-		AtomicBool,
-		Ordering::Acquire,
+use std::{
+	sync::{
+		Arc,
+		atomic::{
+			AtomicBool,
+			Ordering::Acquire,
+		},
 	},
+	time::Duration,
 };
 
 
 
+/// # Pause Check Interval.
+///
+/// How long to sleep between checks while paused.
+const PAUSE_SLEEP: Duration = Duration::from_millis(100);
+
+
+
 #[derive(Debug)]
 /// # Kill Switch.
 ///
@@ -20,18 +30,48 @@ use std::sync::{
 ///
 /// The main program's CTRL-C intercept sets the value, allowing Rip Rip to
 /// tidy up before dying.
-pub struct KillSwitch(Arc<AtomicBool>);
+///
+/// It also holds a softer pause state — toggled by the main program's SIGUSR1
+/// intercept — which halts ripping between sectors until released.
+pub struct KillSwitch {
+	/// # Killed?
+	killed: Arc<AtomicBool>,
+
+	/// # Paused?
+	paused: Arc<AtomicBool>,
+}
 
 impl Default for KillSwitch {
-	fn default() -> Self { Self(Arc::from(AtomicBool::new(false))) }
+	fn default() -> Self {
+		Self {
+			killed: Arc::from(AtomicBool::new(false)),
+			paused: Arc::from(AtomicBool::new(false)),
+		}
+	}
 }
 
 impl KillSwitch {
 	#[must_use]
 	/// # Dead?
-	pub fn killed(&self) -> bool { self.0.load(Acquire) }
+	pub fn killed(&self) -> bool { self.killed.load(Acquire) }
 
 	#[must_use]
 	/// # Inner Clone.
-	pub fn inner(&self) -> Arc<AtomicBool> { Arc::clone(&self.0) }
+	pub fn inner(&self) -> Arc<AtomicBool> { Arc::clone(&self.killed) }
+
+	#[must_use]
+	/// # Paused?
+	pub fn paused(&self) -> bool { self.paused.load(Acquire) }
+
+	#[must_use]
+	/// # Inner Clone (Paused).
+	pub fn inner_paused(&self) -> Arc<AtomicBool> { Arc::clone(&self.paused) }
+
+	/// # Wait While Paused.
+	///
+	/// Sleep in short bursts until the pause is lifted (or the kill switch is
+	/// thrown).
+	pub(crate) fn wait_while_paused(&self) {
+		while self.paused() && ! self.killed() { std::thread::sleep(PAUSE_SLEEP); }
+	}
 }
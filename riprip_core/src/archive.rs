@@ -0,0 +1,405 @@
+/*!
+# Rip Rip Hooray: Archive Verification
+*/
+
+use cdtoc::{
+	Toc,
+	Track,
+};
+use crate::{
+	ar_unlisted_note,
+	chk_accuraterip,
+	chk_ctdb,
+	fmt_ar,
+	fmt_ctdb,
+	RipRipError,
+	ReadOffset,
+	RipSample,
+	SAMPLE_OVERREAD,
+	track_path,
+};
+use std::path::{
+	Path,
+	PathBuf,
+};
+
+
+
+/// # Verify Archived Rip.
+///
+/// Re-verify previously exported track WAVs against AccurateRip and CUETools
+/// using nothing but the `CDTOC` string printed in the disc summary. No disc
+/// (or drive) is required.
+///
+/// The tracks are expected to live wherever Rip Rip would have saved them,
+/// i.e. `_riprip/` under the current working directory, with their original
+/// file names.
+///
+/// Tracks that are missing or unreadable are noted and skipped; the rest are
+/// printed alongside their confidences.
+///
+/// ## Errors
+///
+/// This will return an error if the TOC is invalid or none of the tracks
+/// could be loaded.
+pub fn verify_archive(cdtoc: &str) -> Result<(), RipRipError> {
+	let toc = Toc::from_cdtoc(cdtoc)?;
+	let tracks = toc.audio_tracks().collect::<Vec<Track>>();
+
+	// Load everything upfront; the CUETools check needs to borrow a little
+	// from each neighbor.
+	let mut saved: Vec<(PathBuf, Option<Vec<RipSample>>)> = Vec::with_capacity(tracks.len());
+	for &track in &tracks {
+		let dst = track_path(&toc, track)?;
+		// The first track might have the HTOA merged into it.
+		let data = read_wav(&dst, track).or_else(||
+			toc.htoa()
+				.filter(|_| track.position().is_first())
+				.and_then(|htoa| usize::try_from(htoa.duration().samples()).ok())
+				.and_then(|pregap| read_wav_pregap(&dst, track, pregap))
+		);
+		saved.push((dst, data));
+	}
+
+	verify_saved(&toc, &tracks, &saved)
+}
+
+/// # Verify Existing WAVs.
+///
+/// Like `verify_archive`, but for track WAVs living anywhere — e.g. those
+/// produced by some other ripper — with any names.
+///
+/// The `*.wav` files in `dir` are sorted by name and paired with the audio
+/// tracks in order. If there is one extra file and the disc has an HTOA, the
+/// first is assumed to be that and is skipped.
+///
+/// Most rippers apply the drive's read offset before saving, so by default
+/// the data is verified as-is. If the files were saved _without_ the offset,
+/// pass the drive's offset and the data will be shifted across the track
+/// boundaries accordingly before verification. (Samples shifted past either
+/// end of the disc are treated as silence.)
+///
+/// ## Errors
+///
+/// This will return an error if the TOC is invalid, the number of files
+/// doesn't match the number of tracks, or none of the tracks could be
+/// loaded.
+pub fn verify_existing<P>(cdtoc: &str, dir: P, offset: ReadOffset)
+-> Result<(), RipRipError>
+where P: AsRef<Path> {
+	let toc = Toc::from_cdtoc(cdtoc)?;
+	let tracks = toc.audio_tracks().collect::<Vec<Track>>();
+
+	// Find the WAVs.
+	let mut files: Vec<PathBuf> = std::fs::read_dir(dir.as_ref())
+		.map(|dir|
+			dir.filter_map(|e| e.ok().map(|e| e.path()))
+				.filter(|p|
+					p.is_file() &&
+					p.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav"))
+				)
+				.collect()
+		)
+		.unwrap_or_default();
+	files.sort_unstable();
+	if files.len() == tracks.len() + 1 && toc.htoa().is_some() { files.remove(0); }
+	if files.len() != tracks.len() {
+		return Err(RipRipError::WavCount(toc.audio_len(), files.len()));
+	}
+
+	let mut saved: Vec<(PathBuf, Option<Vec<RipSample>>)> = files.into_iter()
+		.zip(tracks.iter())
+		.map(|(src, &track)| {
+			let data = read_wav(&src, track);
+			(src, data)
+		})
+		.collect();
+	if offset.samples() != 0 { shift_saved(&tracks, &mut saved, offset.samples()); }
+
+	verify_saved(&toc, &tracks, &saved)
+}
+
+/// # Verify Saved.
+///
+/// This does the actual work for `verify_archive` and `verify_existing`,
+/// checking and printing the results for each of the loaded tracks.
+///
+/// ## Errors
+///
+/// This will return an error if none of the tracks could be loaded.
+fn verify_saved(toc: &Toc, tracks: &[Track], saved: &[(PathBuf, Option<Vec<RipSample>>)])
+-> Result<(), RipRipError> {
+	use std::io::Write;
+
+	if saved.iter().all(|(_, data)| data.is_none()) { return Err(RipRipError::Noop); }
+
+	let writer = std::io::stderr();
+	let mut handle = writer.lock();
+	let col1 = saved.iter().map(|(dst, _)| dst.to_string_lossy().len()).max().unwrap_or(0);
+	let _res = writeln!(&mut handle, "\nThe fruits of your (past) labor:");
+
+	let mut total = 0;
+	let mut good = 0;
+	for (k, &track) in tracks.iter().enumerate() {
+		let (dst, data) = &saved[k];
+		let Some(data) = data.as_deref() else {
+			let _res = writeln!(
+				&mut handle,
+				"  \x1b[2m{:<col1$}\x1b[0m    \x1b[0;91mmissing or invalid\x1b[0m",
+				dst.display(),
+			);
+			continue;
+		};
+
+		// CUETools wants the neighboring samples too.
+		let prev = k.checked_sub(1).and_then(|k| saved[k].1.as_deref());
+		let next = saved.get(k + 1).and_then(|(_, d)| d.as_deref());
+		let padded = pad_track(prev, data, next);
+
+		let (ar, ctdb) = std::thread::scope(|s| {
+			let ar = s.spawn(|| chk_accuraterip(toc, track, data));
+			let ctdb = s.spawn(|| chk_ctdb(toc, track, &padded, false, SAMPLE_OVERREAD));
+			(
+				ar.join().ok().flatten().map(|((v1, v2), _)| (v1.min(99), v2.min(99))),
+				ctdb.join().ok().flatten().map(|(v1, _)| v1.min(999)),
+			)
+		});
+
+		total += 1;
+		if
+			ar.is_some_and(|(v1, v2)| v1 != 0 || v2 != 0) ||
+			ctdb.is_some_and(|v| v != 0)
+		{ good += 1; }
+
+		let _res = writeln!(
+			&mut handle,
+			"  \x1b[2m{:<col1$}\x1b[0m{}{}",
+			dst.display(),
+			fmt_ar(ar),
+			fmt_ctdb(ctdb),
+		);
+	}
+
+	let _res = writeln!(
+		&mut handle,
+		"  {line: >col1$}  AccurateRip  CUETools  \x1b[2m(\x1b[0m{good}\x1b[2m/\x1b[0m{total}\x1b[2m)\x1b[0m",
+		line="",
+	);
+	if let Some(note) = ar_unlisted_note(toc) {
+		let _res = write!(&mut handle, "{note}");
+	}
+	let _res = writeln!(&mut handle).and_then(|()| handle.flush());
+
+	Ok(())
+}
+
+
+
+/// # Pad Track.
+///
+/// Return a copy of the track data with `SAMPLE_OVERREAD` samples from the
+/// neighboring tracks on either side, mirroring the rip range used during
+/// the original rip. Missing neighbors are padded with nulls.
+fn pad_track(prev: Option<&[RipSample]>, data: &[RipSample], next: Option<&[RipSample]>)
+-> Vec<RipSample> {
+	let pad = usize::from(SAMPLE_OVERREAD);
+	let mut out = Vec::with_capacity(data.len() + pad * 2);
+
+	// Start.
+	let before = prev.map_or(&[][..], |p| &p[p.len().saturating_sub(pad)..]);
+	out.resize(pad - before.len(), RipSample::Lead);
+	out.extend_from_slice(before);
+
+	// Middle.
+	out.extend_from_slice(data);
+
+	// End.
+	let after = next.map_or(&[][..], |n| &n[..n.len().min(pad)]);
+	out.extend_from_slice(after);
+	out.resize(out.len() + pad - after.len(), RipSample::Lead);
+
+	out
+}
+
+/// # Shift Saved.
+///
+/// Apply a read offset to the loaded track data, treating it as one
+/// continuous stream so samples can cross track boundaries. Missing tracks
+/// contribute silence, but stay missing.
+fn shift_saved(tracks: &[Track], saved: &mut [(PathBuf, Option<Vec<RipSample>>)], offset: i16) {
+	// Stitch everything together.
+	let lens: Vec<usize> = tracks.iter()
+		.map(|t| usize::try_from(t.duration().samples()).unwrap_or(0))
+		.collect();
+	let mut stream = Vec::with_capacity(lens.iter().sum());
+	for (&len, (_, data)) in lens.iter().zip(saved.iter()) {
+		if let Some(data) = data { stream.extend_from_slice(data); }
+		else { stream.resize(stream.len() + len, RipSample::Lead); }
+	}
+
+	// Shift it. A positive offset means the data we want is further along.
+	let abs = usize::from(offset.unsigned_abs());
+	if 0 < offset {
+		stream.drain(..abs.min(stream.len()));
+		stream.resize(stream.len() + abs, RipSample::Lead);
+	}
+	else {
+		let len = stream.len();
+		stream.truncate(len.saturating_sub(abs));
+		stream.splice(0..0, std::iter::repeat_n(RipSample::Lead, abs));
+	}
+
+	// And split it back up.
+	let mut rest = stream.as_slice();
+	for (&len, (_, data)) in lens.iter().zip(saved.iter_mut()) {
+		if let Some(data) = data { data.clone_from_slice(&rest[..len]); }
+		rest = &rest[len..];
+	}
+}
+
+/// # Read WAV.
+///
+/// Parse a stereo 44.1kHz WAV file, returning its samples, or `None` if it
+/// is missing, in some other format, or the wrong length for the track.
+///
+/// Files upconverted to 24 or 32 bits are shifted back down to 16.
+pub(crate) fn read_wav(src: &Path, track: Track) -> Option<Vec<RipSample>> {
+	read_wav_pregap(src, track, 0)
+}
+
+/// # Read WAV (With Pregap).
+///
+/// Same as `read_wav`, but for a file with `pregap` extra samples ahead of
+/// the track proper — e.g. a merged HTOA — which are skipped.
+pub(crate) fn read_wav_pregap(src: &Path, track: Track, pregap: usize)
+-> Option<Vec<RipSample>> {
+	let raw = std::fs::read(src).ok()?;
+	let (bits, pcm) = wav_pcm(&raw)?;
+	let width = usize::from(bits / 8);
+	let expected = usize::try_from(track.duration().samples()).ok()?.checked_add(pregap)?;
+	if pcm.len() != expected * width * 2 { return None; }
+
+	Some(
+		pcm.chunks_exact(width * 2)
+			.skip(pregap)
+			.map(|c| {
+				let (l, r) = c.split_at(width);
+				RipSample::from([l[width - 2], l[width - 1], r[width - 2], r[width - 1]])
+			})
+			.collect()
+	)
+}
+
+/// # WAV PCM Data.
+///
+/// Walk the RIFF chunks, making sure the format is CD audio — possibly
+/// upconverted to 24 or 32 bits — and return the bit depth and raw PCM data.
+pub(crate) fn wav_pcm(raw: &[u8]) -> Option<(u8, &[u8])> {
+	if raw.len() < 12 || &raw[..4] != b"RIFF" || &raw[8..12] != b"WAVE" { return None; }
+
+	let mut bits = None;
+	let mut rest = &raw[12..];
+	while 8 <= rest.len() {
+		let id = &rest[..4];
+		let len = usize::try_from(u32::from_le_bytes([rest[4], rest[5], rest[6], rest[7]])).ok()?;
+		let body = rest.get(8..8 + len)?;
+
+		if id == b"fmt " {
+			// PCM, two channels, 44100Hz, 16, 24, or 32 bits.
+			bits =
+				if
+					16 <= body.len() &&
+					body[..4] == [1, 0, 2, 0] &&
+					body[4..8] == 44_100_u32.to_le_bytes() &&
+					matches!(body[14..16], [16 | 24 | 32, 0])
+				{ Some(body[14]) }
+				else { None };
+		}
+		else if id == b"data" {
+			return bits.map(|b| (b, body));
+		}
+
+		// Chunks are padded to even lengths.
+		rest = rest.get(8 + len + (len & 1)..)?;
+	}
+
+	None
+}
+
+
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn t_wav_pcm() {
+		let mut raw = Vec::new();
+		raw.extend_from_slice(b"RIFF\0\0\0\0WAVE");
+		raw.extend_from_slice(b"fmt \x10\0\0\0");
+		raw.extend_from_slice(&[1, 0, 2, 0]);
+		raw.extend_from_slice(&44_100_u32.to_le_bytes());
+		raw.extend_from_slice(&176_400_u32.to_le_bytes());
+		raw.extend_from_slice(&[4, 0, 16, 0]);
+		raw.extend_from_slice(b"data\x08\0\0\0");
+		raw.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+		assert_eq!(wav_pcm(&raw), Some((16, &[1, 2, 3, 4, 5, 6, 7, 8][..])));
+
+		// Mono is no good.
+		raw[22] = 1;
+		assert_eq!(wav_pcm(&raw), None);
+
+		// Neither is garbage.
+		assert_eq!(wav_pcm(b"RIFF1234WAV"), None);
+	}
+
+	#[test]
+	fn t_shift_saved() {
+		let toc = Toc::from_cdtoc("2+96+1F6+2F6").expect("Invalid TOC.");
+		let tracks = toc.audio_tracks().collect::<Vec<Track>>();
+		let lens: Vec<usize> = tracks.iter()
+			.map(|t| usize::try_from(t.duration().samples()).unwrap())
+			.collect();
+
+		// Number each sample so we can see where it ends up.
+		let mut idx = 0_u32;
+		let mut saved: Vec<(PathBuf, Option<Vec<RipSample>>)> = lens.iter()
+			.map(|&len| {
+				let data = (0..len).map(|_| {
+					idx += 1;
+					RipSample::from(idx.to_le_bytes())
+				}).collect();
+				(PathBuf::new(), Some(data))
+			})
+			.collect();
+
+		// Forward.
+		shift_saved(&tracks, &mut saved, 2);
+		let one = saved[0].1.as_deref().unwrap();
+		let two = saved[1].1.as_deref().unwrap();
+		assert_eq!(one[0].as_array(), 3_u32.to_le_bytes());
+		assert_eq!(two[0].as_array(), u32::try_from(lens[0] + 3).unwrap().to_le_bytes());
+		assert!(matches!(two[lens[1] - 1], RipSample::Lead));
+
+		// And back again.
+		shift_saved(&tracks, &mut saved, -2);
+		let one = saved[0].1.as_deref().unwrap();
+		let two = saved[1].1.as_deref().unwrap();
+		assert!(matches!(one[0], RipSample::Lead));
+		assert_eq!(one[2].as_array(), 3_u32.to_le_bytes());
+		assert_eq!(two[0].as_array(), u32::try_from(lens[0] + 1).unwrap().to_le_bytes());
+	}
+
+	#[test]
+	fn t_pad_track() {
+		let pad = usize::from(SAMPLE_OVERREAD);
+		let prev = vec![RipSample::from([1, 1, 1, 1]); pad + 5];
+		let data = vec![RipSample::from([2, 2, 2, 2]); 10];
+
+		let out = pad_track(Some(&prev), &data, None);
+		assert_eq!(out.len(), pad * 2 + 10);
+		assert!(out[..pad].iter().all(|v| v.as_array() == [1, 1, 1, 1]));
+		assert!(out[pad..pad + 10].iter().all(|v| v.as_array() == [2, 2, 2, 2]));
+		assert!(out[pad + 10..].iter().all(|v| matches!(v, RipSample::Lead)));
+	}
+}
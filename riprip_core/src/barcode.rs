@@ -45,6 +45,16 @@ impl fmt::Display for Barcode {
 	}
 }
 
+impl Barcode {
+	#[must_use]
+	/// # As Str.
+	///
+	/// Return the barcode as a plain, unformatted 13-digit string.
+	pub fn as_str(&self) -> &str {
+		std::str::from_utf8(self.0.as_slice()).unwrap_or_default()
+	}
+}
+
 impl TryFrom<&[u8]> for Barcode {
 	type Error = RipRipError;
 	fn try_from(mut src: &[u8]) -> Result<Self, Self::Error> {
@@ -76,6 +86,30 @@ impl TryFrom<&str> for Barcode {
 	}
 }
 
+impl Barcode {
+	/// # Parse MCN.
+	///
+	/// Parse a raw media catalog number, returning the `Barcode` if valid, or
+	/// failing that, the (trimmed) raw text as the error so it isn't lost
+	/// entirely.
+	///
+	/// Blank, all-zero, and non-printable values are ignored altogether, as
+	/// are any containing quotes, which would break the cue/TOC lines.
+	pub(crate) fn parse_mcn(src: &str) -> Option<Result<Self, String>> {
+		if let Ok(out) = Self::try_from(src) { return Some(Ok(out)); }
+
+		let src = src.trim_matches(|c: char| c.is_ascii_whitespace() || c == '\0');
+		if
+			src.is_empty() ||
+			src.bytes().all(|b| b == b'0') ||
+			! src.bytes().all(|b| b.is_ascii_graphic() && b != b'"')
+		{
+			None
+		}
+		else { Some(Err(src.to_owned())) }
+	}
+}
+
 
 
 /// # Is EAN13?
@@ -133,4 +167,22 @@ mod tests {
 		let bc = Barcode::try_from("0018861006529").expect("Barcode failed.");
 		assert_eq!(bc.to_string(), "0-18861-00652-9");
 	}
+
+	#[test]
+	fn t_parse_mcn() {
+		assert_eq!(
+			Barcode::parse_mcn(" 0018861006529\0"),
+			Some(Ok(Barcode::try_from("0018861006529").expect("Barcode failed."))),
+		);
+
+		// Bad check digits and odd lengths are kept as-is.
+		assert_eq!(Barcode::parse_mcn("0089218545555"), Some(Err("0089218545555".to_owned())));
+		assert_eq!(Barcode::parse_mcn(" 12345\0\0"), Some(Err("12345".to_owned())));
+
+		// But not nothing.
+		assert_eq!(Barcode::parse_mcn(""), None);
+		assert_eq!(Barcode::parse_mcn("0000000000000"), None);
+		assert_eq!(Barcode::parse_mcn("123\"45"), None);
+		assert_eq!(Barcode::parse_mcn("12\x0145"), None);
+	}
 }
@@ -13,11 +13,25 @@ use crate::{
 };
 use fyi_msg::Msg;
 use std::{
+	fs::{
+		File,
+		TryLockError,
+	},
+	io::Write,
+	ops::Range,
 	path::{
 		Path,
 		PathBuf,
 	},
-	sync::OnceLock,
+	sync::{
+		atomic::{
+			AtomicBool,
+			AtomicU64,
+			Ordering::Relaxed,
+		},
+		OnceLock,
+	},
+	time::Duration,
 };
 use tempfile::NamedTempFile;
 
@@ -25,14 +39,157 @@ use tempfile::NamedTempFile;
 
 /// # Cache Root.
 ///
-/// This will ultimately hold `CWD/CACHE_BASE`.
+/// This will ultimately hold `CWD/CACHE_BASE`, unless overridden by
+/// `ENV_OUTPUT_DIR`.
 static CACHE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
 
+/// # Cache Root Fallback?
+///
+/// This is set if `CACHE_ROOT` had to fall back to the temporary directory
+/// because the current working directory was missing.
+static CACHE_FALLBACK: AtomicBool = AtomicBool::new(false);
+
+/// # Scratch Root.
+///
+/// This will ultimately hold `CACHE_ROOT/CACHE_SCRATCH`, unless overridden by
+/// `ENV_SCRATCH_DIR`.
+static SCRATCH_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// # Output Directory Environment Variable.
+///
+/// The exported tracks, cue sheets, etc., are saved here.
+const ENV_OUTPUT_DIR: &str = "RIPRIP_OUTPUT_DIR";
+
+/// # Scratch Directory Environment Variable.
+///
+/// The rip states, checksums, etc., are saved here.
+const ENV_SCRATCH_DIR: &str = "RIPRIP_SCRATCH_DIR";
+
+/// # Lock Retry Delay.
+///
+/// How long `CacheLock::new` waits before its second (and last) attempt.
+const LOCK_RETRY: Duration = Duration::from_secs(1);
+
 /// # Cache Prefix.
 ///
 /// The formatted CDDB ID for the current disc.
 static CACHE_PREFIX: OnceLock<String> = OnceLock::new();
 
+/// # Durable Saves.
+///
+/// When true, `CacheWriter::finish` will fsync the data before (and after)
+/// swapping it into place.
+static DURABLE: AtomicBool = AtomicBool::new(false);
+
+/// # Bytes Saved.
+///
+/// A running total of the bytes saved by `CacheWriter` this run, so there's
+/// something to report if the disk fills up.
+static SAVED: AtomicU64 = AtomicU64::new(0);
+
+
+
+/// # Cache Lock.
+///
+/// This holds an advisory lock on a per-disc file in the scratch directory to
+/// keep concurrent rips from clobbering each other's state. The lock is
+/// released on drop.
+///
+/// The file itself is left in place; unlinking a locked file would let a
+/// newcomer lock a fresh inode at the same path while a straggler still holds
+/// the old one.
+///
+/// The OS releases advisory locks automatically when a process exits, even
+/// uncleanly, so there's no such thing as a stale lock; one that's held is
+/// held by somebody, possibly in another container or on another host
+/// sharing the directory.
+pub(super) struct CacheLock {
+	/// # Lock File.
+	file: File,
+}
+
+impl Drop for CacheLock {
+	fn drop(&mut self) { let _res = self.file.unlock(); }
+}
+
+impl CacheLock {
+	/// # New Lock.
+	///
+	/// Acquire the lock for the disc. If `retry` is true and the lock is held,
+	/// it is tried once more after a short pause — in case its holder was just
+	/// exiting — and if still held, the PID recorded by the holder is
+	/// reported.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the lock is already held by another
+	/// process, or there are I/O problems.
+	pub(super) fn new(toc: &Toc, retry: bool) -> Result<Self, RipRipError> {
+		let path = scratch_path(format!("{}.lock", cache_prefix(toc)))?;
+		let err = || RipRipError::CachePath(path.to_string_lossy().into_owned());
+
+		// Make sure the scratch directory exists.
+		let parent = path.parent().ok_or_else(err)?;
+		if ! parent.is_dir() {
+			std::fs::create_dir_all(parent).map_err(|_| err())?;
+		}
+
+		match Self::try_new(&path) {
+			Err(RipRipError::Locked) if retry => {
+				std::thread::sleep(LOCK_RETRY);
+				let res = Self::try_new(&path);
+				if matches!(res, Err(RipRipError::Locked)) {
+					if let Some(pid) = lock_holder(&path) {
+						Msg::warning(format!(
+							"The lock is held by process {pid} (which may be running in another container or on another host).",
+						)).eprint();
+					}
+				}
+				res
+			},
+			res => res,
+		}
+	}
+
+	/// # Try Lock.
+	///
+	/// Open (or create) the lock file and try to lock it, recording our PID
+	/// inside on success.
+	fn try_new(path: &Path) -> Result<Self, RipRipError> {
+		let err = || RipRipError::CachePath(path.to_string_lossy().into_owned());
+		let mut file = File::options()
+			.create(true)
+			.truncate(false)
+			.read(true)
+			.write(true)
+			.open(path)
+			.map_err(|_| err())?;
+
+		match file.try_lock() {
+			Ok(()) => {
+				// The PID is informational; the lock is what matters.
+				let _res = file.set_len(0)
+					.and_then(|()| write!(file, "{}", std::process::id()))
+					.and_then(|()| file.flush());
+				Ok(Self { file })
+			},
+			Err(TryLockError::WouldBlock) => Err(RipRipError::Locked),
+			Err(TryLockError::Error(_)) => Err(err()),
+		}
+	}
+}
+
+/// # Lock Holder.
+///
+/// Return the PID recorded in the lock file by whoever holds it, if any. This
+/// is informational only; it might belong to a different PID namespace or
+/// host entirely.
+fn lock_holder(path: &Path) -> Option<u32> {
+	std::fs::read_to_string(path).ok()
+		.and_then(|v| v.trim().parse::<u32>().ok())
+		.filter(|&v| v != std::process::id())
+}
+
 
 
 /// # Cache Writer.
@@ -45,6 +202,9 @@ pub(super) struct CacheWriter<'a> {
 
 	/// # Tempfile.
 	tmp: NamedTempFile,
+
+	/// # Keep Backup?
+	backup: bool,
 }
 
 impl<'a> CacheWriter<'a> {
@@ -72,15 +232,26 @@ impl<'a> CacheWriter<'a> {
 		// If that doesn't exist, try to create it.
 		if ! parent.is_dir() {
 			std::fs::create_dir_all(parent)
-				.map_err(|_| RipRipError::CachePath(dst.to_string_lossy().into_owned()))?;
+				.map_err(|e| write_error(&e, dst, || RipRipError::CachePath(dst.to_string_lossy().into_owned())))?;
 		}
 
 		// Make a tempfile.
 		let tmp = tempfile::Builder::new().tempfile_in(parent)
-			.map_err(|_| RipRipError::CachePath(dst.to_string_lossy().into_owned()))?;
+			.map_err(|e| write_error(&e, dst, || RipRipError::CachePath(dst.to_string_lossy().into_owned())))?;
 
 		// We should be good!
-		Ok(Self { dst, tmp })
+		Ok(Self { dst, tmp, backup: false })
+	}
+
+	#[must_use]
+	/// # With Backup.
+	///
+	/// Preserve the previous version of the destination file — if any — as a
+	/// `.bak` sibling when finishing, so there's something to fall back on if
+	/// the new copy winds up corrupted.
+	pub(super) const fn with_backup(mut self) -> Self {
+		self.backup = true;
+		self
 	}
 
 	/// # Writer Reference.
@@ -93,20 +264,93 @@ impl<'a> CacheWriter<'a> {
 	/// Flush the data (just in case) and permanently save the contents to
 	/// `self.dst`.
 	pub(super) fn finish(mut self) -> Result<(), RipRipError> {
-		use std::io::Write;
+		let err = || RipRipError::CachePath(self.dst.to_string_lossy().into_owned());
 
 		// Flush for good measure.
-		self.tmp.flush()
-			.map_err(|_| RipRipError::CachePath(self.dst.to_string_lossy().into_owned()))?;
+		self.tmp.flush().map_err(|e| write_error(&e, self.dst, err))?;
+
+		// Make sure the data has actually hit the disk, if requested.
+		let durable = DURABLE.load(Relaxed);
+		if durable {
+			self.tmp.as_file().sync_all().map_err(|e| write_error(&e, self.dst, err))?;
+		}
+		let len = self.tmp.as_file().metadata().map_or(0, |m| m.len());
+
+		// Rotate the old copy out of the way, if requested. This is a hard
+		// link so the destination remains intact until the persist swaps it.
+		// Failure here isn't worth dying over.
+		if self.backup && self.dst.is_file() {
+			let bak = backup_path(self.dst);
+			let _res = std::fs::remove_file(&bak);
+			if std::fs::hard_link(self.dst, &bak).is_err() {
+				let _res = std::fs::copy(self.dst, &bak);
+			}
+		}
 
 		self.tmp.persist(self.dst)
-			.map(|_| ())
-			.map_err(|_| RipRipError::CachePath(self.dst.to_string_lossy().into_owned()))
+			.map_err(|e| write_error(&e.error, self.dst, err))?;
+		SAVED.fetch_add(len, Relaxed);
+
+		// The rename itself lives in the parent directory, so that needs
+		// syncing too. Not every platform allows this, so failures are
+		// ignored.
+		if durable {
+			if let Some(parent) = self.dst.parent() {
+				let _res = File::open(parent).and_then(|f| f.sync_all());
+			}
+		}
+
+		Ok(())
 	}
 }
 
 
 
+/// # Set Durable Saves.
+///
+/// Enable or disable fsyncing for all subsequent `CacheWriter` saves. This is
+/// slower, but makes sure a "saved" state actually survives a power loss.
+pub(super) fn set_durable_saves(durable: bool) { DURABLE.store(durable, Relaxed); }
+
+/// # Write Error.
+///
+/// Convert an I/O error encountered while saving `dst` into the appropriate
+/// `RipRipError`: `RipRipError::DiskFull` if the disk (or quota) is out of
+/// room, otherwise whatever `fallback` returns.
+///
+/// Running out of space isn't specific to any one file, so callers should
+/// bubble the former up rather than moving on to the next track.
+pub(super) fn write_error<F>(err: &std::io::Error, dst: &Path, fallback: F)
+-> RipRipError
+where F: FnOnce() -> RipRipError {
+	use std::io::ErrorKind;
+
+	if matches!(err.kind(), ErrorKind::StorageFull | ErrorKind::QuotaExceeded) {
+		RipRipError::DiskFull(dst.to_string_lossy().into_owned(), SAVED.load(Relaxed))
+	}
+	else { fallback() }
+}
+
+/// # Backup Path.
+///
+/// Return the path of the `.bak` sibling for `src`, i.e. the same path with
+/// `.bak` tacked onto the end.
+pub(super) fn backup_path(src: &Path) -> PathBuf {
+	let mut out = src.as_os_str().to_owned();
+	out.push(".bak");
+	PathBuf::from(out)
+}
+
+/// # Merge Ledger Path.
+///
+/// Return the path of the `.merged` sibling for the state file `src`, which
+/// lists the other states that have already been folded into it.
+pub(super) fn merged_path(src: &Path) -> PathBuf {
+	let mut out = src.as_os_str().to_owned();
+	out.push(".merged");
+	PathBuf::from(out)
+}
+
 /// # Cache Path.
 ///
 /// Glue `src` onto the cache root and return the resulting path.
@@ -119,6 +363,34 @@ where P: AsRef<Path> {
 	cache_root().map(|root| root.join(src))
 }
 
+/// # C2 Dump Path.
+///
+/// Return the file path to append raw C2 error maps to when
+/// `RipOptions::dump_c2` is enabled. Like the state, this is scratch data,
+/// named after the CDDB ID and two-digit track number.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location.
+pub(crate) fn c2_path(toc: &Toc, track: Track) -> Result<PathBuf, RipRipError> {
+	scratch_path(format!("{}__{:02}.c2", cache_prefix(toc), track.number()))
+}
+
+/// # Log Path.
+///
+/// Return the file path to append per-track log records to when
+/// `RipOptions::log_files` is enabled. Like the C2 dumps, these are scratch
+/// data, named after the CDDB ID and two-digit track number.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location.
+pub(crate) fn log_path(toc: &Toc, track: u8) -> Result<PathBuf, RipRipError> {
+	scratch_path(format!("{}__{track:02}.log", cache_prefix(toc)))
+}
+
 /// # Cache Prefix.
 ///
 /// All of the file names are prefixed with the disc's CDDB ID. This is
@@ -143,13 +415,139 @@ pub(super) fn cache_prefix(toc: &Toc) -> &'static str {
 /// This will return an error if there are problems determining the cache
 /// location.
 pub(crate) fn state_path(toc: &Toc, track: Track) -> Result<PathBuf, RipRipError> {
-	cache_path(format!(
-		"{CACHE_SCRATCH}/{}__{:02}.state",
+	scratch_path(format!(
+		"{}__{:02}.state",
 		cache_prefix(toc),
 		track.number()
 	))
 }
 
+/// # Scratch Path.
+///
+/// Glue `src` onto the scratch root and return the resulting path.
+///
+/// ## Errors
+///
+/// This will return an error if the scratch root cannot be established.
+pub(super) fn scratch_path<P>(src: P) -> Result<PathBuf, RipRipError>
+where P: AsRef<Path> {
+	scratch_root().map(|root| root.join(src))
+}
+
+/// # Scratch Files.
+///
+/// Return the paths of all files in the scratch directory, or if a prefix is
+/// provided, only those belonging to that disc.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location or reading its contents.
+pub(crate) fn scratch_files(prefix: Option<&str>) -> Result<Vec<PathBuf>, RipRipError> {
+	let dir = scratch_root()?;
+	if ! dir.is_dir() { return Ok(Vec::new()); }
+
+	let prefix = prefix.map(|p| format!("{p}__"));
+	let mut out: Vec<PathBuf> = std::fs::read_dir(dir)
+		.map_err(|_| RipRipError::CachePath(dir.to_string_lossy().into_owned()))?
+		.filter_map(|e| {
+			let e = e.ok()?;
+			if e.file_type().ok()?.is_dir() { return None; }
+
+			// Leave the locks alone.
+			let path = e.path();
+			if path.extension().is_some_and(|ext| ext == "lock") { return None; }
+
+			if let Some(p) = prefix.as_deref() {
+				if ! e.file_name().to_str()?.starts_with(p) { return None; }
+			}
+			Some(path)
+		})
+		.collect();
+
+	out.sort_unstable();
+	Ok(out)
+}
+
+/// # Sector Range State Path.
+///
+/// Same as `state_path`, but for manual sector range rips, which are named
+/// after their (inclusive) bounds rather than a track number.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location.
+pub(crate) fn sectors_state_path(toc: &Toc, rng: &Range<i32>)
+-> Result<PathBuf, RipRipError> {
+	scratch_path(format!(
+		"{}__{:06}-{:06}.state",
+		cache_prefix(toc),
+		rng.start,
+		rng.end - 1,
+	))
+}
+
+/// # Sector Range Path.
+///
+/// Same as `track_path`, but for manual sector range rips, which are named
+/// after their (inclusive) bounds rather than a track number.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location.
+pub(crate) fn sectors_path(toc: &Toc, rng: &Range<i32>)
+-> Result<PathBuf, RipRipError> {
+	cache_path(format!(
+		"{}__{:06}-{:06}.wav",
+		cache_prefix(toc),
+		rng.start,
+		rng.end - 1,
+	))
+}
+
+/// # AccurateRip Submission Path.
+///
+/// Return the file path to save the AccurateRip submission data to when
+/// `RipOptions::submit_file` is enabled. Like the cue sheet, this lives with
+/// the exported tracks.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location.
+pub(crate) fn ar_submit_path(toc: &Toc) -> Result<PathBuf, RipRipError> {
+	cache_path(format!("{}__ar-submit.bin", cache_prefix(toc)))
+}
+
+/// # Album Path.
+///
+/// Return the file path to save the single-file album to when
+/// `RipOptions::single_file` is enabled. This is just the CDDB ID, same as
+/// the cue sheet.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location.
+pub(crate) fn album_path(toc: &Toc) -> Result<PathBuf, RipRipError> {
+	cache_path(format!("{}.wav", cache_prefix(toc)))
+}
+
+/// # Lossy Path.
+///
+/// Return the file path to save the lossy copy of the track to. This is the
+/// same as the track path, but with an `.ogg` extension.
+///
+/// ## Errors
+///
+/// This will return an error if there are problems determining the cache
+/// location.
+pub(crate) fn lossy_path(toc: &Toc, track: Track) -> Result<PathBuf, RipRipError> {
+	cache_path(format!("{}__{:02}.ogg", cache_prefix(toc), track.number()))
+}
+
 /// # Track Path.
 ///
 /// Return the file path to save the exported track to. To keep things
@@ -165,6 +563,31 @@ pub(crate) fn track_path(toc: &Toc, track: Track) -> Result<PathBuf, RipRipError
 
 
 
+/// # Output Directory.
+///
+/// Return the directory exported tracks are saved to, creating it if it
+/// doesn't already exist.
+///
+/// This is `CWD/_riprip` by default, but can be overridden by setting the
+/// `RIPRIP_OUTPUT_DIR` environment variable. If the current working directory
+/// has gone missing, `TMPDIR/_riprip` is used instead; see
+/// `output_dir_fallback`.
+///
+/// ## Errors
+///
+/// This will return an error if the path cannot be determined or created.
+pub fn output_dir() -> Result<&'static Path, RipRipError> { cache_root() }
+
+#[must_use]
+/// # Output Directory Fallback.
+///
+/// Return the output directory if it had to fall back to the temporary
+/// directory because the current working directory was missing, so the user
+/// can be told where their files are going.
+pub fn output_dir_fallback() -> Option<&'static Path> {
+	cache_root().ok().filter(|_| CACHE_FALLBACK.load(Relaxed))
+}
+
 /// # Cache Root.
 ///
 /// Return the canonical cache root for the program, creating it if it doesn't
@@ -172,34 +595,78 @@ pub(crate) fn track_path(toc: &Toc, track: Track) -> Result<PathBuf, RipRipError
 ///
 /// ## Errors
 ///
-/// This will return an error if the path cannot be determined or the current
-/// working directory does not exist.
+/// This will return an error if the path cannot be determined or created.
 fn cache_root() -> Result<&'static Path, RipRipError> {
-	let out = CACHE_ROOT.get_or_init(|| {
+	let out = CACHE_ROOT.get_or_init(|| init_root(ENV_OUTPUT_DIR, || {
 		// The base must already exist.
-		let dir = std::env::current_dir().ok()?;
-		if ! dir.is_dir() { return None; }
-
-		// Our root.
-		let dir = dir.join(CACHE_BASE);
-
-		// Make it if necessary.
-		if ! dir.is_dir() {
-			std::fs::create_dir_all(&dir).ok()?;
+		if let Some(dir) = std::env::current_dir().ok().filter(|d| d.is_dir()) {
+			return Some(dir.join(CACHE_BASE));
 		}
 
-		// Make sure it is really there.
-		std::fs::canonicalize(dir).ok()
-	})
-		.as_deref()
-		.ok_or(RipRipError::Cache)?;
+		// If it was deleted out from under us, the temporary directory is
+		// better than nothing.
+		CACHE_FALLBACK.store(true, Relaxed);
+		Some(std::env::temp_dir().join(CACHE_BASE))
+	}));
+	check_root(out.as_deref())
+}
+
+/// # Scratch Root.
+///
+/// Return the canonical scratch root for the program — where the states and
+/// such are kept — creating it if it doesn't already exist.
+///
+/// ## Errors
+///
+/// This will return an error if the path cannot be determined or created.
+fn scratch_root() -> Result<&'static Path, RipRipError> {
+	let out = SCRATCH_ROOT.get_or_init(|| init_root(
+		ENV_SCRATCH_DIR,
+		|| cache_root().ok().map(|dir| dir.join(CACHE_SCRATCH)),
+	));
+	check_root(out.as_deref())
+}
 
+/// # Check Root.
+///
+/// Make sure a previously-initialized root still exists, recreating it if
+/// need be.
+///
+/// ## Errors
+///
+/// This will return an error if the root was never established or has
+/// vanished beyond repair.
+fn check_root(out: Option<&'static Path>) -> Result<&'static Path, RipRipError> {
+	let out = out.ok_or(RipRipError::Cache)?;
 	if out.is_dir() { Ok(out) }
 	// It seems to have vanished… try to recreate it.
 	else {
-		Msg::warning(format!("The {CACHE_BASE} cache directory has vanished!")).eprint();
+		Msg::warning(format!("The {} cache directory has vanished!", out.display())).eprint();
 		std::fs::create_dir_all(out).map_err(|_| RipRipError::Cache)?;
 		if out.is_dir() { Ok(out) }
 		else { Err(RipRipError::Cache) }
 	}
 }
+
+/// # Initialize Root.
+///
+/// Figure out the root directory from the environment — falling back to the
+/// default — creating it if necessary, and return its canonical path.
+///
+/// Relative environmental paths are resolved against the current working
+/// directory.
+fn init_root<F>(env: &str, default: F) -> Option<PathBuf>
+where F: FnOnce() -> Option<PathBuf> {
+	let dir = std::env::var_os(env)
+		.filter(|v| ! v.is_empty())
+		.map(PathBuf::from)
+		.map_or_else(default, Some)?;
+
+	// Make it if necessary.
+	if ! dir.is_dir() {
+		std::fs::create_dir_all(&dir).ok()?;
+	}
+
+	// Make sure it is really there. (This also resolves relative paths.)
+	std::fs::canonicalize(dir).ok()
+}
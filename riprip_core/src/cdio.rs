@@ -7,13 +7,17 @@ Somewhat useful documentation:
 
 use crate::{
 	Barcode,
+	CacheBustMode,
 	CD_DATA_C2_SIZE,
+	CD_DATA_C2_SUBCHANNEL_SIZE,
 	CD_DATA_SIZE,
 	CD_DATA_SUBCHANNEL_SIZE,
 	CD_LEADIN,
+	CD_SUBCHANNEL_SIZE,
 	CDTextKind,
 	DriveVendorModel,
 	KillSwitch,
+	RipOptions,
 	RipRipError,
 };
 use dactyl::{
@@ -21,6 +25,7 @@ use dactyl::{
 	traits::SaturatingFrom,
 };
 use libcdio_sys::{
+	cdio_drive_cap_read_t_CDIO_DRIVE_CAP_READ_ISRC,
 	cdio_hwinfo,
 	cdio_track_enums_CDIO_CDROM_LEADOUT_TRACK,
 	discmode_t_CDIO_DISC_MODE_CD_DA,
@@ -38,6 +43,7 @@ use std::{
 	ffi::{
 		CStr,
 		CString,
+		OsStr,
 	},
 	ops::Range,
 	os::{
@@ -57,6 +63,45 @@ use std::{
 /// # Cache Bust Timeout.
 const CACHE_BUST_TIMEOUT: Duration = Duration::from_secs(45);
 
+/// # Cache Test Maximum (KiB).
+///
+/// Drive buffers larger than this are vanishingly rare, so the measurement
+/// stops here.
+const CACHE_TEST_MAX: u32 = 16 * 1024;
+
+/// # Cache Test Minimum (KiB).
+///
+/// The measurement starts here, and is only refined to this precision.
+const CACHE_TEST_MIN: u32 = 64;
+
+/// # Cache Test Miss Floor.
+///
+/// A re-read quicker than this is considered a cache hit no matter what.
+/// Actually fetching data from the disc means a seek and (partial) spin,
+/// which take a good deal longer.
+const CACHE_TEST_MISS: Duration = Duration::from_millis(5);
+
+/// # Cache Bust Seek Attempts.
+///
+/// The maximum number of sectors to try when looking for a readable one at
+/// the far end of the disc.
+const CACHE_BUST_SEEK_TRIES: i32 = 8;
+
+/// # Disc Mode Attempts.
+///
+/// Cold drives might not be spun up enough to report the disc mode right
+/// away, so we'll give them a few tries before giving up.
+const DISC_MODE_ATTEMPTS: u8 = 6;
+
+/// # Disc Mode Retry Delay.
+const DISC_MODE_DELAY: Duration = Duration::from_millis(750);
+
+/// # Pre-Emphasis Sectors.
+///
+/// The maximum number of sectors to check when looking for a timecode (ADR-1)
+/// subchannel frame to pull the track control bits from.
+const PREEMPHASIS_SECTORS: i32 = 5;
+
 /// # Initialization Counter.
 static LIBCDIO_INIT: Once = Once::new();
 
@@ -94,9 +139,6 @@ impl Drop for LibcdioInstance {
 		if ! self.ptr.is_null() {
 			// Safety: this is an FFI call…
 			unsafe { libcdio_sys::cdio_destroy(self.as_mut_ptr()); }
-
-			// Use the dev field so Rust won't complain about dead code. Haha.
-			self.dev.take();
 		}
 	}
 }
@@ -168,21 +210,30 @@ impl LibcdioInstance {
 	///
 	/// This makes sure an audio CD is actually present in the drive.
 	///
+	/// Slow drives might not have spun the disc up yet, so the query will be
+	/// retried a few times — up to about four seconds in total — before
+	/// giving up.
+	///
 	/// ## Errors
 	///
 	/// Returns an error if the disc is missing or unsupported.
 	fn check_disc_mode__(&self) -> Result<(), RipRipError> {
-		// Safety: this is an FFI call…
-		let discmode = unsafe {
-			libcdio_sys::cdio_get_discmode(self.as_mut_ptr())
-		};
-		if matches!(
-			discmode,
-			discmode_t_CDIO_DISC_MODE_CD_DA | discmode_t_CDIO_DISC_MODE_CD_MIXED
-		) {
-			Ok(())
+		for i in 0..DISC_MODE_ATTEMPTS {
+			if i != 0 { std::thread::sleep(DISC_MODE_DELAY); }
+
+			// Safety: this is an FFI call…
+			let discmode = unsafe {
+				libcdio_sys::cdio_get_discmode(self.as_mut_ptr())
+			};
+			if matches!(
+				discmode,
+				discmode_t_CDIO_DISC_MODE_CD_DA | discmode_t_CDIO_DISC_MODE_CD_MIXED
+			) {
+				return Ok(());
+			}
 		}
-		else { Err(RipRipError::DiscMode) }
+
+		Err(RipRipError::DiscMode)
 	}
 
 	#[expect(unsafe_code, reason = "For FFI.")]
@@ -204,6 +255,14 @@ impl LibcdioInstance {
 }
 
 impl LibcdioInstance {
+	/// # Device Path.
+	///
+	/// Return the device path the instance was opened with, if any. (`None`
+	/// means libcdio picked the default drive.)
+	pub(super) fn dev(&self) -> Option<&Path> {
+		self.dev.as_ref().map(|v| Path::new(OsStr::from_bytes(v.as_bytes())))
+	}
+
 	/// # As Ptr.
 	pub(super) const fn as_ptr(&self) -> *const libcdio_sys::CdIo_t { self.ptr.cast() }
 
@@ -239,8 +298,8 @@ impl LibcdioInstance {
 	#[expect(unsafe_code, reason = "For FFI.")]
 	/// # Get the Number of Tracks.
 	///
-	/// Return the total number of tracks, or the last track number, however
-	/// you want to think of it.
+	/// Return the total number of tracks. Note this is only the same as the
+	/// last track number if the disc numbers its tracks from one.
 	pub(super) fn num_tracks(&self) -> Result<u8, RipRipError> {
 		// Safety: this is an FFI call…
 		let raw = unsafe {
@@ -309,47 +368,76 @@ impl LibcdioInstance {
 		c_char_to_string(raw)
 	}
 
-	/*
+	#[expect(unsafe_code, reason = "For FFI.")]
+	/// # Supports ISRC?
+	///
+	/// Returns `true` if the drive claims to be able to read ISRCs from the
+	/// subchannel.
+	pub(super) fn supports_isrc(&self) -> bool {
+		let mut read_cap = 0;
+		let mut write_cap = 0;
+		let mut misc_cap = 0;
+
+		// Safety: this is an FFI call…
+		unsafe {
+			libcdio_sys::cdio_get_drive_cap(
+				self.as_ptr(),
+				&raw mut read_cap,
+				&raw mut write_cap,
+				&raw mut misc_cap,
+			);
+		}
+
+		0 != read_cap & cdio_drive_cap_read_t_CDIO_DRIVE_CAP_READ_ISRC
+	}
+
 	#[expect(unsafe_code, reason = "For FFI.")]
 	/// # Track ISRC.
 	///
 	/// This method is used as a fallback when the value is not within the
-	/// CDText, but is relatively slow.
+	/// CDText, but is relatively slow. Callers should check
+	/// `LibcdioInstance::supports_isrc` first.
 	pub(super) fn track_isrc(&self, idx: u8) -> Option<String> {
-		if self.supports_isrc() {
-			// Safety: this is an FFI call…
-			let raw = unsafe {
-				libcdio_sys::cdio_get_track_isrc(self.as_ptr(), idx)
-			};
+		// Safety: this is an FFI call…
+		let raw = unsafe {
+			libcdio_sys::cdio_get_track_isrc(self.as_ptr(), idx)
+		};
+		if raw.is_null() { return None; }
 
-			let out = c_char_to_string(raw.cast());
-			// Safety: this is an FFI call…
-			unsafe { libcdio_sys::cdio_free(raw.cast()); }
-			out
-		}
-		else { None }
+		let out = c_char_to_string(raw.cast_const());
+		// Safety: this is an FFI call…
+		unsafe { libcdio_sys::cdio_free(raw.cast()); }
+		out
 	}
-	*/
 
 	/// # MCN.
 	///
 	/// Return the disc's associated UPC/EAN, if present. This will try CDText
 	/// first since that data is already loaded, and fall back to the direct
 	/// `cdio_get_mcn` request if that doesn't work.
-	pub(super) fn mcn(&self) -> Option<Barcode> {
+	///
+	/// If neither source yields a valid `Barcode`, the raw text (if any) is
+	/// returned as the error instead; see `Barcode::parse_mcn`.
+	pub(super) fn mcn(&self) -> Option<Result<Barcode, String>> {
 		// It probably isn't in CDText, but we already have it, so might as
 		// well check there first.
-		self.cdtext(0, CDTextKind::Barcode)
-			.and_then(|v| Barcode::try_from(v.as_bytes()).ok())
-			// Otherwise try pulling it directly.
-			.or_else(|| self.mcn__())
+		let cdtext = self.cdtext(0, CDTextKind::Barcode)
+			.and_then(|v| Barcode::parse_mcn(&v));
+		if matches!(cdtext, Some(Ok(_))) { return cdtext; }
+
+		// Otherwise try pulling it directly, falling back to the raw CDText
+		// if that doesn't work either.
+		match self.mcn__() {
+			Some(Ok(v)) => Some(Ok(v)),
+			direct => cdtext.or(direct),
+		}
 	}
 
 	#[expect(unsafe_code, reason = "For FFI.")]
 	/// # MCN Fallback.
 	///
 	/// Try pulling MCN via `cdio_get_mcn` in cases where CDText fails.
-	fn mcn__(&self) -> Option<Barcode> {
+	fn mcn__(&self) -> Option<Result<Barcode, String>> {
 		// Safety: this is an FFI call…
 		let raw = unsafe {
 			libcdio_sys::cdio_get_mcn(self.as_ptr())
@@ -360,7 +448,7 @@ impl LibcdioInstance {
 			let mcn = unsafe { CStr::from_ptr(raw) }
 				.to_str()
 				.ok()
-				.and_then(|v| Barcode::try_from(v.as_bytes()).ok());
+				.and_then(Barcode::parse_mcn);
 			// Safety: this is an FFI call…
 			unsafe { libcdio_sys::cdio_free(raw.cast()); }
 			mcn
@@ -370,10 +458,10 @@ impl LibcdioInstance {
 
 impl LibcdioInstance {
 	#[expect(unsafe_code, reason = "For FFI.")]
-	/// # Drive Vendor/Model.
+	/// # Hardware Info.
 	///
-	/// Fetch the drive vendor and/or model, if possible.
-	pub(super) fn drive_vendor_model(&self) -> Option<DriveVendorModel> {
+	/// Fetch the raw drive vendor, model, and revision, if possible.
+	fn hwinfo(&self) -> Option<cdio_hwinfo> {
 		let mut raw = cdio_hwinfo {
 			psz_vendor: [0; 9],
 			psz_model: [0; 17],
@@ -384,33 +472,57 @@ impl LibcdioInstance {
 		// 0 FFI normally kicks back.
 		// Safety: this is an FFI call…
 		if 1 == unsafe { libcdio_sys::cdio_get_hwinfo(self.as_ptr(), &mut raw) } {
-			// Rather than deal with the uncertainty of pointers, let's recast
-			// the signs since we have everything right here.
-			let vendor_u8 = raw.psz_vendor.map(u8::saturating_from);
-			let model_u8 = raw.psz_model.map(u8::saturating_from);
-
-			// Vendor might be empty.
-			let vendor =
-				if vendor_u8[0] == 0 { "" }
-				else {
-					CStr::from_bytes_until_nul(vendor_u8.as_slice())
-					.ok()
-					.and_then(|v| v.to_str().ok())?
-				};
-
-			// But model is required.
-			let model =
-				if model_u8[0] == 0 { None }
-				else {
-					CStr::from_bytes_until_nul(model_u8.as_slice())
-					.ok()
-					.and_then(|v| v.to_str().ok())
-				}?;
-
-			DriveVendorModel::new(vendor, model).ok()
+			Some(raw)
 		}
 		else { None }
 	}
+
+	/// # Drive Revision.
+	///
+	/// Fetch the drive's firmware revision, if possible.
+	pub(super) fn drive_revision(&self) -> Option<String> {
+		let raw = self.hwinfo()?;
+		let rev_u8 = raw.psz_revision.map(u8::saturating_from);
+		let rev = CStr::from_bytes_until_nul(rev_u8.as_slice())
+			.ok()
+			.and_then(|v| v.to_str().ok())?
+			.trim();
+
+		if rev.is_empty() || ! rev.is_ascii() { None }
+		else { Some(rev.to_owned()) }
+	}
+
+	/// # Drive Vendor/Model.
+	///
+	/// Fetch the drive vendor and/or model, if possible.
+	pub(super) fn drive_vendor_model(&self) -> Option<DriveVendorModel> {
+		let raw = self.hwinfo()?;
+
+		// Rather than deal with the uncertainty of pointers, let's recast
+		// the signs since we have everything right here.
+		let vendor_u8 = raw.psz_vendor.map(u8::saturating_from);
+		let model_u8 = raw.psz_model.map(u8::saturating_from);
+
+		// Vendor might be empty.
+		let vendor =
+			if vendor_u8[0] == 0 { "" }
+			else {
+				CStr::from_bytes_until_nul(vendor_u8.as_slice())
+				.ok()
+				.and_then(|v| v.to_str().ok())?
+			};
+
+		// But model is required.
+		let model =
+			if model_u8[0] == 0 { None }
+			else {
+				CStr::from_bytes_until_nul(model_u8.as_slice())
+				.ok()
+				.and_then(|v| v.to_str().ok())
+			}?;
+
+		DriveVendorModel::new(vendor, model).ok()
+	}
 }
 
 impl LibcdioInstance {
@@ -435,20 +547,30 @@ impl LibcdioInstance {
 	/// Also of note: drives tend to slow down for read errors. This will
 	/// skip any sector which previously returned a read error to keep it from
 	/// being too terrible.
+	///
+	/// When the mode is `CacheBustMode::Seek`, a single read from the far end
+	/// of the disc is attempted first instead. Many drives drop their buffer
+	/// on a long seek, making the fill unnecessary. If no such read succeeds,
+	/// the fill is used anyway.
 	pub(super) fn cache_bust(
 		&self,
 		buf: &mut[u8],
 		mut todo: u32,
 		rng: &Range<i32>,
 		leadout: i32,
-		backwards: bool,
+		opts: &RipOptions,
 		killed: &KillSwitch,
 	) {
 		if 0 != todo && buf.len() == usize::from(CD_DATA_SIZE) {
+			if
+				matches!(opts.cache_bust_mode(), CacheBustMode::Seek) &&
+				self.cache_bust_seek(buf, rng, leadout, killed)
+			{ return; }
+
 			let now = Instant::now();
 
 			// If we're moving backwards, try after, then before.
-			if backwards {
+			if opts.backwards() {
 				self.cache_bust__(buf, rng.end, leadout, &mut todo, now, killed);
 				self.cache_bust__(buf, 0, rng.start - 1, &mut todo, now, killed);
 			}
@@ -487,6 +609,143 @@ impl LibcdioInstance {
 		}
 	}
 
+	/// # Cache Bust (Seek).
+	///
+	/// Read one sector from whichever end of the disc is farthest from the
+	/// range, walking inward a little if the first candidates are bad.
+	///
+	/// Returns `true` if a read succeeded.
+	fn cache_bust_seek(
+		&self,
+		buf: &mut[u8],
+		rng: &Range<i32>,
+		leadout: i32,
+		killed: &KillSwitch,
+	) -> bool {
+		let (from, step) =
+			if rng.start < leadout - rng.end { (0, 1) }
+			else { (leadout - 1, -1) };
+
+		for i in 0..CACHE_BUST_SEEK_TRIES {
+			let lsn = from + i * step;
+			if killed.killed() || rng.contains(&lsn) || lsn < 0 || leadout <= lsn {
+				break;
+			}
+			if
+				! SHITLIST.with_borrow(|q| q.contains(&lsn)) &&
+				self.read_cd(buf, lsn, false, 0, CD_DATA_SIZE).is_ok()
+			{ return true; }
+		}
+
+		false
+	}
+
+	/// # Cache Test.
+	///
+	/// Measure the size of the drive's read buffer, in KiB, by reading sector
+	/// `lsn`, then `n` KiB worth of unrelated sectors starting from `fill`,
+	/// then `lsn` again, timing that last read. If the re-read is about as
+	/// fast as an immediate one, `lsn` must still have been in the buffer;
+	/// if not, the buffer is smaller than `n`.
+	///
+	/// The `n` is doubled until the first miss, then bisected down to
+	/// `CACHE_TEST_MIN` precision. The smallest miss is returned, or `None` if
+	/// the drive doesn't seem to buffer audio sectors at all.
+	///
+	/// If there's no miss even at `CACHE_TEST_MAX` — or the disc runs out of
+	/// room for filler — the biggest amount tried is returned instead, which
+	/// should at least be enough.
+	///
+	/// Note that drives that read ahead or keep multiple cache segments can
+	/// muddy the results; treat the answer as an estimate.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if `lsn` can't be read, or the user aborts.
+	pub(super) fn cache_test(&self, lsn: i32, fill: Range<i32>, killed: &KillSwitch)
+	-> Result<Option<u32>, RipRipError> {
+		let mut buf = vec![0_u8; usize::from(CD_DATA_SIZE)];
+
+		// Establish what a hit looks like, taking the slowest of a few
+		// back-to-back reads to be safe.
+		self.read_cd(&mut buf, lsn, false, 0, CD_DATA_SIZE)?;
+		let mut hit = Duration::ZERO;
+		for _ in 0..3 {
+			let now = Instant::now();
+			self.read_cd(&mut buf, lsn, false, 0, CD_DATA_SIZE)?;
+			hit = hit.max(now.elapsed());
+		}
+		if CACHE_TEST_MISS <= hit { return Ok(None); }
+		let miss = CACHE_TEST_MISS.max(hit * 4);
+
+		// Cap the search to whatever filler the disc can actually provide.
+		let room = u32::try_from(fill.end - fill.start).unwrap_or(0)
+			.saturating_mul(u32::from(CD_DATA_SIZE)) / 1024;
+		let max = CACHE_TEST_MAX.min(room);
+		if max < CACHE_TEST_MIN { return Ok(None); }
+
+		// Double until we miss.
+		let mut lo = 0;
+		let mut hi = CACHE_TEST_MIN;
+		loop {
+			if self.cache_test__(&mut buf, lsn, &fill, hi, miss, killed)? { break; }
+			if max <= hi { return Ok(Some(max)); }
+			lo = hi;
+			hi = max.min(hi * 2);
+		}
+
+		// If the very first fill missed, the drive isn't holding on to
+		// anything worth mentioning.
+		if lo == 0 { return Ok(None); }
+
+		// Bisect the rest of the way.
+		while CACHE_TEST_MIN < hi - lo {
+			let mid = lo + (hi - lo) / 2;
+			if self.cache_test__(&mut buf, lsn, &fill, mid, miss, killed)? { hi = mid; }
+			else { lo = mid; }
+		}
+
+		Ok(Some(hi))
+	}
+
+	/// # Cache Test (Single Run).
+	///
+	/// Read `lsn`, then `kib` KiB of filler, then `lsn` again, returning
+	/// `true` if that last read was slow enough to count as a `miss`.
+	///
+	/// Filler sectors that fail to read are simply skipped over.
+	fn cache_test__(
+		&self,
+		buf: &mut [u8],
+		lsn: i32,
+		fill: &Range<i32>,
+		kib: u32,
+		miss: Duration,
+		killed: &KillSwitch,
+	) -> Result<bool, RipRipError> {
+		self.read_cd(buf, lsn, false, 0, CD_DATA_SIZE)?;
+
+		let mut todo = (kib * 1024).div_ceil(u32::from(CD_DATA_SIZE));
+		let mut from = fill.start;
+		while from < fill.end && 0 < todo {
+			if killed.killed() { return Err(RipRipError::Killed); }
+			if self.read_cd(buf, from, false, 0, CD_DATA_SIZE).is_ok() { todo -= 1; }
+			from += 1;
+		}
+
+		let now = Instant::now();
+		self.read_cd(buf, lsn, false, 0, CD_DATA_SIZE)?;
+		Ok(miss <= now.elapsed())
+	}
+
+	/// # Clear Shitlist.
+	///
+	/// Forget about any sectors that previously triggered hard read errors,
+	/// giving them another chance.
+	pub(super) fn clear_shitlist() {
+		SHITLIST.with_borrow_mut(HashSet::clear);
+	}
+
 	/// # Read Data + C2.
 	///
 	/// Read a single sector's worth of data and C2 error pointer information
@@ -496,11 +755,13 @@ impl LibcdioInstance {
 	///
 	/// This will return an error if the read operation is unsupported or
 	/// otherwise fails.
-	pub(super) fn read_cd_c2(
-		&self,
-		buf: &mut [u8; CD_DATA_C2_SIZE as usize],
-		lsn: i32,
-	) -> Result<(), RipRipError> {
+	pub(super) fn read_cd_c2(&self, buf: &mut [u8], lsn: i32)
+	-> Result<(), RipRipError> {
+		// The buffer and block size are equivalent for our purposes.
+		if buf.len() != usize::from(CD_DATA_C2_SIZE) {
+			return Err(RipRipError::Bug("Invalid read buffer size (C2)."));
+		}
+
 		// We can't read negative, so assume everything is good and null.
 		if lsn < 0 {
 			for v in buf { *v = 0; }
@@ -511,7 +772,95 @@ impl LibcdioInstance {
 		self.read_cd(buf, lsn, true, 0, CD_DATA_C2_SIZE)
 	}
 
+	/// # Read Data.
+	///
+	/// Read a single sector's worth of data — without C2 — into the buffer,
+	/// for drives that don't support error pointers.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the read operation is unsupported or
+	/// otherwise fails.
+	pub(super) fn read_cd_data(&self, buf: &mut [u8], lsn: i32)
+	-> Result<(), RipRipError> {
+		// The buffer and block size are equivalent for our purposes.
+		if buf.len() != usize::from(CD_DATA_SIZE) {
+			return Err(RipRipError::Bug("Invalid read buffer size (data)."));
+		}
+
+		// We can't read negative, so assume everything is good and null.
+		if lsn < 0 {
+			for v in buf { *v = 0; }
+			return Ok(());
+		}
+
+		// Read it!
+		self.read_cd(buf, lsn, false, 0, CD_DATA_SIZE)
+	}
+
+	#[expect(unsafe_code, reason = "For FFI.")]
+	/// # Eject.
+	///
+	/// Open the tray (or spit out the disc), consuming the instance.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the drive refuses or doesn't support the
+	/// request.
+	pub(super) fn eject(mut self) -> Result<(), RipRipError> {
+		// Safety: this is an FFI call… Unless the drive itself chokes,
+		// libcdio destroys the instance and nulls the pointer, so the drop
+		// glue won't try to free it twice.
+		let res = unsafe { libcdio_sys::cdio_eject_media(&raw mut self.ptr) };
+		if res == driver_return_code_t_DRIVER_OP_SUCCESS { Ok(()) }
+		else { Err(RipRipError::Eject) }
+	}
+
 	#[expect(unsafe_code, reason = "For FFI.")]
+	/// # Set Read Speed.
+	///
+	/// Ask the drive to read at `x` times the standard CD speed. A value of
+	/// zero is a no-op, leaving the drive to its own devices.
+	///
+	/// Note that drives are free to round the value to something they
+	/// actually support, or ignore it altogether.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the drive rejects the request.
+	pub(super) fn set_speed(&self, x: u16) -> Result<(), RipRipError> {
+		if x == 0 { return Ok(()); }
+
+		// Safety: this is an FFI call…
+		let res = unsafe { libcdio_sys::cdio_set_speed(self.as_ptr(), i32::from(x)) };
+		if res == driver_return_code_t_DRIVER_OP_SUCCESS { Ok(()) }
+		else { Err(RipRipError::DriveSpeed(x)) }
+	}
+
+	/// # Track Pre-Emphasis.
+	///
+	/// Check the subchannel Q control bits of the first few sectors starting
+	/// at `lsn` for the pre-emphasis flag. The first timecode (ADR-1) frame
+	/// found wins.
+	///
+	/// Returns `None` if the subchannel can't be read.
+	pub(super) fn preemphasis(&self, lsn: i32) -> Option<bool> {
+		let mut buf = [0_u8; CD_DATA_SUBCHANNEL_SIZE as usize];
+		for lsn in lsn..lsn + PREEMPHASIS_SECTORS {
+			match self.read_subchannel(&mut buf, lsn) {
+				// Desyncs still have perfectly good control bits.
+				Ok(_) | Err(RipRipError::SubchannelDesync) => {
+					let q = buf[usize::from(CD_DATA_SIZE)];
+					if 1 == q & 0b0000_1111 { return Some(0 != q & 0b0001_0000); }
+				},
+				Err(RipRipError::CdReadUnsupported) => return None,
+				Err(_) => {},
+			}
+		}
+
+		None
+	}
+
 	/// # Read Data + Subchannel
 	///
 	/// Read a single sector's worth of data and formatted 16-byte subchannel
@@ -519,6 +868,9 @@ impl LibcdioInstance {
 	/// confirm the timecode matches up with the LSN, where possible, and
 	/// trigger a sync error if that fails.
 	///
+	/// Returns `false` if the subchannel had no (ADR-1) timecode, i.e. the
+	/// position could not actually be verified.
+	///
 	/// ## Errors
 	///
 	/// This will return an error if the read operation is unsupported or
@@ -527,7 +879,7 @@ impl LibcdioInstance {
 		&self,
 		buf: &mut [u8],
 		lsn: i32,
-	) -> Result<(), RipRipError> {
+	) -> Result<bool, RipRipError> {
 		// The buffer and block size are equivalent for our purposes.
 		if buf.len() != usize::from(CD_DATA_SUBCHANNEL_SIZE) {
 			return Err(RipRipError::Bug("Invalid read buffer size (subchannel)."));
@@ -536,28 +888,38 @@ impl LibcdioInstance {
 		// We can't read negative, so assume everything is good and null.
 		if lsn < 0 {
 			for v in &mut *buf { *v = 0; }
-			return Ok(());
+			return Ok(true);
 		}
 
 		// Read it!
 		self.read_cd(buf, lsn, false, 2, CD_DATA_SUBCHANNEL_SIZE)?;
+		check_subchannel(&buf[usize::from(CD_DATA_SIZE)..], lsn)
+	}
 
-		// We can only get timing information from ADR-1.
-		if 1 == buf[usize::from(CD_DATA_SIZE)] & 0b0000_1111 {
-			// Confirm the subchannel LSN matches the LSN we requested.
-			let msf = libcdio_sys::msf_s {
-				m: buf[usize::from(CD_DATA_SIZE) + 7],
-				s: buf[usize::from(CD_DATA_SIZE) + 8],
-				f: buf[usize::from(CD_DATA_SIZE) + 9],
-			};
-			// Safety: this is an FFI call…
-			if lsn != unsafe { libcdio_sys::cdio_msf_to_lsn(&msf) } {
-				return Err(RipRipError::SubchannelDesync);
-			}
+	/// # Read Data + C2 + Subchannel.
+	///
+	/// Same as `LibcdioInstance::read_subchannel`, but with the C2 error
+	/// pointers sandwiched between the data and subchannel, so everything
+	/// can be had from a single read.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the read operation is unsupported or
+	/// otherwise fails, or if the timecode does not match the LSN.
+	pub(super) fn read_cd_c2_subchannel(
+		&self,
+		buf: &mut [u8; CD_DATA_C2_SUBCHANNEL_SIZE as usize],
+		lsn: i32,
+	) -> Result<bool, RipRipError> {
+		// We can't read negative, so assume everything is good and null.
+		if lsn < 0 {
+			for v in buf { *v = 0; }
+			return Ok(true);
 		}
 
-		// As good as we can do!
-		Ok(())
+		// Read it!
+		self.read_cd(buf, lsn, true, 2, CD_DATA_C2_SUBCHANNEL_SIZE)?;
+		check_subchannel(&buf[usize::from(CD_DATA_C2_SIZE)..], lsn)
 	}
 
 	#[expect(unsafe_code, reason = "For FFI.")]
@@ -612,6 +974,34 @@ impl LibcdioInstance {
 
 
 
+#[expect(unsafe_code, reason = "For FFI.")]
+/// # Check Subchannel.
+///
+/// Confirm the timecode in a (formatted) subchannel Q block matches the LSN
+/// we requested, where possible.
+///
+/// Returns `false` if the block had no (ADR-1) timecode, i.e. the position
+/// could not actually be verified.
+///
+/// ## Errors
+///
+/// This will return a desync error if the timecode does not match the LSN.
+fn check_subchannel(q: &[u8], lsn: i32) -> Result<bool, RipRipError> {
+	// We can only get timing information from ADR-1.
+	if q.len() == usize::from(CD_SUBCHANNEL_SIZE) && 1 == q[0] & 0b0000_1111 {
+		// Confirm the subchannel LSN matches the LSN we requested.
+		let msf = libcdio_sys::msf_s { m: q[7], s: q[8], f: q[9] };
+		// Safety: this is an FFI call…
+		if lsn != unsafe { libcdio_sys::cdio_msf_to_lsn(&msf) } {
+			return Err(RipRipError::SubchannelDesync);
+		}
+
+		Ok(true)
+	}
+	// As good as we can do!
+	else { Ok(false) }
+}
+
 #[expect(unsafe_code, reason = "For FFI.")]
 /// # Pointer to String.
 ///
@@ -4,21 +4,26 @@
 
 use crate::{
 	BYTES_PER_SAMPLE,
-	cache_path,
 	cache_prefix,
-	CACHE_SCRATCH,
 	CacheWriter,
+	RipRipError,
 	RipSample,
 	SAMPLE_OVERREAD,
 	SAMPLES_PER_SECTOR,
+	scratch_path,
 };
 use crc32fast::Hasher as Crc;
 use cdtoc::{
 	Toc,
 	Track,
+	TrackPosition,
 };
 use std::{
-	path::Path,
+	collections::BTreeMap,
+	path::{
+		Path,
+		PathBuf,
+	},
 	sync::{
 		Arc,
 		atomic::{
@@ -26,15 +31,59 @@ use std::{
 			Ordering::Relaxed,
 		},
 		Mutex,
+		OnceLock,
+	},
+	time::{
+		Duration,
+		SystemTime,
 	},
 };
 
+/// # Cached 404 Lifetime.
+///
+/// Negative lookups are only trusted for this long before the server is asked
+/// again, in case the disc has since been added.
+const CHK_404_TTL: Duration = Duration::from_hours(24);
+
 /// # Maximum CTDB Offset Shift (in bytes).
 const CTDB_WIGGLE: usize = CTDB_WIGGLE_SAMPLES * BYTES_PER_SAMPLE as usize;
 
 /// # Maximum CTDB Offset Shift (in samples).
 const CTDB_WIGGLE_SAMPLES: usize = SAMPLE_OVERREAD as usize;
 
+/// # Checksum Directory Environment Variable.
+///
+/// When set, checksums are read from this directory before trying the
+/// network.
+const ENV_CHECKSUM_DIR: &str = "RIPRIP_CHECKSUM_DIR";
+
+/// # Checksum Mode Environment Variable.
+///
+/// When set to `offline`, anything missing from `ENV_CHECKSUM_DIR` is treated
+/// as unknown instead of being looked up as usual.
+const ENV_CHECKSUM_MODE: &str = "RIPRIP_CHECKSUM_MODE";
+
+/// # Checksum Directory.
+///
+/// This holds the value of `ENV_CHECKSUM_DIR`, if any, and whether
+/// `ENV_CHECKSUM_MODE` rules out falling back to the network.
+static CHECKSUM_DIR: OnceLock<Option<(PathBuf, bool)>> = OnceLock::new();
+
+/// # HTTP Headers Environment Variable.
+///
+/// Extra `Name: value` headers, one per line, to send with each lookup.
+const ENV_HTTP_HEADERS: &str = "RIPRIP_HTTP_HEADERS";
+
+/// # User Agent Environment Variable.
+///
+/// When set, this replaces the default user agent.
+const ENV_USER_AGENT: &str = "RIPRIP_USER_AGENT";
+
+/// # HTTP Headers.
+///
+/// This holds the user agent and any extra headers, if any.
+static HTTP_HEADERS: OnceLock<(String, Vec<(String, String)>)> = OnceLock::new();
+
 /// # User Agent.
 const UA: &str = concat!(
 	"Mozilla/5.0 (X11; Linux x86_64; rv:",
@@ -65,32 +114,110 @@ const UA: &str = concat!(
 /// AccurateRip is pressing-specific and their database only accepts
 /// submissions from two Windows-only programs, so the match pool is limited
 /// compared to CUETools.
+///
+/// Alongside the confidences, the (zero-based) indices of the database
+/// entries the track matched are returned. AccurateRip groups its checksums
+/// into separate entries — more or less one per pressing — so these make it
+/// possible to tell whether a set of tracks all came from the same pressing.
 pub(crate) fn chk_accuraterip(toc: &Toc, track: Track, data: &[RipSample])
--> Option<(u8, u8)> {
+-> Option<((u8, u8), Vec<usize>)> {
 	// Fetch/cache the checksums.
-	let ar = toc.accuraterip_id();
-	let dst = cache_path(format!("{CACHE_SCRATCH}/{}__chk-ar.bin", cache_prefix(toc))).ok()?;
-	let chk = std::fs::read(&dst).ok()
-		.or_else(|| {
-			let url = ar.checksum_url();
-			let chk = download(&url, &dst)?;
-			Some(chk)
-		})
-		.and_then(|chk| ar.parse_checksums(&chk).ok())
+	let bin = accuraterip_bin(toc)?;
+	accuraterip_match(toc, track, data, &bin)
+}
+
+/// # AccurateRip Match.
+///
+/// This does the actual work for `chk_accuraterip`, given the raw checksum
+/// data.
+fn accuraterip_match(toc: &Toc, track: Track, data: &[RipSample], bin: &[u8])
+-> Option<((u8, u8), Vec<usize>)> {
+	let chk = toc.accuraterip_id().parse_checksums(bin).ok()
 		.and_then(|mut chk| {
 			let idx = usize::from(track.number() - 1);
 			if idx < chk.len() { Some(chk.remove(idx)) }
 			else { None }
 		})?;
 
+	// Crunch!
+	let (crc1, crc2) = accuraterip_crcs(track, data)?;
+
+	// Return the matches, if any.
+	let conf = (
+		chk.get(&crc1).copied().unwrap_or(0),
+		chk.get(&crc2).copied().unwrap_or(0),
+	);
+	let entries =
+		if conf == (0, 0) { Vec::new() }
+		else { accuraterip_entries(toc, track, bin, (crc1, crc2)) };
+	Some((conf, entries))
+}
+
+/// # AccurateRip Entries.
+///
+/// Return the (zero-based) indices of the entries in the raw checksum data
+/// containing either of the track's CRCs.
+fn accuraterip_entries(toc: &Toc, track: Track, bin: &[u8], crcs: (u32, u32))
+-> Vec<usize> {
+	// Each entry is a 13-byte disc ID followed by a 9-byte confidence and
+	// checksum(s) block for each track.
+	let audio_len = usize::from(toc.accuraterip_id().audio_len());
+	let pos = 13 + 9 * usize::from(track.number() - 1);
+	bin.chunks_exact(13 + 9 * audio_len)
+		.enumerate()
+		.filter_map(|(k, chunk)| {
+			let v = chunk.get(pos..pos + 9)?;
+			let crc = u32::from_le_bytes([v[1], v[2], v[3], v[4]]);
+			if crc != 0 && (crc == crcs.0 || crc == crcs.1) { Some(k) }
+			else { None }
+		})
+		.collect()
+}
+
+/// # AccurateRip ID (From TOC).
+///
+/// Parse a `cdtoc`-style table of contents string — e.g. `4+96+2D2B+6256+B327+D84A`
+/// — and return its AccurateRip ID along with the URL of its checksum file.
+///
+/// This requires neither a drive nor a network connection, so is handy for
+/// checking the database before a rip.
+///
+/// ## Errors
+///
+/// This will return an error if the table of contents is invalid.
+pub fn accuraterip_id_from_toc(src: &str) -> Result<(String, String), RipRipError> {
+	let ar = Toc::from_cdtoc(src)?.accuraterip_id();
+	Ok((ar.to_string(), ar.checksum_url()))
+}
+
+/// # CUETools ID (From TOC).
+///
+/// Same as `accuraterip_id_from_toc`, but for the CUETools database.
+///
+/// ## Errors
+///
+/// This will return an error if the table of contents is invalid.
+pub fn ctdb_id_from_toc(src: &str) -> Result<(String, String), RipRipError> {
+	let toc = Toc::from_cdtoc(src)?;
+	Ok((toc.ctdb_id().to_string(), toc.ctdb_checksum_url()))
+}
+
+/// # AccurateRip Checksums (Raw).
+///
+/// Return the raw AccurateRip checksum data for the disc, downloading and
+/// caching it if necessary.
+fn accuraterip_bin(toc: &Toc) -> Option<Vec<u8>> {
+	let ar = toc.accuraterip_id();
+	lookup(&format!("{}__chk-ar.bin", cache_prefix(toc)), &ar.checksum_url(), &format!("dBAR-{ar}.bin")).ok()
+}
+
+/// # AccurateRip CRCs.
+///
+/// Crunch the v1 and v2 AccurateRip checksums for the track data.
+pub(crate) fn accuraterip_crcs(track: Track, data: &[RipSample]) -> Option<(u32, u32)> {
 	// Figure out which samples we need to crunch.
-	let pos = track.position();
-	let start =
-		if pos.is_first() { usize::from(SAMPLES_PER_SECTOR) * 5 - 1 }
-		else { 0 };
-	let end =
-		if pos.is_last() { data.len().saturating_sub(usize::from(SAMPLES_PER_SECTOR) * 5 + 1) }
-		else { data.len() };
+	let (start, end) = accuraterip_ignored(track.position());
+	let end = data.len().saturating_sub(end);
 	if end <= start { return None; }
 
 	// Crunch!
@@ -118,16 +245,129 @@ pub(crate) fn chk_accuraterip(toc: &Toc, track: Track, data: &[RipSample])
 
 	// Sixty-four bits were only used to help with overflow; the final checksum
 	// only uses half that much.
-	let crc1 = (crc1 & 0xFFFF_FFFF) as u32;
-	let crc2 = (crc2 & 0xFFFF_FFFF) as u32;
+	Some(((crc1 & 0xFFFF_FFFF) as u32, (crc2 & 0xFFFF_FFFF) as u32))
+}
 
-	// Return the matches, if any.
-	Some((
-		chk.get(&crc1).copied().unwrap_or(0),
-		chk.get(&crc2).copied().unwrap_or(0),
+/// # AccurateRip Submission.
+///
+/// Encode the v1/v2 checksums for each track — in order — the same way
+/// AccurateRip encodes its own responses: two entries, one per version, each
+/// with the 13-byte disc ID followed by a confidence of one and the checksum
+/// for every track.
+///
+/// The frame 450 checksums used for offset detection aren't calculated, so
+/// are left zeroed.
+///
+/// Returns `None` if the number of checksums doesn't match the number of
+/// audio tracks.
+pub(crate) fn accuraterip_submission(toc: &Toc, crcs: &[(u32, u32)]) -> Option<Vec<u8>> {
+	let ar = toc.accuraterip_id();
+	if crcs.len() != usize::from(ar.audio_len()) { return None; }
+
+	let mut out = Vec::with_capacity((13 + 9 * crcs.len()) * 2);
+	for crc in [|c: &(u32, u32)| c.0, |c: &(u32, u32)| c.1] {
+		out.extend_from_slice(ar.as_ref());
+		for c in crcs {
+			out.push(1);
+			out.extend_from_slice(crc(c).to_le_bytes().as_slice());
+			out.extend_from_slice(&[0, 0, 0, 0]);
+		}
+	}
+	Some(out)
+}
+
+/// # AccurateRip Ignored Edges.
+///
+/// Return the number of samples AccurateRip skips at the start of a track in
+/// the given position, and the (inclusive) offset from the end at which it
+/// stops. In practice this leaves out the first `2939` samples of the first
+/// track and the last `2940` of the last.
+///
+/// CTDB ignores even more at either end of the disc, so samples within these
+/// regions can't be verified by anybody.
+pub(crate) const fn accuraterip_ignored(pos: TrackPosition) -> (usize, usize) {
+	(
+		if pos.is_first() { SAMPLES_PER_SECTOR as usize * 5 - 1 } else { 0 },
+		if pos.is_last() { SAMPLES_PER_SECTOR as usize * 5 + 1 } else { 0 },
+	)
+}
+
+/// # Listed in AccurateRip?
+///
+/// Returns `Some(true)` if AccurateRip has checksums for the disc, or
+/// `Some(false)` if it doesn't — i.e. the URL 404s or the parsed list is
+/// empty — to help distinguish "nobody has submitted this disc" from "this
+/// track didn't match".
+///
+/// If the answer can't be determined, e.g. because the network is down,
+/// `None` is returned instead.
+pub(crate) fn chk_accuraterip_listed(toc: &Toc) -> Option<bool> {
+	let ar = toc.accuraterip_id();
+	accuraterip_listed(toc, lookup(
+		&format!("{}__chk-ar.bin", cache_prefix(toc)),
+		&ar.checksum_url(),
+		&format!("dBAR-{ar}.bin"),
 	))
 }
 
+/// # Listed in AccurateRip? (Inner.)
+///
+/// This does the actual work for `chk_accuraterip_listed`, given the result
+/// of the lookup.
+fn accuraterip_listed(toc: &Toc, chk: Result<Vec<u8>, Option<i32>>) -> Option<bool> {
+	match chk {
+		Ok(chk) => Some(
+			toc.accuraterip_id().parse_checksums(&chk)
+				.is_ok_and(|chk| chk.iter().any(|v| ! v.is_empty()))
+		),
+		Err(Some(404)) => Some(false),
+		Err(_) => None,
+	}
+}
+
+/// # Leadout Quirk?
+///
+/// Some drives report a leadout that is off by one sector, which changes the
+/// AccurateRip and CUETools disc IDs and makes matching impossible. This
+/// recomputes the IDs with the leadout nudged either way, returning the
+/// nudge — `-1` or `1` — if either database knows about that "other" disc.
+///
+/// This is purely advisory, so it should only be called when nothing else
+/// matched. The lookups are cached like any other, but under the real disc's
+/// prefix, since that's the one that gets cleaned up.
+pub(crate) fn chk_leadout_quirk(toc: &Toc) -> Option<i8> {
+	let prefix = cache_prefix(toc);
+	for delta in [-1_i8, 1] {
+		let Some(leadout) = toc.leadout().checked_add_signed(i32::from(delta)) else { continue; };
+		let Ok(alt) = Toc::from_parts(toc.audio_sectors().to_vec(), toc.data_sector(), leadout)
+		else { continue; };
+
+		// AccurateRip.
+		let ar = alt.accuraterip_id();
+		if lookup(
+			&format!("{prefix}__chk-ar{delta:+}.bin"),
+			&ar.checksum_url(),
+			&format!("dBAR-{ar}.bin"),
+		).is_ok_and(|chk|
+			ar.parse_checksums(&chk).is_ok_and(|chk| chk.iter().any(|v| ! v.is_empty()))
+		) { return Some(delta); }
+
+		// CUETools.
+		if lookup(
+			&format!("{prefix}__chk-ctdb{delta:+}.xml"),
+			&alt.ctdb_checksum_url(),
+			&format!("{}.xml", alt.ctdb_id()),
+		).ok()
+			.and_then(|chk| String::from_utf8(chk).ok())
+			.is_some_and(|chk|
+				alt.ctdb_parse_checksums(&chk).is_ok_and(|chk| chk.iter().any(|v| ! v.is_empty()))
+			)
+		{ return Some(delta); }
+	}
+
+	None
+}
+
 
 
 /// # Verify w/ CUETools.
@@ -148,8 +388,10 @@ pub(crate) fn chk_accuraterip(toc: &Toc, track: Track, data: &[RipSample])
 /// greatly increases the size of the potential match pool.
 ///
 /// Most pressings will be within a thousand or so samples of one another, so
-/// there isn't much point shifting data too much. Rip Rip checks `±5880`
-/// to ensure the full ignored region at the start is testable.
+/// there isn't much point shifting data too much. Rip Rip checks up to
+/// `±5880` — or `±max_shift`, if smaller — to ensure the full ignored region
+/// at the start is testable. A `max_shift` of zero checks only the exact
+/// offset.
 ///
 /// Shifting works best when the adjacent track data is known, which we can
 /// accommodate since we overrip tracks by 10 sectors on either side anyway.
@@ -162,25 +404,42 @@ pub(crate) fn chk_accuraterip(toc: &Toc, track: Track, data: &[RipSample])
 ///
 /// Also of note: CUETools submissions are published more or less immediately
 /// and require no second opinion, so this method will return `0` for any value
-/// less than `2` to avoid confusion.
-pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u16> {
+/// less than `2` to avoid confusion, unless `single` is `true`, in which case
+/// a lone match is let through.
+///
+/// Alongside the confidence, the "winning" shift — i.e. the one with the
+/// highest individual confidence — is returned in samples. If that isn't
+/// zero, it could just be a different pressing, but it might also mean the
+/// read offset is wrong.
+pub(crate) fn chk_ctdb(
+	toc: &Toc,
+	track: Track,
+	data: &[RipSample],
+	single: bool,
+	max_shift: u16,
+) -> Option<(u16, i16)> {
 	// Fetch/cache the checksums.
-	let dst = cache_path(format!("{CACHE_SCRATCH}/{}__chk-ctdb.xml", cache_prefix(toc))).ok()?;
-	let mut chk = std::fs::read(&dst).ok()
-		.or_else(|| {
-			let url = toc.ctdb_checksum_url();
-			let chk = download(&url, &dst)?;
-			Some(chk)
-		})
-		.and_then(|chk| {
-			let chk = String::from_utf8(chk).ok()?;
-			toc.ctdb_parse_checksums(&chk).ok()
-		})
-		.and_then(|mut chk| {
-			let idx = usize::from(track.number() - 1);
-			if idx < chk.len() { Some(chk.remove(idx)) }
-			else { None }
-		})?;
+	let chk = ctdb_checksums(toc)?;
+	ctdb_match(toc, track, data, chk, single, max_shift)
+}
+
+/// # CUETools Match.
+///
+/// This does the actual work for `chk_ctdb`, given the parsed checksums.
+fn ctdb_match(
+	toc: &Toc,
+	track: Track,
+	data: &[RipSample],
+	mut chk: Vec<BTreeMap<u32, u16>>,
+	single: bool,
+	max_shift: u16,
+) -> Option<(u16, i16)> {
+	let floor = if single { 1 } else { 2 };
+	let wiggle = usize::from(max_shift).min(CTDB_WIGGLE_SAMPLES);
+
+	let idx = usize::from(track.number() - 1);
+	if chk.len() <= idx { return None; }
+	let mut chk = chk.remove(idx);
 
 	// Our data range is the track with ten extra sectors on either end. We
 	// need to keep that padding in byte form, as well as the portions of the
@@ -228,6 +487,7 @@ pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u1
 
 	// Check the zero shift first.
 	let mut confidence = 0;
+	let mut best: (u16, i16) = (0, 0);
 	let mut crc = Crc::new();
 	crc.update(&start[CTDB_WIGGLE + ignore_first..]);
 	crc.combine(&middle);
@@ -236,9 +496,12 @@ pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u1
 	// Check it!
 	if let Some(v) = chk.remove(&crc.finalize()) {
 		confidence += v;
-		if chk.is_empty() {
-			return Some(if confidence < 2 { 0 } else { confidence });
-		}
+		best.0 = v;
+	}
+
+	// If there's nothing left to find — or we aren't shifting — we're done!
+	if wiggle == 0 || chk.is_empty() {
+		return Some(if confidence < floor { (0, 0) } else { (confidence, 0) });
 	}
 
 	// Using two threads — one for each direction — strikes a good balance
@@ -246,12 +509,13 @@ pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u1
 	// though, to maintain mutability across threads.
 	let chk = Arc::new(Mutex::new(chk));
 	let confidence = AtomicU16::new(confidence);
+	let best = Mutex::new(best);
 	std::thread::scope(|s| {
 		// Negative offsets shift into the previous track.
 		s.spawn(|| {
-			for shift in 1..=CTDB_WIGGLE_SAMPLES {
+			for shift_samples in 1..=wiggle {
 				// We're stepping in samples, but working in bytes.
-				let shift = shift * usize::from(BYTES_PER_SAMPLE);
+				let shift = shift_samples * usize::from(BYTES_PER_SAMPLE);
 				let mut crc = Crc::new();
 				crc.update(&start[CTDB_WIGGLE + ignore_first - shift..]);
 				crc.combine(&middle);
@@ -266,6 +530,7 @@ pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u1
 					else if let Some(v) = tmp.remove(&crc.finalize()) {
 						drop(tmp); // Be a good neighbor and drop the borrow ASAP.
 						confidence.fetch_add(v, Relaxed);
+						ctdb_best(&best, v, shift_samples, true);
 					}
 				}
 			}
@@ -273,9 +538,9 @@ pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u1
 
 		// Positive offsets shift into the next track.
 		s.spawn(|| {
-			for shift in 1..=CTDB_WIGGLE_SAMPLES {
+			for shift_samples in 1..=wiggle {
 				// We're stepping in samples, but working in bytes.
-				let shift = shift * usize::from(BYTES_PER_SAMPLE);
+				let shift = shift_samples * usize::from(BYTES_PER_SAMPLE);
 
 				let mut crc = Crc::new();
 				// The max shift won't include any start.
@@ -291,6 +556,7 @@ pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u1
 					else if let Some(v) = tmp.remove(&crc.finalize()) {
 						drop(tmp); // Be a good neighbor and drop the borrow ASAP.
 						confidence.fetch_add(v, Relaxed);
+						ctdb_best(&best, v, shift_samples, false);
 					}
 				}
 			}
@@ -298,40 +564,419 @@ pub(crate) fn chk_ctdb(toc: &Toc, track: Track, data: &[RipSample]) -> Option<u1
 	});
 
 	// As mentioned at the start, we shouldn't be confident in confidences less
-	// than two, so to avoid confusion, we'll treat them as equivalent to no
-	// matches at all.
+	// than two (unless asked to be), so to avoid confusion, we'll treat them
+	// as equivalent to no matches at all.
 	let confidence = confidence.into_inner();
-	Some(if confidence < 2 { 0 } else { confidence })
+	if confidence < floor { Some((0, 0)) }
+	else { Some((confidence, best.into_inner().map_or(0, |(_, shift)| shift))) }
 }
 
+/// # CTDB Best Shift.
+///
+/// Record the shift — in samples — if its confidence beats the previous
+/// best.
+fn ctdb_best(best: &Mutex<(u16, i16)>, v: u16, shift: usize, negative: bool) {
+	if let Ok(mut best) = best.lock() {
+		if best.0 < v {
+			let shift = i16::try_from(shift).unwrap_or(i16::MAX);
+			*best = (v, if negative { -shift } else { shift });
+		}
+	}
+}
+
+
+
+/// # Lookup.
+///
+/// Return the checksum data for `url` from the scratch file named `cache`,
+/// downloading (and caching) it if necessary.
+///
+/// If `RIPRIP_CHECKSUM_DIR` is set, the data is read from the file called
+/// `name` there first — i.e. AccurateRip's `dBAR-….bin`, or `{CTDB ID}.xml`
+/// for CUETools lookups — and only looked up as usual if missing. When
+/// `RIPRIP_CHECKSUM_MODE` is `offline`, the network is left alone entirely and
+/// missing files are treated like a `404` instead.
+///
+/// A cached `404` — an empty file — is returned as such, without asking the
+/// server again, until it is older than `CHK_404_TTL`.
+///
+/// ## Errors
+///
+/// This returns the same errors as `download`.
+fn lookup(cache: &str, url: &str, name: &str) -> Result<Vec<u8>, Option<i32>> {
+	// Local copies!
+	if let Some((dir, offline)) = checksum_dir() {
+		let out = fetch_local(dir, name);
+		if out.is_ok() || offline { return out; }
+	}
+
+	let dst = scratch_path(cache).map_err(|_| None)?;
+	read_cached(&dst, SystemTime::now()).unwrap_or_else(|| download(url, &dst))
+}
 
+/// # Read Cached.
+///
+/// Return the cached lookup result from `dst`, if any, or `None` if it is
+/// missing or is a `404` that has gone stale as of `now`.
+fn read_cached(dst: &Path, now: SystemTime) -> Option<Result<Vec<u8>, Option<i32>>> {
+	let out = std::fs::read(dst).ok()?;
+	if ! out.is_empty() { return Some(Ok(out)); }
+
+	// (A timestamp from the future is as fresh as it gets.)
+	let modified = std::fs::metadata(dst).and_then(|m| m.modified()).ok()?;
+	if now.duration_since(modified).map_or(true, |age| age < CHK_404_TTL) {
+		Some(Err(Some(404)))
+	}
+	else { None }
+}
 
 /// # Download.
 ///
-/// Download and return the data!
-fn download(url: &str, dst: &Path) -> Option<Vec<u8>> {
+/// Download and return the data, caching a copy to `dst` for next time.
+///
+/// A `404` is cached too, as an empty file, so discs the databases don't know
+/// about don't cost a round trip every time. (It expires after `CHK_404_TTL`,
+/// or clean the scratch files to try again sooner.)
+///
+/// ## Errors
+///
+/// If the server responds unhappily — or with an empty body — the status code
+/// is returned as the error; if there is no response at all, the error is
+/// `None`.
+fn download(url: &str, dst: &Path) -> Result<Vec<u8>, Option<i32>> {
 	use std::io::Write;
 
+	let out = fetch(url);
+
+	// Cache the contents — or lack thereof — for next time.
+	let raw = match &out {
+		Ok(out) => out.as_slice(),
+		Err(Some(404)) => &[],
+		Err(_) => return out,
+	};
+	let _res = CacheWriter::new(dst).ok()
+		.and_then(|mut writer| {
+			writer.writer().write_all(raw).ok()?;
+			writer.finish().ok()
+		});
+
+	out
+}
+
+/// # Fetch.
+///
+/// Download and return the data, without caching it.
+///
+/// ## Errors
+///
+/// This returns the same errors as `download`.
+fn fetch(url: &str) -> Result<Vec<u8>, Option<i32>> {
 	// Download the data into a vector.
-	let res = minreq::get(url)
-		.with_header("user-agent", UA)
+	let (ua, extra) = http_headers();
+	let res = extra.iter()
+		.fold(
+			minreq::get(url).with_header("user-agent", ua.as_str()),
+			|req, (k, v)| req.with_header(k.as_str(), v.as_str()),
+		)
 		.with_timeout(15)
 		.send()
-		.ok()?;
+		.map_err(|_| None)?;
 
 	// Only accept happy response codes with sized bodies.
-	if (200..=399).contains(&res.status_code) {
+	let status = res.status_code;
+	if (200..=399).contains(&status) {
 		let out = res.into_bytes();
-		if ! out.is_empty() {
-			// Cache the contents for next time.
-			let _res = CacheWriter::new(dst).ok()
-				.and_then(|mut writer| {
-					writer.writer().write_all(&out).ok()?;
-					writer.finish().ok()
-				});
-			return Some(out);
+		if ! out.is_empty() { return Ok(out); }
+	}
+
+	Err(Some(status))
+}
+
+/// # Fetch (Local).
+///
+/// Read and return the data from the file called `name` in `dir`, for
+/// local lookups. Missing or empty files are treated like a `404`.
+///
+/// ## Errors
+///
+/// This returns the same errors as `download`.
+fn fetch_local(dir: &Path, name: &str) -> Result<Vec<u8>, Option<i32>> {
+	std::fs::read(dir.join(name)).ok()
+		.filter(|out| ! out.is_empty())
+		.ok_or(Some(404))
+}
+
+/// # HTTP Headers.
+///
+/// Return the user agent — `RIPRIP_USER_AGENT` or `UA` — and any extra
+/// headers from `RIPRIP_HTTP_HEADERS`.
+fn http_headers() -> &'static (String, Vec<(String, String)>) {
+	HTTP_HEADERS.get_or_init(|| {
+		let ua = std::env::var(ENV_USER_AGENT).ok()
+			.map(|v| v.trim().to_owned())
+			.filter(|v| ! v.is_empty())
+			.unwrap_or_else(|| UA.to_owned());
+		let extra = std::env::var(ENV_HTTP_HEADERS)
+			.map_or_else(|_| Vec::new(), |v| parse_http_headers(&v));
+		(ua, extra)
+	})
+}
+
+/// # Parse HTTP Headers.
+///
+/// Split `Name: value` pairs, one per line, skipping anything malformed.
+fn parse_http_headers(src: &str) -> Vec<(String, String)> {
+	src.lines()
+		.filter_map(|line| {
+			let (k, v) = line.split_once(':')?;
+			let k = k.trim();
+			let v = v.trim();
+			if k.is_empty() || k.contains(char::is_whitespace) { None }
+			else { Some((k.to_owned(), v.to_owned())) }
+		})
+		.collect()
+}
+
+/// # CUETools Checksums.
+///
+/// Return the parsed CUETools checksums for the disc, from the scratch cache
+/// or network (or local checksum directory).
+fn ctdb_checksums(toc: &Toc) -> Option<Vec<BTreeMap<u32, u16>>> {
+	let chk = lookup(
+		&format!("{}__chk-ctdb.xml", cache_prefix(toc)),
+		&toc.ctdb_checksum_url(),
+		&format!("{}.xml", toc.ctdb_id()),
+	).ok()?;
+	let chk = String::from_utf8(chk).ok()?;
+	toc.ctdb_parse_checksums(&chk).ok()
+}
+
+/// # Checksum Directory.
+///
+/// Return the local checksum directory, if set, and whether it is all there
+/// is, i.e. missing files should not be looked up online.
+fn checksum_dir() -> Option<(&'static Path, bool)> {
+	CHECKSUM_DIR.get_or_init(||
+		std::env::var_os(ENV_CHECKSUM_DIR)
+			.filter(|v| ! v.is_empty())
+			.map(|v| (
+				PathBuf::from(v),
+				std::env::var(ENV_CHECKSUM_MODE).is_ok_and(|m| parse_checksum_mode(&m)),
+			))
+	).as_ref().map(|(dir, offline)| (dir.as_path(), *offline))
+}
+
+/// # Parse Checksum Mode.
+///
+/// Returns `true` if the `RIPRIP_CHECKSUM_MODE` value is `offline`. Anything
+/// else allows falling back to the network.
+fn parse_checksum_mode(src: &str) -> bool {
+	src.trim().eq_ignore_ascii_case("offline")
+}
+
+
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// # Test Disc.
+	const TOC: &str = "4+96+2D2B+6256+B327+D84A";
+
+	/// # Test Directory.
+	///
+	/// Return an empty temporary directory for the named test to stash its
+	/// checksum files in. (It should be removed when finished.)
+	fn test_dir(name: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("riprip-{name}-{}", std::process::id()));
+		let _res = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(&dir).expect("Unable to create test directory.");
+		dir
+	}
+
+	/// # Noise.
+	///
+	/// Generate some deterministic, non-silent samples. (The values are kept
+	/// small enough that the AccurateRip sums won't overflow.)
+	fn noise(len: usize) -> Vec<RipSample> {
+		(0_u32..)
+			.take(len)
+			.map(|k| RipSample::from((k.wrapping_mul(2_654_435_761) >> 16).to_le_bytes()))
+			.collect()
+	}
+
+	#[test]
+	fn t_accuraterip_ignored() {
+		let toc = Toc::from_cdtoc(TOC).expect("Invalid TOC.");
+		let tracks: Vec<Track> = toc.audio_tracks().collect();
+		assert_eq!(accuraterip_ignored(tracks[0].position()), (2939, 0));
+		assert_eq!(accuraterip_ignored(tracks[1].position()), (0, 0));
+		assert_eq!(accuraterip_ignored(tracks[3].position()), (0, 2941));
+		assert_eq!(accuraterip_ignored(TrackPosition::Only), (2939, 2941));
+		assert_eq!(accuraterip_ignored(TrackPosition::Invalid), (0, 0));
+	}
+
+	#[test]
+	fn t_accuraterip_submission() {
+		let toc = Toc::from_cdtoc("4+96+2D2B+6256+B327+D84A").expect("Invalid TOC.");
+		let crcs = [(1, 2), (3, 4), (5, 6), (7, 8)];
+		let bin = accuraterip_submission(&toc, &crcs).expect("Missing submission.");
+		assert_eq!(bin.len(), (13 + 9 * 4) * 2);
+
+		// It should parse just like a real response.
+		let parsed = toc.accuraterip_id().parse_checksums(&bin).expect("Invalid checksums.");
+		for (chk, (v1, v2)) in parsed.iter().zip(crcs) {
+			assert_eq!(chk.get(&v1), Some(&1));
+			assert_eq!(chk.get(&v2), Some(&1));
 		}
+
+		// The track count has to match.
+		assert!(accuraterip_submission(&toc, &crcs[..3]).is_none());
 	}
 
-	None
+	#[test]
+	fn t_id_from_toc() {
+		let (id, url) = accuraterip_id_from_toc(TOC).expect("Invalid TOC.");
+		assert_eq!(id, "004-0002189a-00087f33-1f02e004");
+		assert_eq!(url, "http://www.accuraterip.com/accuraterip/a/9/8/dBAR-004-0002189a-00087f33-1f02e004.bin");
+
+		let (id, url) = ctdb_id_from_toc(TOC).expect("Invalid TOC.");
+		assert_eq!(id, "VukMWWItblELRM.CEFpXxw0FlME-");
+		assert_eq!(url, "http://db.cuetools.net/lookup2.php?version=3&ctdb=1&fuzzy=1&toc=0:11413:25024:45713:55220");
+
+		// Garbage is garbage.
+		assert!(accuraterip_id_from_toc("nope").is_err());
+		assert!(ctdb_id_from_toc("nope").is_err());
+	}
+
+	#[test]
+	fn t_parse_http_headers() {
+		assert!(parse_http_headers("").is_empty());
+		assert_eq!(
+			parse_http_headers("X-Api-Key: abc123\nnonsense\n: empty\nBad Name: x\n  From:me@example.com  "),
+			vec![
+				("X-Api-Key".to_owned(), "abc123".to_owned()),
+				("From".to_owned(), "me@example.com".to_owned()),
+			],
+		);
+	}
+
+	#[test]
+	fn t_accuraterip_offline() {
+		let dir = test_dir("ar-offline");
+		let toc = Toc::from_cdtoc(TOC).expect("Invalid TOC.");
+		let track = toc.audio_track(2).expect("Missing track.");
+		let data = noise(usize::try_from(track.duration().samples()).unwrap());
+		let (crc1, crc2) = accuraterip_crcs(track, &data).expect("Missing CRCs.");
+
+		// Build a checksum file with two entries, one for each CRC version.
+		let ar = toc.accuraterip_id();
+		let mut bin = Vec::new();
+		for (conf, crc) in [(7_u8, crc1), (3, crc2)] {
+			bin.extend_from_slice(ar.as_ref());
+			for idx in 1..=4 {
+				bin.push(conf);
+				if idx == 2 { bin.extend_from_slice(&crc.to_le_bytes()); }
+				else { bin.extend_from_slice(&[0, 0, 0, 0]); }
+				bin.extend_from_slice(&[0, 0, 0, 0]);
+			}
+		}
+		let name = format!("dBAR-{ar}.bin");
+		std::fs::write(dir.join(&name), bin).expect("Unable to write checksums.");
+
+		let bin = fetch_local(&dir, &name).expect("Missing checksums.");
+		assert_eq!(accuraterip_match(&toc, track, &data, &bin), Some(((7, 3), vec![0, 1])));
+		assert_eq!(accuraterip_listed(&toc, Ok(bin.clone())), Some(true));
+
+		// Different data shouldn't match.
+		let other = noise(data.len() + 1);
+		assert_eq!(accuraterip_match(&toc, track, &other[1..], &bin), Some(((0, 0), Vec::new())));
+
+		// Missing files are unlisted.
+		assert_eq!(accuraterip_listed(&toc, fetch_local(&dir, "nope.bin")), Some(false));
+
+		let _res = std::fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn t_ctdb_offline() {
+		let dir = test_dir("ctdb-offline");
+		let toc = Toc::from_cdtoc(TOC).expect("Invalid TOC.");
+		let track = toc.audio_track(3).expect("Missing track.");
+		let len = usize::try_from(track.duration().samples()).unwrap();
+		let padded = noise(len + CTDB_WIGGLE_SAMPLES * 2);
+
+		// Publish a CRC for the track shifted by a hundred samples.
+		let start = CTDB_WIGGLE_SAMPLES + 100;
+		let mut crc = Crc::new();
+		for s in &padded[start..start + len] { crc.update(s.as_slice()); }
+		let crc = crc.finalize();
+		let name = format!("{}.xml", toc.ctdb_id());
+		std::fs::write(
+			dir.join(&name),
+			format!("<ctdb>\n<entry confidence=\"5\" trackcrcs=\"00000000 00000000 {crc:08x} 00000000\" />\n</ctdb>\n"),
+		).expect("Unable to write checksums.");
+		let chk = fetch_local(&dir, &name).ok()
+			.and_then(|chk| String::from_utf8(chk).ok())
+			.and_then(|chk| toc.ctdb_parse_checksums(&chk).ok())
+			.expect("Missing checksums.");
+
+		// The shift search should find it.
+		let (conf, shift) = ctdb_match(&toc, track, &padded, chk.clone(), false, SAMPLE_OVERREAD)
+			.expect("Missing CTDB result.");
+		assert_eq!(conf, 5);
+		assert_eq!(shift.unsigned_abs(), 100);
+
+		// But not if we don't let it look that far.
+		assert_eq!(ctdb_match(&toc, track, &padded, chk, false, 50), Some((0, 0)));
+
+		let _res = std::fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn t_fetch_local() {
+		let dir = test_dir("fetch-local");
+
+		// Missing and empty files are both unknown.
+		assert_eq!(fetch_local(&dir, "nope.xml"), Err(Some(404)));
+		std::fs::write(dir.join("empty.xml"), "").expect("Unable to write checksums.");
+		assert_eq!(fetch_local(&dir, "empty.xml"), Err(Some(404)));
+
+		// Anything else is returned as-is.
+		std::fs::write(dir.join("some.xml"), "<ctdb>\n</ctdb>\n").expect("Unable to write checksums.");
+		assert_eq!(fetch_local(&dir, "some.xml"), Ok(b"<ctdb>\n</ctdb>\n".to_vec()));
+
+		let _res = std::fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn t_read_cached() {
+		let dir = test_dir("read-cached");
+
+		// Missing files aren't cached.
+		let dst = dir.join("chk.bin");
+		assert_eq!(read_cached(&dst, SystemTime::now()), None);
+
+		// Data is data.
+		std::fs::write(&dst, "data").expect("Unable to write checksums.");
+		assert_eq!(read_cached(&dst, SystemTime::now()), Some(Ok(b"data".to_vec())));
+
+		// A fresh 404 sticks…
+		std::fs::write(&dst, "").expect("Unable to write checksums.");
+		let now = SystemTime::now();
+		assert_eq!(read_cached(&dst, now), Some(Err(Some(404))));
+
+		// …but not forever.
+		assert_eq!(read_cached(&dst, now + CHK_404_TTL), None);
+
+		let _res = std::fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn t_parse_checksum_mode() {
+		assert!(parse_checksum_mode("offline"));
+		assert!(parse_checksum_mode(" Offline\n"));
+		assert!(! parse_checksum_mode(""));
+		assert!(! parse_checksum_mode("prefer-local"));
+	}
 }
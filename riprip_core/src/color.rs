@@ -0,0 +1,86 @@
+/*!
+# Rip Rip Hooray: Colors
+*/
+
+use std::{
+	borrow::Cow,
+	fmt,
+	sync::OnceLock,
+};
+
+
+
+/// # Color.
+///
+/// This holds an ANSI SGR color code — e.g. `91` or `38;5;208` — used to
+/// highlight the various quality states.
+///
+/// The default can be overridden by setting the corresponding environment
+/// variable, like `RIPRIP_COLOR_BAD=31`. The variable is only read once, the
+/// first time the color is needed; invalid values are ignored.
+pub(crate) struct Color {
+	/// # Environment Variable.
+	env: &'static str,
+
+	/// # Default Value.
+	default: &'static str,
+
+	/// # Value.
+	value: OnceLock<Cow<'static, str>>,
+}
+
+impl fmt::Display for Color {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl Color {
+	/// # New.
+	pub(crate) const fn new(env: &'static str, default: &'static str) -> Self {
+		Self { env, default, value: OnceLock::new() }
+	}
+
+	/// # As Str.
+	///
+	/// Return the SGR code, checking the environment the first time around.
+	pub(crate) fn as_str(&self) -> &str {
+		self.value.get_or_init(||
+			std::env::var(self.env).ok()
+				.map(|v| v.trim().to_owned())
+				.filter(|v| is_sgr(v))
+				.map_or(Cow::Borrowed(self.default), Cow::Owned)
+		)
+	}
+}
+
+
+
+/// # Is SGR?
+///
+/// Make sure a value looks like a (non-empty) sequence of semicolon-separated
+/// SGR parameters, e.g. `1;91` or `38;5;208`, so nobody can sneak arbitrary
+/// escapes into the output.
+fn is_sgr(src: &str) -> bool {
+	! src.is_empty() &&
+	src.len() <= 32 &&
+	src.split(';').all(|v| ! v.is_empty() && v.len() <= 3 && v.parse::<u8>().is_ok())
+}
+
+
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn t_is_sgr() {
+		for v in ["1", "91", "38;5;208", "1;38;2;255;0;0"] {
+			assert!(is_sgr(v), "{v}");
+		}
+		for v in ["", ";", "91;", ";91", "256", "9a", "\x1b[91m", "0001", "38;5;-1"] {
+			assert!(! is_sgr(v), "{v}");
+		}
+	}
+}
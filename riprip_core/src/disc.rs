@@ -3,14 +3,27 @@
 */
 
 use cdtoc::{
+	Duration,
 	Toc,
 	TocKind,
+	Track,
 };
 use crate::{
+	accessible_range,
+	accuraterip_crcs,
+	accuraterip_submission,
+	album_path,
+	ar_submit_path,
 	Barcode,
 	cache_prefix,
+	CacheLock,
 	CacheWriter,
 	CD_LEADOUT_LABEL,
+	chk_accuraterip_listed,
+	chk_leadout_quirk,
+	htoa_silence,
+	htoa_tracks,
+	HtoaMode,
 	CDTextKind,
 	COLOR_BAD,
 	COLOR_CONFIRMED,
@@ -18,18 +31,46 @@ use crate::{
 	DriveVendorModel,
 	KillSwitch,
 	LibcdioInstance,
+	output_dir,
+	probe_offset,
+	read_wav,
+	read_wav_pregap,
+	ReadOffset,
 	RipOptions,
 	Ripper,
 	RipRipError,
+	rip_sectors,
+	rip_whole_disc,
+	RipTrackResult,
+	save_album_wav,
+	save_sha256,
+	saved_rips,
 	SavedRips,
+	scratch_files,
+	SECTOR_OVERREAD,
+	set_durable_saves,
+	track_confirmed,
+	track_exported,
+	track_too_short,
 };
 use dactyl::NoHash;
-use fyi_msg::Progless;
+use fyi_msg::{
+	Msg,
+	Progless,
+};
+use oxford_join::JoinFmt;
 use std::{
 	borrow::Cow,
-	collections::HashMap,
+	collections::{
+		HashMap,
+		HashSet,
+	},
 	ffi::OsStr,
 	fmt,
+	ops::{
+		Range,
+		RangeInclusive,
+	},
 	path::{
 		Path,
 		PathBuf,
@@ -38,6 +79,54 @@ use std::{
 
 
 
+/// # Redbook Maximum Tracks.
+const REDBOOK_MAX_TRACKS: u8 = 99;
+
+/// # Redbook Maximum Length (Sectors).
+///
+/// Eighty minutes' worth.
+const REDBOOK_MAX_SECTORS: u32 = 80 * 60 * 75;
+
+
+
+/// # Generate AccurateRip Submission if Verified.
+///
+/// If every track has been ripped _and_ verified, re-crunch the AccurateRip
+/// checksums from the exported WAVs and save them in submission form.
+fn save_submission(disc: &Disc, ripped: &SavedRips, opts: &RipOptions) -> Option<PathBuf> {
+	let (parent, _, all) = ripped_files(&disc.toc, ripped, opts.htoa_mode())?;
+	if ! ripped.iter().all(|(&k, (_, ar, ctdb))| k == 0 || ar.is_some() || ctdb.is_some()) {
+		return None;
+	}
+
+	let pregap = merged_pregap(&disc.toc, opts).map_or(0, Duration::samples);
+	let crcs = all.iter()
+		.map(|&(track, src)| {
+			let data =
+				if track.position().is_first() {
+					read_wav_pregap(&parent.join(src), track, usize::try_from(pregap).ok()?)?
+				}
+				else { read_wav(&parent.join(src), track)? };
+			accuraterip_crcs(track, &data)
+		})
+		.collect::<Option<Vec<_>>>()?;
+	let bin = accuraterip_submission(&disc.toc, &crcs)?;
+	save_sheet(ar_submit_path(&disc.toc).ok()?, &bin)
+}
+
+/// # Ripped Files.
+///
+/// The output folder, HTOA file name (if any), and track/file name pairs for
+/// everything else.
+type RippedFiles<'a> = (&'a Path, Option<&'a str>, Vec<(Track, &'a str)>);
+
+/// # ToC Parts.
+///
+/// The audio starts, data start, and disc track numbers for the audio.
+type TocParts = (Vec<u32>, Option<u32>, Vec<u8>);
+
+
+
 #[derive(Debug)]
 /// # Disc.
 ///
@@ -49,11 +138,34 @@ pub struct Disc {
 	/// # Disc Table of Contents.
 	toc: Toc,
 
+	/// # Disc Track Numbers.
+	///
+	/// The `Toc` always numbers audio tracks from one, but the disc itself
+	/// might not, and a leading data track shifts everything along. These are
+	/// the disc's own numbers for each audio track, in `Toc` order, needed
+	/// for track-specific libcdio lookups.
+	track_nums: Vec<u8>,
+
 	/// # Barcode.
 	barcode: Option<Barcode>,
 
+	/// # Unvalidated Barcode.
+	///
+	/// The raw MCN text, if present but not a valid `Barcode`.
+	barcode_raw: Option<String>,
+
 	/// # Track ISRCs.
 	isrcs: HashMap<u8, String, NoHash>,
+
+	/// # Pre-Emphasized Tracks.
+	preemphasis: HashSet<u8, NoHash>,
+
+	/// # Second Drive (and Its Read Offset).
+	///
+	/// A second drive holding another copy of the same disc, for
+	/// cross-checked rips, along with its read offset and whether that was
+	/// given explicitly.
+	second: Option<(LibcdioInstance, ReadOffset, bool)>,
 }
 
 impl fmt::Display for Disc {
@@ -76,6 +188,9 @@ impl fmt::Display for Disc {
 		if let Some(barcode) = self.barcode.as_ref() {
 			kv.push(("Barcode:", 199, barcode.to_string()));
 		}
+		else if let Some(raw) = self.barcode_raw.as_deref() {
+			kv.push(("Barcode:", 199, format!("{raw} \x1b[2m(unvalidated)\x1b[0m")));
+		}
 
 		let col_max: usize = kv.iter().map(|(k, _, _)| k.len()).max().unwrap_or(0);
 		for (k, color, v) in kv {
@@ -83,10 +198,12 @@ impl fmt::Display for Disc {
 		}
 
 		// Start the table of contents.
+		let pre = ! self.preemphasis.is_empty();
 		write!(
 			f,
-			"\n\x1b[2m##   FIRST    LAST  LENGTH          {}\x1b[0m\n",
-			if self.isrcs.is_empty() { "" } else { "ISRC" },
+			"\n\x1b[2m##   FIRST    LAST  LENGTH  {:>12}{}\x1b[0m\n",
+			if self.isrcs.is_empty() && ! pre { "" } else { "ISRC" },
+			if pre { "  FLAGS" } else { "" },
 		)?;
 		f.write_str(DIVIDER)?;
 
@@ -122,9 +239,10 @@ impl fmt::Display for Disc {
 			let isrc = self.isrc(num).unwrap_or_default();
 			writeln!(
 				f,
-				"{num:02}  {:>6}  {:>6}  {len:>6}  {isrc:>12}",
+				"{num:02}  {:>6}  {:>6}  {len:>6}  {isrc:>12}{}",
 				rng.start,
 				rng.end - 1,
+				if self.preemphasis(num) { "  \x1b[93mPRE\x1b[0m" } else { "" },
 			)?;
 		}
 
@@ -147,6 +265,15 @@ impl fmt::Display for Disc {
 
 		// Close it off!
 		f.write_str(DIVIDER)?;
+
+		// Mention if the disc is suspiciously long.
+		if is_overlong(&self.toc) {
+			writeln!(
+				f,
+				"\x1b[{COLOR_LIKELY}m!\x1b[0;2m This disc runs longer than the Redbook maximum of 80 minutes. It\n  might just be overburned, but the drive could also be misreporting\n  the table of contents.\x1b[0m\n",
+			)?;
+		}
+
 		writeln!(f)
 	}
 }
@@ -163,56 +290,146 @@ impl Disc {
 	pub fn new<P>(dev: Option<P>) -> Result<Self, RipRipError>
 	where P: AsRef<Path> {
 		let cdio = LibcdioInstance::new(dev)?;
+		let (toc, track_nums) = read_toc(&cdio)?;
 
-		// Parse the table of contents into the pieces needed for `Toc`.
-		let mut audio = Vec::new();
-		let mut data = None;
-
-		// The inclusive range to search.
-		let from = cdio.first_track_num()?;
-		let to = cdio.num_tracks()?;
-		if to < from { return Err(RipRipError::NumTracks); }
+		// Pull the barcode (if any), holding onto the raw text if it doesn't
+		// validate.
+		let (barcode, barcode_raw) = match cdio.mcn() {
+			Some(Ok(v)) => (Some(v), None),
+			Some(Err(v)) => (None, Some(v)),
+			None => (None, None),
+		};
 
-		// Grab the position and type for each track.
-		for idx in from..=to {
-			let start = cdio.track_lba_start(idx)?;
-			if cdio.track_format(idx)? {
-				audio.push(start);
-			}
-			else {
-				if data.is_some() || (idx != 1 && idx != to) {
-					return Err(RipRipError::TrackFormat(idx));
-				}
-				data.replace(start);
-			}
-		}
-
-		// Grab the leadout, then build the ToC.
-		let leadout = cdio.leadout_lba()?;
-		let toc = Toc::from_parts(audio, data, leadout)?;
-
-		// Pull the barcode (if any).
-		let barcode = cdio.mcn();
-
-		// Pull the track ISRCs (if any).
+		// Pull the track ISRCs and pre-emphasis flags (if any). ISRCs missing
+		// from the CDText might still be in the subchannel if the drive can
+		// get at them.
 		let mut isrcs = HashMap::with_hasher(NoHash::default());
+		let mut preemphasis = HashSet::with_hasher(NoHash::default());
+		let isrc_fallback = cdio.supports_isrc();
 		for t in toc.audio_tracks() {
 			let idx = t.number();
-			if let Some(isrc) = cdio.cdtext(idx, CDTextKind::Isrc) {
+			let num = disc_track_num(&track_nums, idx);
+			if let Some(isrc) = cdio.cdtext(num, CDTextKind::Isrc).or_else(||
+				if isrc_fallback { cdio.track_isrc(num).filter(|v| is_isrc(v)) }
+				else { None }
+			) {
 				isrcs.insert(idx, isrc);
 			}
+			let lsn = i32::try_from(t.sector_range_normalized().start).unwrap_or(i32::MAX);
+			if cdio.preemphasis(lsn) == Some(true) { preemphasis.insert(idx); }
 		}
 
 		// Finally done!
-		Ok(Self { cdio, toc, barcode, isrcs })
+		Ok(Self { cdio, toc, track_nums, barcode, barcode_raw, isrcs, preemphasis, second: None })
+	}
+
+	/// # With Second Drive.
+	///
+	/// Connect to a second drive — with its own read offset — holding another
+	/// copy of the same disc, so track rips can alternate passes between the
+	/// two and cross-check each other.
+	///
+	/// If the offset is `None`, the drive's detected offset is used, or zero
+	/// if unknown.
+	///
+	/// ## Errors
+	///
+	/// Both drives must be specified explicitly and refer to different
+	/// devices, and the second disc's table of contents must match the
+	/// first's; otherwise an error is returned. Any of the usual connection
+	/// errors are bubbled up too.
+	pub fn with_second_drive<P>(mut self, dev: P, offset: Option<ReadOffset>)
+	-> Result<Self, RipRipError>
+	where P: AsRef<Path> {
+		let dev = dev.as_ref();
+		let err = || RipRipError::SecondDrive(dev.to_string_lossy().into_owned());
+
+		// The first drive has to be explicit too, otherwise we can't tell if
+		// they're the same thing.
+		let first = self.cdio.dev()
+			.and_then(|p| std::fs::canonicalize(p).ok())
+			.ok_or_else(err)?;
+		if std::fs::canonicalize(dev).map_err(|_| err())? == first {
+			return Err(err());
+		}
+
+		// Connect and make sure it's the same disc.
+		let cdio = LibcdioInstance::new(Some(dev))?;
+		if ! read_toc(&cdio).is_ok_and(|(toc, _)| toc == self.toc) {
+			return Err(err());
+		}
+
+		let manual = offset.is_some();
+		let offset = offset
+			.or_else(|| cdio.drive_vendor_model().and_then(|vm| vm.detect_offset()))
+			.unwrap_or_default();
+		self.second.replace((cdio, offset, manual));
+		Ok(self)
 	}
 }
 
 impl Disc {
+	/// # Check Disc.
+	///
+	/// Re-read the table of contents and make sure it still matches the one
+	/// we started with, i.e. nobody swapped discs mid-rip.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the table of contents has changed or can
+	/// no longer be read.
+	pub(crate) fn check_toc(&self) -> Result<(), RipRipError> {
+		if
+			read_toc(&self.cdio).is_ok_and(|(toc, _)| toc == self.toc) &&
+			self.second.as_ref().is_none_or(|(cdio, _, _)|
+				read_toc(cdio).is_ok_and(|(toc, _)| toc == self.toc)
+			)
+		{ Ok(()) }
+		else { Err(RipRipError::DiscChanged) }
+	}
+
+	#[must_use]
+	/// # Accessible Range.
+	///
+	/// Return the range of audio samples — normalized, i.e. starting from
+	/// zero — the drive can actually reach given its read offset.
+	///
+	/// A negative offset cuts off the start of the disc, while a positive one
+	/// cuts off the end, so depending on the offset, the first or last track
+	/// might never be fully ripped.
+	///
+	/// This will return `None` if the numbers don't make sense.
+	pub fn accessible_range(&self, offset: ReadOffset) -> Option<Range<i32>> {
+		accessible_range(&self.toc, offset).ok()
+	}
+
 	#[must_use]
 	/// # Barcode.
 	pub const fn barcode(&self) -> Option<Barcode> { self.barcode }
 
+	#[must_use]
+	/// # Unvalidated Barcode.
+	///
+	/// Return the raw media catalog number if the disc has one that failed
+	/// `Barcode` validation, e.g. because it is an unusual length.
+	pub fn barcode_raw(&self) -> Option<&str> { self.barcode_raw.as_deref() }
+
+	#[must_use]
+	#[inline]
+	/// # Drive Firmware Revision.
+	///
+	/// Read offsets can vary between firmware versions of the same model, so
+	/// this is worth noting alongside `Disc::drive_vendor_model`.
+	pub fn drive_revision(&self) -> Option<String> { self.cdio.drive_revision() }
+
+	#[must_use]
+	#[inline]
+	/// # Device Path.
+	///
+	/// Return the (first) drive's device path, if one was specified. (`None`
+	/// means libcdio picked the default drive.)
+	pub fn dev(&self) -> Option<&Path> { self.cdio.dev() }
+
 	#[must_use]
 	#[inline]
 	/// # Drive Vendor and Model.
@@ -220,12 +437,89 @@ impl Disc {
 		self.cdio.drive_vendor_model()
 	}
 
+	#[must_use]
+	/// # Second Drive Vendor and Model.
+	///
+	/// Same as `Disc::drive_vendor_model`, but for the second drive, if any.
+	pub fn second_drive_vendor_model(&self) -> Option<DriveVendorModel> {
+		self.second.as_ref().and_then(|(cdio, _, _)| cdio.drive_vendor_model())
+	}
+
+	#[must_use]
+	/// # Second Drive Read Offset.
+	///
+	/// Return the read offset for the second drive, if any.
+	pub fn second_offset(&self) -> Option<ReadOffset> {
+		self.second.as_ref().map(|(_, offset, _)| *offset)
+	}
+
+	#[must_use]
+	/// # Second Drive Manual Offset?
+	///
+	/// Returns `true` if the second drive's read offset was given explicitly
+	/// rather than detected.
+	pub fn second_offset_manual(&self) -> bool {
+		self.second.as_ref().is_some_and(|(_, _, manual)| *manual)
+	}
+
+	#[must_use]
+	/// # Pre-Emphasis?
+	///
+	/// Returns `true` if the track's subchannel control bits indicate it was
+	/// recorded with pre-emphasis, and will need de-emphasis during playback.
+	pub fn preemphasis(&self, idx: u8) -> bool { self.preemphasis.contains(&idx) }
+
 	#[must_use]
 	/// # ISRC.
 	pub fn isrc(&self, idx: u8) -> Option<&str> {
 		self.isrcs.get(&idx).map(String::as_str)
 	}
 
+	#[must_use]
+	/// # Rip Scope.
+	///
+	/// Return the number of tracks that will be ripped given `opts`, along
+	/// with the total number of sectors — overread included — to be read per
+	/// pass.
+	///
+	/// This mirrors the accounting done by the ripper itself, so tracks that
+	/// are missing or too short are left out.
+	pub fn rip_scope(&self, opts: &RipOptions) -> (usize, u64) {
+		let padding = u64::from(SECTOR_OVERREAD) * 2 - u64::from(opts.offset().sectors_abs());
+		htoa_tracks(&self.toc, *opts).tracks()
+			.filter_map(|idx|
+				if idx == 0 { self.toc.htoa() }
+				else { self.toc.audio_track(usize::from(idx)) }
+			)
+			.filter(|&t| ! track_too_short(t, opts))
+			.fold((0, 0), |(len, sectors), t| (len + 1, sectors + t.duration().sectors() + padding))
+	}
+
+	/// # Eject.
+	///
+	/// Eject the disc from the (first) drive, consuming the instance.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the drive refuses or doesn't support the
+	/// request.
+	pub fn eject(self) -> Result<(), RipRipError> { self.cdio.eject() }
+
+	/// # Scratch Files.
+	///
+	/// Return the paths of the rip state files belonging to this disc, or if
+	/// `all` is true, the paths of every state file regardless of disc.
+	///
+	/// Exported tracks are never included.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the cache cannot be read.
+	pub fn scratch_files(&self, all: bool) -> Result<Vec<PathBuf>, RipRipError> {
+		if all { scratch_files(None) }
+		else { scratch_files(Some(cache_prefix(&self.toc))) }
+	}
+
 	#[must_use]
 	/// # Table of Contents.
 	pub const fn toc(&self) -> &Toc { &self.toc }
@@ -233,6 +527,26 @@ impl Disc {
 	#[must_use]
 	/// # Internal CDIO.
 	pub(super) const fn cdio(&self) -> &LibcdioInstance { &self.cdio }
+
+	/// # Second CDIO Instance (and Offset).
+	pub(super) fn second_cdio(&self) -> Option<(&LibcdioInstance, ReadOffset)> {
+		self.second.as_ref().map(|(cdio, offset, _)| (cdio, *offset))
+	}
+
+	/// # Set Read Speed.
+	///
+	/// Ask the drive(s) to read at the `RipOptions::speed`, if any. A
+	/// rejected speed isn't worth giving up over, so is merely reported as a
+	/// warning.
+	fn set_speed(&self, opts: &RipOptions) {
+		let speed = opts.speed();
+		if speed == 0 { return; }
+
+		let second = self.second.as_ref().map(|(cdio, _, _)| cdio);
+		for cdio in std::iter::once(&self.cdio).chain(second) {
+			if let Err(e) = cdio.set_speed(speed) { Msg::warning(e.to_string()).eprint(); }
+		}
+	}
 }
 
 impl Disc {
@@ -241,40 +555,55 @@ impl Disc {
 	/// Rip the disc using the chosen options, extracting the track(s)
 	/// afterward.
 	///
+	/// If `RipOptions::whole_disc` is set, the audio is ripped as a single
+	/// continuous stream and sliced into tracks at the end instead.
+	///
+	/// The final details for each track — paths, confidences, sample
+	/// qualities — are returned (in order) for anybody who needs them.
+	///
 	/// ## Errors
 	///
 	/// This will bubble up any IO/rip/etc. errors encountered along the way.
 	pub fn rip(&self, opts: &RipOptions, progress: &Progless, killed: &KillSwitch)
-	-> Result<(), RipRipError> {
+	-> Result<Vec<RipTrackResult>, RipRipError> {
 		use std::io::Write;
 
+		// Make sure nobody else is working on this disc.
+		let _lock = CacheLock::new(&self.toc, opts.unlock())?;
+		set_durable_saves(opts.durable_saves());
+		self.set_speed(opts);
+		let opts = &opts.with_drives([self.drive_vendor_model(), self.second_drive_vendor_model()]);
+
 		// Handle all the ripping business!
-		let mut rip = Ripper::new(self, opts)?;
-		rip.rip(progress, killed)?;
-		rip.summarize();
+		let results =
+			if opts.whole_disc() { rip_whole_disc(self, opts, progress, killed)? }
+			else {
+				let mut rip = Ripper::new(self, opts)?;
+				rip.rip(progress, killed)?;
+				rip.summarize();
+				rip.results()
+			};
 
 		// Mention all the file paths and statuses, and maybe build a cue
 		// sheet to go along with them.
-		if let Some(saved) = rip.finish() {
+		if let Some(saved) = saved_rips(&results, opts) {
 			let writer = std::io::stderr();
 			let mut handle = writer.lock();
 			let mut total = 0;
 			let mut good = 0;
 
 			let htoa_any = saved.contains_key(&0);
+			let htoa_only = saved.keys().all(|&k| k == 0);
 			let htoa_likely = saved.get(&0).is_some_and(|(_, ar, ctdb)| ar.is_some() || ctdb.is_some());
 			let conf = saved.values().any(|(_, ar, ctdb)| ar.is_some() || ctdb.is_some());
+			let low_ctdb = saved.iter().any(|(&k, (_, _, ctdb))| k != 0 && *ctdb == Some(1));
 			let col1 = saved.first_key_value().map_or(0, |(_, (dst, _, _))| dst.to_string_lossy().len());
 
 			let _res = writeln!(&mut handle, "\nThe fruits of your labor:");
 
-			// If we did all tracks, make a cue sheet.
-			if let Some(file) = save_cuesheet(&self.toc, &saved) {
-				let _res = writeln!(
-					&mut handle,
-					"  \x1b[2m{}\x1b[0m",
-					file.display(),
-				);
+			// If we did all tracks, make a cue sheet (and maybe a TOC, etc.).
+			for file in save_sheets(self, &saved, opts)? {
+				let _res = writeln!(&mut handle, "  \x1b[2m{}\x1b[0m", file.display());
 			}
 
 			for (idx, (file, ar, ctdb)) in saved {
@@ -302,7 +631,7 @@ impl Disc {
 				"  {line: >width$}  AccurateRip  CUETools  \x1b[2m(\x1b[0;{color}m{good}\x1b[0;2m/\x1b[0m{total}\x1b[2m)\x1b[0m",
 				line="",
 				width=col1,
-				color=if good == 0 { COLOR_BAD } else { COLOR_CONFIRMED },
+				color=if good == 0 { COLOR_BAD.as_str() } else { COLOR_CONFIRMED.as_str() },
 			);
 
 			// Mention that the HTOA can't be verified but is probably okay.
@@ -329,13 +658,274 @@ impl Disc {
 				);
 			}
 
+			// Mention how much of the HTOA is silence, to help tell hidden
+			// tracks apart from plain old pregap.
+			if htoa_any && ! opts.whole_disc() {
+				if let Ok(Some((start, end, total))) = htoa_silence(&self.toc, opts) {
+					let _res = writeln!(&mut handle, "{}", fmt_htoa_silence(start, end, total, opts.trim_htoa()));
+				}
+			}
+
+			// Mention that lone CTDB matches aren't worth much.
+			if low_ctdb {
+				let _res = writeln!(
+					&mut handle,
+					"\n\x1b[{COLOR_LIKELY}m!\x1b[0;2m A CTDB confidence of 1 is a single, unverified submission;"
+				);
+				let _res = writeln!(
+					&mut handle,
+					"  it was accepted per --ctdb-single, but is \x1b[0;{COLOR_LIKELY}mlow-confidence\x1b[0;2m.\x1b[0m"
+				);
+			}
+
+			// Mention that AccurateRip simply doesn't know about the disc, if
+			// that's why nothing matched there.
+			if conf && ! htoa_only && ! opts.no_accuraterip() {
+				if let Some(note) = ar_unlisted_note(&self.toc) {
+					let _res = write!(&mut handle, "{note}");
+				}
+			}
+
 			// An extra line break for separation.
 			let _res = writeln!(&mut handle).and_then(|()| handle.flush());
 		}
 
+		// Mention the tracks held back per --verified-only, if any.
+		if let Some(msg) = pending_msg(&results, opts) { msg.eprint(); }
+
+		Ok(results)
+	}
+
+	/// # Is Track Confirmed?
+	///
+	/// Check whether or not a track — by number — was already ripped and
+	/// confirmed by AccurateRip and/or CUETools during a previous session,
+	/// according to the confidence threshold set in `opts`.
+	///
+	/// This is a quiet, scripting-friendly alternative to `Disc::status`;
+	/// nothing is read from the disc, and nothing is printed or changed.
+	///
+	/// Tracks without a saved state, and the HTOA, return `false`.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the track isn't on the disc, or its state
+	/// is corrupt.
+	pub fn is_track_confirmed(&self, idx: u8, opts: &RipOptions)
+	-> Result<bool, RipRipError> {
+		let track =
+			if idx == 0 { self.toc.htoa() }
+			else { self.toc.audio_track(usize::from(idx)) }
+			.ok_or(RipRipError::NoTrack(idx))?;
+		track_confirmed(&self.toc, track, opts)
+	}
+
+	/// # All Tracks Confirmed?
+	///
+	/// Returns `true` if resuming and every track selected by `opts` — too
+	/// short ones aside — was already confirmed by AccurateRip and/or
+	/// CUETools during a previous session — and exported, with the cue sheet
+	/// still in place — i.e. there's nothing left to rip or save.
+	///
+	/// Paranoid re-reads, minimum passes, partial fresh starts, and
+	/// whole-disc rips work on confirmed data too, so this always returns
+	/// `false` for those. The same goes for selections including the HTOA,
+	/// which can never be confirmed.
+	///
+	/// The extra exports — other bit depths, lossy copies, `bext`/`INFO`
+	/// chunks, single-file albums, checksums, submission files, and cdrdao
+	/// TOCs — aren't tracked, so this returns `false` whenever any are
+	/// requested to make sure they get (re)written.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if any of the states are corrupt.
+	pub fn all_confirmed(&self, opts: &RipOptions) -> Result<bool, RipRipError> {
+		if
+			! opts.resume() ||
+			opts.from_track().is_some() ||
+			opts.paranoid() ||
+			opts.whole_disc() ||
+			opts.min_passes() != 0 ||
+			! opts.has_tracks() ||
+			opts.bits() != 16 ||
+			opts.lossy() ||
+			opts.bwf() ||
+			opts.wav_info() ||
+			opts.single_file() ||
+			opts.sha256() ||
+			opts.submit_file() ||
+			opts.cdrdao_toc()
+		{
+			return Ok(false);
+		}
+
+		let mut all = 0;
+		for idx in htoa_tracks(&self.toc, *opts).tracks() {
+			let track =
+				if idx == 0 { self.toc.htoa() }
+				else { self.toc.audio_track(usize::from(idx)) }
+				.ok_or(RipRipError::NoTrack(idx))?;
+			if track_too_short(track, opts) { continue; }
+			if ! track_confirmed(&self.toc, track, opts)? || ! track_exported(&self.toc, track) {
+				return Ok(false);
+			}
+			all += 1;
+		}
+
+		// A full set of tracks should have a cue sheet to go with it.
+		if all == self.toc.audio_len() {
+			let cue = format!("{}.cue", cache_prefix(&self.toc));
+			if ! output_dir()?.join(cue).is_file() { return Ok(false); }
+		}
+
+		Ok(true)
+	}
+
+	/// # Rip Sectors.
+	///
+	/// Rip an arbitrary range of (normalized) sectors — exclusive of the end —
+	/// into a single WAV file instead of the usual track-by-track business.
+	///
+	/// This is mainly useful for data that doesn't line up with the TOC, such
+	/// as hidden or mis-indexed audio. Because there is no track to match,
+	/// the result cannot be verified against AccurateRip or CUETools.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered, including invalid ranges.
+	pub fn rip_sectors(
+		&self,
+		rng: Range<i32>,
+		opts: &RipOptions,
+		progress: &Progless,
+		killed: &KillSwitch,
+	) -> Result<(), RipRipError> {
+		// Make sure nobody else is working on this disc.
+		let _lock = CacheLock::new(&self.toc, opts.unlock())?;
+		set_durable_saves(opts.durable_saves());
+		self.set_speed(opts);
+		let opts = &opts.with_drives([self.drive_vendor_model(), self.second_drive_vendor_model()]);
+
+		let dst = rip_sectors(self, rng, opts, progress, killed)?;
+		eprintln!(
+			"\nThe fruits of your labor:\n  \x1b[2m{}\x1b[0m\n",
+			dst.display(),
+		);
+
 		Ok(())
 	}
 
+	/// # Probe Read Offset.
+	///
+	/// Rip a single track — the first one selected — from scratch and sweep
+	/// it against CUETools at every shift within the overread range (`±5880`
+	/// samples, ignoring any lower `--ctdb-shift`), then recommend the read
+	/// offset that best lines up with the database.
+	///
+	/// This is meant for drives missing from the offset database; nothing is
+	/// saved, so the real rip can be run afterward with the suggested
+	/// `-o/--offset`.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered along the way.
+	pub fn probe_offset(&self, opts: &RipOptions, progress: &Progless, killed: &KillSwitch)
+	-> Result<(), RipRipError> {
+		// Make sure nobody else is working on this disc.
+		let _lock = CacheLock::new(&self.toc, opts.unlock())?;
+		self.set_speed(opts);
+
+		let Some((idx, shift, conf)) = probe_offset(self, opts, progress, killed)? else {
+			Msg::warning("No CUETools matches were found at any offset; try a different track or disc.")
+				.eprint();
+			return Ok(());
+		};
+
+		let current = opts.offset().samples();
+		let suggested = current.checked_add(shift).and_then(|n| ReadOffset::try_from(n).ok());
+		if let Some(suggested) = suggested {
+			Msg::success(format!(
+				"Track #{idx:02} best matched CUETools (confidence {conf}) at a shift of {shift:+} samples; try \x1b[1m-o {}\x1b[0m.",
+				suggested.samples(),
+			)).eprint();
+		}
+		else {
+			Msg::warning(format!(
+				"Track #{idx:02} best matched CUETools (confidence {conf}) at a shift of {shift:+} samples, but {current:+} {shift:+} is not a valid read offset.",
+			)).eprint();
+		}
+
+		Ok(())
+	}
+
+	/// # Measure Drive Cache.
+	///
+	/// Estimate the size of the drive's read buffer by timing re-reads of the
+	/// first audio sector after progressively larger amounts of filler, and
+	/// suggest a matching `-c/--cache`.
+	///
+	/// This is meant for drives the compiled cache list is missing or wrong
+	/// about; nothing is saved.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any read errors, or return `RipRipError::Killed`
+	/// if the user aborts.
+	pub fn cache_test(&self, killed: &KillSwitch) -> Result<(), RipRipError> {
+		let lsn = self.toc.audio_tracks().next()
+			.and_then(|t| i32::try_from(t.sector_range_normalized().start).ok())
+			.ok_or(RipRipError::Noop)?;
+		let leadout = i32::try_from(self.toc.audio_leadout_normalized())
+			.map_err(|_| RipRipError::RipOverflow)?;
+
+		// The filler comes from the back half of the disc, well away from the
+		// sector being tested.
+		let fill = (leadout / 2).max(lsn + 1)..leadout;
+
+		Msg::custom("Testing", 199, "Measuring the drive's read buffer; this could take a few minutes…")
+			.eprint();
+		match self.cdio.cache_test(lsn, fill, killed)? {
+			Some(kib) => Msg::success(format!(
+				"The drive's read buffer appears to be about {kib} KiB; try \x1b[1m-c {kib}\x1b[0m.",
+			)).eprint(),
+			None => Msg::warning(
+				"The drive doesn't seem to be buffering audio sectors, so -c/--cache probably isn't needed."
+			).eprint(),
+		}
+
+		Ok(())
+	}
+
+	/// # Merge State.
+	///
+	/// Fold the rip states found in `src` — another rip's scratch directory,
+	/// presumably made with a different drive — into the states for the
+	/// selected tracks, returning the number of tracks that changed.
+	///
+	/// Saved samples are offset-corrected, so this works across drives so
+	/// long as each was ripped with its own correct offset. States that have
+	/// already been merged are skipped.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the other states are corrupt or belong to
+	/// a different disc, or there are I/O problems or the user aborts.
+	pub fn merge_state<P>(
+		&self,
+		src: P,
+		opts: &RipOptions,
+		progress: &Progless,
+		killed: &KillSwitch,
+	) -> Result<usize, RipRipError>
+	where P: AsRef<Path> {
+		// Make sure nobody else is working on this disc.
+		let _lock = CacheLock::new(&self.toc, opts.unlock())?;
+		set_durable_saves(opts.durable_saves());
+
+		Ripper::new(self, opts)?.merge(src.as_ref(), progress, killed)
+	}
+
 	/// # Status.
 	///
 	/// Print the status information for each track, if any.
@@ -352,22 +942,138 @@ impl Disc {
 
 		Ok(())
 	}
+
+	/// # Status (One Line).
+	///
+	/// Print a terse, single-line status for the disc as a whole — CDDB ID,
+	/// confirmed tracks, and worst track — to STDOUT. This is meant for
+	/// `watch`-style monitoring.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if there are I/O problems or the user aborts.
+	pub fn status_oneline(&self, opts: &RipOptions, progress: &Progless, killed: &KillSwitch)
+	-> Result<(), RipRipError> {
+		// Load the ripper.
+		let mut rip = Ripper::new(self, opts)?;
+		rip.status(progress, killed)?;
+		println!("{}", rip.summarize_status_oneline());
+
+		Ok(())
+	}
 }
 
 
 
+/// # Read Table of Contents.
+///
+/// Query the drive for the disc's table of contents, returning it along with
+/// the disc's own numbers for each of its audio tracks.
+///
+/// ## Errors
+///
+/// This will return an error if the disc is missing or unsupported, or the
+/// data is otherwise unreadable.
+fn read_toc(cdio: &LibcdioInstance) -> Result<(Toc, Vec<u8>), RipRipError> {
+	// The inclusive range to search.
+	let from = cdio.first_track_num()?;
+	let len = cdio.num_tracks()?;
+	if REDBOOK_MAX_TRACKS < len { return Err(RipRipError::TooManyTracks(len)); }
+	let rng = disc_track_range(from, len).ok_or(RipRipError::NumTracks)?;
+
+	// Grab the position and type for each track.
+	let mut tracks = Vec::with_capacity(usize::from(len));
+	for idx in rng {
+		tracks.push((idx, cdio.track_lba_start(idx)?, cdio.track_format(idx)?));
+	}
+
+	// Sort out the audio and data, grab the leadout, then build the ToC. A
+	// leadout at or before the first track means the ToC is junk.
+	let (audio, data, nums) = toc_parts(&tracks)?;
+	let leadout = cdio.leadout_lba()?;
+	if audio.first().is_some_and(|&start| leadout <= start) {
+		return Err(RipRipError::TocEmpty);
+	}
+	Ok((Toc::from_parts(audio, data, leadout)?, nums))
+}
+
+/// # ToC Parts.
+///
+/// Split the `(number, start, audio)` track entries into the audio and data
+/// pieces needed by `Toc::from_parts`, along with the disc's own numbers for
+/// each of the audio tracks.
+///
+/// Data tracks are placed by sector rather than number: one sitting before
+/// all of the audio makes for a `TocKind::DataFirst` disc, one after it a
+/// `TocKind::CDExtra` disc. Anything else — multiple data tracks, or data
+/// sandwiched between audio — is an error.
+fn toc_parts(tracks: &[(u8, u32, bool)]) -> Result<TocParts, RipRipError> {
+	let mut audio = Vec::with_capacity(tracks.len());
+	let mut nums = Vec::with_capacity(tracks.len());
+	let mut data: Option<(u8, u32)> = None;
+	for &(idx, start, is_audio) in tracks {
+		if is_audio {
+			audio.push(start);
+			nums.push(idx);
+		}
+		else if data.is_some() { return Err(RipRipError::TrackFormat(idx)); }
+		else { data.replace((idx, start)); }
+	}
+
+	let Some((idx, start)) = data else { return Ok((audio, None, nums)); };
+	if
+		audio.iter().all(|&a| start < a) ||
+		audio.iter().all(|&a| a < start)
+	{
+		Ok((audio, Some(start), nums))
+	}
+	else { Err(RipRipError::TrackFormat(idx)) }
+}
+
+/// # Disc Track Range.
+///
+/// Return the inclusive range of the disc's own track numbers given the
+/// first number and total count, or `None` if they don't make sense. (Red
+/// Book caps track numbers at 99.)
+fn disc_track_range(first: u8, len: u8) -> Option<RangeInclusive<u8>> {
+	let last = first.checked_add(len.checked_sub(1)?)?;
+	if first == 0 || REDBOOK_MAX_TRACKS < last { None }
+	else { Some(first..=last) }
+}
+
+/// # Overlong Disc?
+///
+/// Returns `true` if the disc runs past the Redbook maximum of 80 minutes.
+const fn is_overlong(toc: &Toc) -> bool {
+	REDBOOK_MAX_SECTORS < toc.leadout_normalized()
+}
+
+/// # Disc Track Number.
+///
+/// Convert a `Toc` audio track number — which always starts at one — to the
+/// disc's own numbering (as returned by `read_toc`) for libcdio lookups.
+/// Zero, i.e. the disc as a whole, is passed through as-is.
+///
+/// This is only used internally; everything user-facing — `-t/--tracks`,
+/// the summary, exported file names, etc. — sticks with the `Toc` numbers,
+/// same as AccurateRip and CUETools.
+fn disc_track_num(nums: &[u8], idx: u8) -> u8 {
+	if idx == 0 { 0 }
+	else { nums.get(usize::from(idx - 1)).copied().unwrap_or(idx) }
+}
+
 /// # Format AccurateRip.
-fn fmt_ar(ar: Option<(u8, u8)>) -> Cow<'static, str> {
+pub(crate) fn fmt_ar(ar: Option<(u8, u8)>) -> Cow<'static, str> {
 	if let Some((v1, v2)) = ar {
 		let c1 =
-			if v1 == 0 { COLOR_BAD }
-			else if v1 <= 5 { COLOR_LIKELY }
-			else { COLOR_CONFIRMED };
+			if v1 == 0 { COLOR_BAD.as_str() }
+			else if v1 <= 5 { COLOR_LIKELY.as_str() }
+			else { COLOR_CONFIRMED.as_str() };
 
 		let c2 =
-			if v2 == 0 { COLOR_BAD }
-			else if v2 <= 5 { COLOR_LIKELY }
-			else { COLOR_CONFIRMED };
+			if v2 == 0 { COLOR_BAD.as_str() }
+			else if v2 <= 5 { COLOR_LIKELY.as_str() }
+			else { COLOR_CONFIRMED.as_str() };
 
 		Cow::Owned(format!(
 			"        \x1b[0;{c1}m{:02}\x1b[0;2m+\x1b[0;{c2}m{:02}\x1b[0m",
@@ -378,51 +1084,126 @@ fn fmt_ar(ar: Option<(u8, u8)>) -> Cow<'static, str> {
 	else { Cow::Borrowed("             ") }
 }
 
+/// # AccurateRip Unlisted Note.
+///
+/// If the disc has no AccurateRip entry at all, return a note explaining that
+/// the lack of matches there isn't necessarily the rip's fault.
+///
+/// If the databases _do_ know about the disc with a leadout one sector off,
+/// the note instead points the finger at the drive's TOC reporting.
+pub(crate) fn ar_unlisted_note(toc: &Toc) -> Option<String> {
+	if chk_accuraterip_listed(toc) == Some(false) {
+		if let Some(delta) = chk_leadout_quirk(toc) {
+			return Some(format!(
+				"\n\x1b[{COLOR_LIKELY}m*\x1b[0;2m This disc isn't in the AccurateRip database as-is, but the checksum\n  databases \x1b[0;1mdo\x1b[0;2m know a disc with a leadout one sector {}. The drive is\n  probably misreporting the TOC, which would break matching.\x1b[0m\n",
+				if delta < 0 { "earlier" } else { "later" },
+			));
+		}
+
+		Some(format!(
+			"\n\x1b[{COLOR_LIKELY}m*\x1b[0;2m This disc isn't in the AccurateRip database (yet), so there was\n  nothing to match against there. That doesn't mean the rip is bad!\x1b[0m\n"
+		))
+	}
+	else { None }
+}
+
+/// # Format HTOA Silence.
+///
+/// Describe the leading/trailing silence of the HTOA.
+fn fmt_htoa_silence(start: usize, end: usize, total: usize, trimmed: bool) -> String {
+	/// # Samples to Seconds.
+	fn secs(n: usize) -> f64 {
+		u32::try_from(n).map_or(f64::MAX, f64::from) / 44_100.0
+	}
+
+	if start == total {
+		"\n\x1b[2m  The HTOA is nothing but silence, so is probably just pregap.\x1b[0m".to_owned()
+	}
+	else {
+		format!(
+			"\n\x1b[2m  The HTOA has {:.2}s of leading and {:.2}s of trailing silence,\n  leaving {:.2}s of possible audio{}.\x1b[0m",
+			secs(start),
+			secs(end),
+			secs(total - start - end),
+			if trimmed && (start != 0 || end != 0) { " (trimmed)" } else { "" },
+		)
+	}
+}
+
 #[expect(clippy::option_if_let_else, reason = "Too messy.")]
 /// # Format CUETools.
-fn fmt_ctdb(ctdb: Option<u16>) -> Cow<'static, str> {
+pub(crate) fn fmt_ctdb(ctdb: Option<u16>) -> Cow<'static, str> {
 	if let Some(v1) = ctdb {
 		let c1 =
-			if v1 == 0 { COLOR_BAD }
-			else if v1 <= 5 { COLOR_LIKELY }
-			else { COLOR_CONFIRMED };
+			if v1 == 0 { COLOR_BAD.as_str() }
+			else if v1 <= 5 { COLOR_LIKELY.as_str() }
+			else { COLOR_CONFIRMED.as_str() };
 
 		Cow::Owned(format!(
-			"       \x1b[0;{c1}m{:03}\x1b[0m",
+			"       \x1b[0;{c1}m{:03}{}\x1b[0m",
 			v1.min(999),
+			if v1 == 1 { "!" } else { "" },
 		))
 	}
 	else { Cow::Borrowed("          ") }
 }
 
+/// # Cue Sheet Offset Comment.
+///
+/// Note the read offset applied to the rip — and whether it was given by the
+/// user (`MANUAL`), detected from the offset database (`AUTO`), or neither
+/// (`DEFAULT`) — so the sheet documents how the audio was aligned.
+fn cue_offset_rem(disc: &Disc, opts: &RipOptions) -> String {
+	let offset = opts.offset();
+	let kind =
+		if opts.manual_offset() { "MANUAL" }
+		else if disc.drive_vendor_model().and_then(|vm| vm.detect_offset()).is_some() { "AUTO" }
+		else { "DEFAULT" };
+	format!("REM RIPRIP_OFFSET {:+} {kind}\n", offset.samples())
+}
+
+/// # Format MSF.
+///
+/// Format a sector count as a cue-style `MM:SS:FF` timecode: minutes,
+/// seconds, and frames (sectors).
+fn fmt_msf(sectors: u64) -> String {
+	format!("{:02}:{:02}:{:02}", sectors / 75 / 60, sectors / 75 % 60, sectors % 75)
+}
+
+/// # Merged Pregap.
+///
+/// Return the duration of the HTOA if it is being merged into the first
+/// track's export.
+fn merged_pregap(toc: &Toc, opts: &RipOptions) -> Option<Duration> {
+	toc.htoa()
+		.filter(|_| matches!(opts.htoa_mode(), HtoaMode::Merge))
+		.map(|t| t.duration())
+}
+
 /// # Generate CUE Sheet if Complete.
-fn save_cuesheet(toc: &Toc, ripped: &SavedRips) -> Option<PathBuf> {
+fn save_cuesheet(disc: &Disc, ripped: &SavedRips, opts: &RipOptions) -> Option<PathBuf> {
 	use std::fmt::Write;
 
-	// Make sure all tracks on the disc have been ripped, and pair their file
-	// names with the corresponding Track object.
-	let mut all = Vec::with_capacity(ripped.len());
-	for track in toc.audio_tracks() {
-		let (dst, _, _) = ripped.get(&track.number())?;
-		let dst = dst.file_name().and_then(OsStr::to_str)?;
-		all.push((track, dst));
+	let (parent, htoa, all) = ripped_files(&disc.toc, ripped, opts.htoa_mode())?;
+	let pregap = merged_pregap(&disc.toc, opts);
+	let mut cue = cue_offset_rem(disc, opts);
+	if let Some(barcode) = disc.barcode {
+		writeln!(&mut cue, "CATALOG {}", barcode.as_str()).ok()?;
+	}
+	else if let Some(raw) = disc.barcode_raw.as_deref() {
+		writeln!(&mut cue, "REM CATALOG \"{raw}\" UNVALIDATED").ok()?;
 	}
-
-	// The output folder.
-	let parent = ripped.get(&1).and_then(|(dst, _, _)| dst.parent())?;
-
-	let mut cue = String::new();
 	for (track, src) in all {
-		// If there's an HTOA, it needs to be grouped with the first track.
-		if track.position().is_first() && toc.htoa().is_some() {
-			// This should have been ripped with everything else.
-			let src0 = ripped.get(&0)
-				.and_then(|(dst, _, _)| dst.file_name())
-				.and_then(OsStr::to_str)?;
+		let flags =
+			if disc.preemphasis(track.number()) { "    FLAGS PRE\n" }
+			else { "" };
 
+		// If there's an HTOA, it needs to be grouped with the first track.
+		if let Some(src0) = htoa.filter(|_| track.position().is_first()) {
 			// Add the lines to our cue!
 			writeln!(&mut cue, "FILE \"{src0}\" WAVE").ok()?;
 			cue.push_str("  TRACK 01 AUDIO\n");
+			cue.push_str(flags);
 			cue.push_str("    INDEX 00 00:00:00\n");
 			writeln!(&mut cue, "FILE \"{src}\" WAVE").ok()?;
 			cue.push_str("    INDEX 01 00:00:00\n");
@@ -431,21 +1212,415 @@ fn save_cuesheet(toc: &Toc, ripped: &SavedRips) -> Option<PathBuf> {
 			continue;
 		}
 
-		// All other tracks are just file/track/index.
+		// A merged HTOA is the first track's pregap, in the same file.
+		if let Some(pregap) = pregap.filter(|_| track.position().is_first()) {
+			writeln!(&mut cue, "FILE \"{src}\" WAVE").ok()?;
+			cue.push_str("  TRACK 01 AUDIO\n");
+			cue.push_str(flags);
+			cue.push_str("    INDEX 00 00:00:00\n");
+			writeln!(&mut cue, "    INDEX 01 {}", fmt_msf(pregap.sectors())).ok()?;
+			continue;
+		}
+
+		// All other tracks are just file/track/(flags)/index.
 		writeln!(&mut cue, "FILE \"{src}\" WAVE").ok()?;
 		writeln!(&mut cue, "  TRACK {:02} AUDIO", track.number()).ok()?;
+		cue.push_str(flags);
 		cue.push_str("    INDEX 01 00:00:00\n");
 	}
 
 	// Save the cue sheet!
-	let dst = parent.join(format!("{}.cue", cache_prefix(toc)));
-	{
-		use std::io::Write;
-		let mut writer = CacheWriter::new(&dst).ok()?;
-		writer.writer().write_all(cue.as_bytes()).ok()?;
-		writer.finish().ok()?;
+	save_sheet(parent.join(format!("{}.cue", cache_prefix(&disc.toc))), &cue)
+}
+
+/// # Generate Sheets if Complete.
+///
+/// Save the cue sheet, along with whatever else the options call for — TOC,
+/// AccurateRip submission, single-file album — returning the paths of
+/// everything written.
+///
+/// ## Errors
+///
+/// Like the track exports, a failure to write the single-file album or its
+/// checksum is returned as an error.
+fn save_sheets(disc: &Disc, ripped: &SavedRips, opts: &RipOptions)
+-> Result<Vec<PathBuf>, RipRipError> {
+	let sheets = [
+		save_cuesheet(disc, ripped, opts),
+		if opts.cdrdao_toc() { save_toc(disc, ripped, opts) } else { None },
+		if opts.submit_file() { save_submission(disc, ripped, opts) } else { None },
+	];
+	let album = if opts.single_file() { save_album(disc, ripped, opts)? } else { None };
+	let sum = match album.as_ref() {
+		Some([_, wav]) if opts.sha256() => Some(save_sha256(wav)?),
+		_ => None,
+	};
+	Ok(
+		sheets.into_iter()
+			.flatten()
+			.chain(album.into_iter().flatten())
+			.chain(sum)
+			.collect()
+	)
+}
+
+/// # Generate Single-File Album if Complete.
+///
+/// Join the HTOA (if any) and tracks into one gapless WAV, and save a cue
+/// sheet for it with each track's `INDEX 01` positioned according to its
+/// sector range. Both paths are returned if successful.
+///
+/// A trimmed HTOA would throw all of the positions off, so nothing is saved
+/// in that case.
+///
+/// ## Errors
+///
+/// This will return an error if the album or its cue sheet cannot be
+/// written.
+fn save_album(disc: &Disc, ripped: &SavedRips, opts: &RipOptions)
+-> Result<Option<[PathBuf; 2]>, RipRipError> {
+	let Some((parent, htoa, all)) = ripped_files(&disc.toc, ripped, opts.htoa_mode()) else {
+		return Ok(None);
+	};
+	if htoa.is_some() && opts.trim_htoa() { return Ok(None); }
+
+	// Work out the cue sheet first; there's no point joining anything if the
+	// positions don't add up.
+	let dst = album_path(&disc.toc)?;
+	let Some(cue) = dst.file_name()
+		.and_then(OsStr::to_str)
+		.and_then(|name| album_cue(disc, opts, &all, name))
+	else { return Ok(None); };
+
+	// Join the files.
+	let srcs: Vec<PathBuf> = htoa.into_iter()
+		.chain(all.iter().map(|(_, src)| *src))
+		.map(|src| parent.join(src))
+		.collect();
+	let wav = save_album_wav(dst, &srcs)?;
+
+	// Save the cue sheet!
+	let dst = parent.join(format!("{}__album.cue", cache_prefix(&disc.toc)));
+	let cue = save_sheet(dst.clone(), &cue)
+		.ok_or_else(|| RipRipError::Write(dst.to_string_lossy().into_owned()))?;
+	Ok(Some([cue, wav]))
+}
+
+/// # Single-File Album Cue Sheet.
+///
+/// Build the cue sheet for the single-file album `name`, or return `None`
+/// if the track positions don't make sense.
+fn album_cue(disc: &Disc, opts: &RipOptions, all: &[(Track, &str)], name: &str)
+-> Option<String> {
+	use std::fmt::Write;
+
+	// Everything is relative to the start of the first file, which includes
+	// the HTOA unless it is being ignored.
+	let lead = disc.toc.htoa().filter(|_| ! matches!(opts.htoa_mode(), HtoaMode::Ignore));
+	let base = match lead {
+		Some(t) => t.sector_range_normalized().start,
+		None => all.first()?.0.sector_range_normalized().start,
+	};
+
+	let mut cue = cue_offset_rem(disc, opts);
+	writeln!(&mut cue, "FILE \"{name}\" WAVE").ok()?;
+	for (track, _) in all {
+		writeln!(&mut cue, "  TRACK {:02} AUDIO", track.number()).ok()?;
+		if disc.preemphasis(track.number()) { cue.push_str("    FLAGS PRE\n"); }
+		if lead.is_some() && track.position().is_first() {
+			cue.push_str("    INDEX 00 00:00:00\n");
+		}
+
+		let pos = track.sector_range_normalized().start.checked_sub(base)?;
+		writeln!(&mut cue, "    INDEX 01 {}", fmt_msf(u64::from(pos))).ok()?;
 	}
 
-	// Return the path.
+	Some(cue)
+}
+
+/// # Generate cdrdao TOC if Complete.
+///
+/// This is like `save_cuesheet`, but in cdrdao's own TOC format, which also
+/// carries the barcode, ISRCs, and CD-Text.
+fn save_toc(disc: &Disc, ripped: &SavedRips, opts: &RipOptions) -> Option<PathBuf> {
+	use std::fmt::Write;
+
+	let (parent, htoa, all) = ripped_files(&disc.toc, ripped, opts.htoa_mode())?;
+	let pregap = merged_pregap(&disc.toc, opts);
+	let mut out = String::from("CD_DA\n");
+	if let Some(barcode) = disc.barcode {
+		writeln!(&mut out, "CATALOG \"{}\"", barcode.as_str()).ok()?;
+	}
+	else if let Some(raw) = disc.barcode_raw.as_deref() {
+		writeln!(&mut out, "// CATALOG \"{raw}\" (unvalidated)").ok()?;
+	}
+
+	// Disc-wide CD-Text requires a language map; we'll just assume English.
+	let cdtext = all.iter()
+		.any(|(t, _)| cdrdao_text(disc, t.number()).is_some());
+	if cdtext {
+		out.push_str("\nCD_TEXT {\n  LANGUAGE_MAP {\n    0 : EN\n  }\n  LANGUAGE 0 {\n");
+		if let Some(text) = cdrdao_text(disc, 0) { out.push_str(&text); }
+		out.push_str("  }\n}\n");
+	}
+
+	for (track, src) in all {
+		let idx = track.number();
+		writeln!(&mut out, "\n// Track {idx:02}\nTRACK AUDIO").ok()?;
+		if disc.preemphasis(idx) { out.push_str("PRE_EMPHASIS\n"); }
+		if let Some(isrc) = disc.isrc(idx).filter(|v| is_isrc(v)) {
+			writeln!(&mut out, "ISRC \"{isrc}\"").ok()?;
+		}
+		if cdtext {
+			out.push_str("CD_TEXT {\n  LANGUAGE 0 {\n");
+			if let Some(text) = cdrdao_text(disc, idx) { out.push_str(&text); }
+			out.push_str("  }\n}\n");
+		}
+
+		// The HTOA is the first track's pregap.
+		if let Some(src0) = htoa.filter(|_| track.position().is_first()) {
+			writeln!(&mut out, "FILE \"{}\" 0\nSTART", cdrdao_str(src0)).ok()?;
+		}
+		writeln!(&mut out, "FILE \"{}\" 0", cdrdao_str(src)).ok()?;
+
+		// Or a merged one, within the same file.
+		if let Some(pregap) = pregap.filter(|_| track.position().is_first()) {
+			writeln!(&mut out, "START {}", fmt_msf(pregap.sectors())).ok()?;
+		}
+	}
+
+	// Save the TOC!
+	save_sheet(parent.join(format!("{}.toc", cache_prefix(&disc.toc))), &out)
+}
+
+/// # Ripped Files.
+///
+/// Make sure all tracks on the disc have been ripped, and if so, return the
+/// output folder, the HTOA file name (if any, and exported separately), and
+/// the file names for everything else paired with their corresponding
+/// `Track` objects.
+fn ripped_files<'a>(toc: &Toc, ripped: &'a SavedRips, htoa_mode: HtoaMode)
+-> Option<RippedFiles<'a>> {
+	let mut all = Vec::with_capacity(ripped.len());
+	for track in toc.audio_tracks() {
+		let (dst, _, _) = ripped.get(&track.number())?;
+		let dst = dst.file_name().and_then(OsStr::to_str)?;
+		all.push((track, dst));
+	}
+
+	// If there's a separate HTOA, it should have been ripped with everything
+	// else.
+	let htoa =
+		if toc.htoa().is_some() && matches!(htoa_mode, HtoaMode::Separate) {
+			let src0 = ripped.get(&0)
+				.and_then(|(dst, _, _)| dst.file_name())
+				.and_then(OsStr::to_str)?;
+			Some(src0)
+		}
+		else { None };
+
+	// The output folder.
+	let parent = ripped.get(&1).and_then(|(dst, _, _)| dst.parent())?;
+
+	Some((parent, htoa, all))
+}
+
+/// # Save Sheet.
+///
+/// Write a cue/TOC sheet (or similar) to disk, returning the path if
+/// successful.
+fn save_sheet<S: AsRef<[u8]>>(dst: PathBuf, src: S) -> Option<PathBuf> {
+	use std::io::Write;
+
+	let mut writer = CacheWriter::new(&dst).ok()?;
+	writer.writer().write_all(src.as_ref()).ok()?;
+	writer.finish().ok()?;
 	Some(dst)
 }
+
+/// # cdrdao CD-Text.
+///
+/// Return the CD-Text fields for the disc (`0`) or track, formatted for
+/// inclusion in a cdrdao `LANGUAGE` block, or `None` if there aren't any.
+fn cdrdao_text(disc: &Disc, idx: u8) -> Option<String> {
+	use std::fmt::Write;
+
+	let mut out = String::new();
+	for (kind, key) in [
+		(CDTextKind::Title, "TITLE"),
+		(CDTextKind::Performer, "PERFORMER"),
+		(CDTextKind::Songwriter, "SONGWRITER"),
+		(CDTextKind::Composer, "COMPOSER"),
+		(CDTextKind::Arranger, "ARRANGER"),
+		(CDTextKind::Message, "MESSAGE"),
+	] {
+		let v = disc.cdio.cdtext(disc_track_num(&disc.track_nums, idx), kind);
+		if let Some(v) = v.filter(|v| ! v.trim().is_empty()) {
+			let _res = writeln!(&mut out, "    {key} \"{}\"", cdrdao_str(v.trim()));
+		}
+	}
+
+	if out.is_empty() { None }
+	else { Some(out) }
+}
+
+/// # cdrdao String.
+///
+/// Escape backslashes and double quotes so the value can be safely wrapped in
+/// quotes.
+fn cdrdao_str(src: &str) -> Cow<'_, str> {
+	if src.contains(['\\', '"']) {
+		Cow::Owned(src.replace('\\', "\\\\").replace('"', "\\\""))
+	}
+	else { Cow::Borrowed(src) }
+}
+
+/// # Pending Message.
+///
+/// Return a message listing the tracks that weren't exported — i.e. held back
+/// per `RipOptions::verified_only` — if any.
+fn pending_msg(results: &[RipTrackResult], opts: &RipOptions) -> Option<Msg> {
+	if ! opts.verified_only() { return None; }
+	let pending: Vec<u8> = results.iter()
+		.filter_map(|r| r.dst().is_none().then_some(r.track()))
+		.collect();
+	if pending.is_empty() { return None; }
+
+	let one = pending.len() == 1;
+	Some(
+		Msg::custom("Pending", 199, &format!(
+			"Track{} {} {} yet to be verified, so {} been left in the cache for more passes.",
+			if one { "" } else { "s" },
+			JoinFmt::new(pending.iter().map(|n| format!("#{n:02}")), ", "),
+			if one { "is" } else { "are" },
+			if one { "has" } else { "have" },
+		))
+			.with_newline(true)
+	)
+}
+
+/// # Is ISRC?
+///
+/// cdrdao is strict about ISRC formatting: two letters for the country,
+/// three alphanumerics for the owner, then seven digits for the year and
+/// serial.
+fn is_isrc(src: &str) -> bool {
+	let src = src.as_bytes();
+	src.len() == 12 &&
+	src[..2].iter().all(u8::is_ascii_uppercase) &&
+	src[2..5].iter().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit()) &&
+	src[5..].iter().all(u8::is_ascii_digit)
+}
+
+
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn t_cdrdao_str() {
+		assert_eq!(cdrdao_str("Hello World"), "Hello World");
+		assert_eq!(cdrdao_str(r#"The "Best" \ Worst"#), r#"The \"Best\" \\ Worst"#);
+	}
+
+	#[test]
+	fn t_is_isrc() {
+		assert!(is_isrc("USRC17607839"));
+		assert!(is_isrc("GBAYE0000351"));
+		assert!(! is_isrc("US-RC1-76-07839"));
+		assert!(! is_isrc("usrc17607839"));
+		assert!(! is_isrc("USRC1760783"));
+	}
+
+	#[test]
+	fn t_disc_track_num() {
+		// The usual case.
+		assert_eq!(disc_track_range(1, 10), Some(1..=10));
+		let plain = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+		assert_eq!(disc_track_num(&plain, 0), 0);
+		assert_eq!(disc_track_num(&plain, 1), 1);
+		assert_eq!(disc_track_num(&plain, 5), 5);
+		assert_eq!(disc_track_num(&plain, 10), 10);
+
+		// A disc numbering its tracks from three.
+		assert_eq!(disc_track_range(3, 4), Some(3..=6));
+		let offset = [3, 4, 5, 6];
+		assert_eq!(disc_track_num(&offset, 0), 0);
+		assert_eq!(disc_track_num(&offset, 1), 3);
+		assert_eq!(disc_track_num(&offset, 2), 4);
+		assert_eq!(disc_track_num(&offset, 4), 6);
+
+		// Gaps in the disc's numbering, e.g. skipped data tracks.
+		let gapped = [2, 3, 7];
+		assert_eq!(disc_track_num(&gapped, 1), 2);
+		assert_eq!(disc_track_num(&gapped, 2), 3);
+		assert_eq!(disc_track_num(&gapped, 3), 7);
+
+		// Anything past the end falls back to itself.
+		assert_eq!(disc_track_num(&offset, 5), 5);
+		assert_eq!(disc_track_num(&[], 2), 2);
+
+		// Nonsense.
+		assert_eq!(disc_track_range(0, 4), None);
+		assert_eq!(disc_track_range(3, 0), None);
+		assert_eq!(disc_track_range(98, 3), None);
+		assert_eq!(disc_track_range(1, 100), None);
+		assert_eq!(disc_track_range(1, 99), Some(1..=99));
+	}
+
+	#[test]
+	fn t_toc_parts() {
+		// Plain audio.
+		let (audio, data, nums) = toc_parts(&[(1, 150, true), (2, 20_000, true)])
+			.expect("Audio ToC failed.");
+		assert_eq!(audio, vec![150, 20_000]);
+		assert_eq!(data, None);
+		assert_eq!(nums, vec![1, 2]);
+
+		// Data-first; the audio starts at disc track two.
+		let (audio, data, nums) = toc_parts(&[(1, 0, false), (2, 20_000, true), (3, 30_000, true)])
+			.expect("Data-first ToC failed.");
+		assert_eq!(audio, vec![20_000, 30_000]);
+		assert_eq!(data, Some(0));
+		let toc = Toc::from_parts(audio, data, 50_000).expect("Invalid ToC.");
+		assert_eq!(toc.kind(), TocKind::DataFirst);
+		let mapped: Vec<u8> = toc.audio_tracks()
+			.map(|t| disc_track_num(&nums, t.number()))
+			.collect();
+		assert_eq!(mapped, vec![2, 3]);
+
+		// CD-Extra numbered from three; the old index check would have
+		// rejected the trailing data track.
+		let tracks = [(3, 150, true), (4, 20_000, true), (5, 40_000, false)];
+		let (audio, data, nums) = toc_parts(&tracks).expect("CD-Extra ToC failed.");
+		assert_eq!(audio, vec![150, 20_000]);
+		assert_eq!(data, Some(40_000));
+		assert_eq!(nums, vec![3, 4]);
+		let toc = Toc::from_parts(audio, data, 50_000).expect("Invalid ToC.");
+		assert_eq!(toc.kind(), TocKind::CDExtra);
+
+		// Data sandwiched between audio.
+		assert!(matches!(
+			toc_parts(&[(1, 150, true), (2, 10_000, false), (3, 20_000, true)]),
+			Err(RipRipError::TrackFormat(2)),
+		));
+
+		// Too much data.
+		assert!(matches!(
+			toc_parts(&[(1, 150, true), (2, 20_000, false), (3, 30_000, false)]),
+			Err(RipRipError::TrackFormat(3)),
+		));
+	}
+
+	#[test]
+	fn t_is_overlong() {
+		let toc = Toc::from_cdtoc("4+96+2D2B+6256+B327+D84A").expect("Invalid TOC.");
+		assert!(! is_overlong(&toc));
+
+		// Ninety minutes.
+		let toc = Toc::from_parts(vec![150, 200_000], None, 150 + 90 * 60 * 75)
+			.expect("Invalid TOC.");
+		assert!(is_overlong(&toc));
+	}
+}
@@ -3,13 +3,18 @@
 */
 
 use crate::{
+	output_dir,
 	RipRipError,
 	SAMPLES_PER_SECTOR,
 };
 use dactyl::traits::BytesToSigned;
+use fyi_msg::Msg;
 use std::{
+	collections::BTreeMap,
 	fmt,
 	ops::RangeInclusive,
+	path::PathBuf,
+	sync::OnceLock,
 };
 
 
@@ -29,6 +34,16 @@ const DRIVE_VENDOR_LEN: usize = 8;
 /// # Max Drive Model Length.
 const DRIVE_MODEL_LEN: usize = 16;
 
+/// # User Drive Offsets File.
+///
+/// This optional file, living in the output directory, can be used to
+/// override the built-in offsets. It uses the same format as
+/// `skel/drive-cache.txt`.
+const USER_OFFSETS_FILE: &str = "drive-offsets.txt";
+
+/// # User Drive Offsets.
+static USER_OFFSETS: OnceLock<Vec<(DriveVendorModel, ReadOffset)>> = OnceLock::new();
+
 // The data generated by build.rs. It is a constant array of known
 // (DriveVendorModel, ReadOffset) pairs, and another of known cache sizes.
 include!(concat!(env!("OUT_DIR"), "/drives.rs"));
@@ -74,11 +89,23 @@ impl DriveVendorModel {
 	/// Validate and parse separate vendor and model strings into our special
 	/// model.
 	///
+	/// No drive is required, so this can also be used to look up the offset
+	/// or cache size for an arbitrary vendor/model pair, like:
+	///
+	/// ```
+	/// use riprip_core::DriveVendorModel;
+	///
+	/// let vm = DriveVendorModel::new("Pioneer", "BD-RW   BDR-XD05").unwrap();
+	/// assert_eq!(vm.to_string(), "PIONEER BD-RW BDR-XD05");
+	/// assert_eq!(vm.detect_offset().map(|o| o.samples()), Some(667));
+	/// assert_eq!(vm.detect_cache(), Some(4096));
+	/// ```
+	///
 	/// ## Errors
 	///
 	/// This will return an error if the lengths are out of range, or the
 	/// model number is missing.
-	pub(crate) fn new(mut vendor: &str, mut model: &str) -> Result<Self, RipRipError> {
+	pub fn new(mut vendor: &str, mut model: &str) -> Result<Self, RipRipError> {
 		vendor = vendor.trim();
 		model = model.trim();
 
@@ -131,13 +158,51 @@ impl DriveVendorModel {
 		Some(DRIVE_CACHES[idx].1)
 	}
 
+	#[must_use]
+	/// # User Offset.
+	///
+	/// Return the offset listed for this exact vendor/model pair in the
+	/// user's `drive-offsets.txt`, if any.
+	pub fn user_offset(&self) -> Option<ReadOffset> {
+		let user = user_offsets();
+		let idx = user.binary_search_by_key(self, |(k, _)| *k).ok()?;
+		Some(user[idx].1)
+	}
+
 	#[must_use]
 	/// # Detect Offset.
 	///
 	/// If the vendor/model pair are known, return the drive offset.
+	///
+	/// Entries in the user's `drive-offsets.txt`, if any, take
+	/// priority over the built-in AccurateRip list.
+	///
+	/// Some drives don't report a vendor at all. If that's the case and there
+	/// is no exact match, the model will be checked against every entry
+	/// regardless of vendor, returning the offset only if it is unambiguous.
 	pub fn detect_offset(&self) -> Option<ReadOffset> {
-		let idx = DRIVE_OFFSETS.binary_search_by_key(self, |(k, _)| *k).ok()?;
-		Some(DRIVE_OFFSETS[idx].1)
+		if let Some(offset) = self.user_offset() { return Some(offset); }
+
+		if let Ok(idx) = DRIVE_OFFSETS.binary_search_by_key(self, |(k, _)| *k) {
+			return Some(DRIVE_OFFSETS[idx].1);
+		}
+
+		// Model-only fallback.
+		if self.0[0] != 0 { return None; }
+		let model = &self.0[DRIVE_VENDOR_LEN..];
+		let mut found = None;
+		for (k, v) in &DRIVE_OFFSETS {
+			if &k.0[DRIVE_VENDOR_LEN..] == model {
+				match found {
+					None => { found.replace(*v); },
+					// Same model, different offset; we can't know which is right.
+					Some(old) if old != *v => return None,
+					Some(_) => {},
+				}
+			}
+		}
+
+		found
 	}
 }
 
@@ -217,6 +282,76 @@ impl ReadOffset {
 
 
 
+/// # User Drive Offsets Path.
+///
+/// Return the path to the (optional) user drive offset overrides file, i.e.
+/// `drive-offsets.txt` in the output directory.
+///
+/// ## Errors
+///
+/// This will return an error if the output directory cannot be determined.
+pub fn user_offsets_path() -> Result<PathBuf, RipRipError> {
+	output_dir().map(|dir| dir.join(USER_OFFSETS_FILE))
+}
+
+/// # User Drive Offsets.
+///
+/// Load and parse the user's drive offset overrides the first time they're
+/// needed, returning them sorted for binary search.
+fn user_offsets() -> &'static [(DriveVendorModel, ReadOffset)] {
+	USER_OFFSETS.get_or_init(||
+		user_offsets_path().ok()
+			.and_then(|path| std::fs::read_to_string(path).ok())
+			.map_or_else(Vec::new, |raw| parse_user_offsets(&raw))
+	)
+}
+
+/// # Parse User Drive Offsets.
+///
+/// Parse the raw file contents, one `VENDOR…MODEL… OFFSET` entry per line,
+/// in the same fixed-width format as `skel/drive-cache.txt`. Invalid lines
+/// are reported and skipped; if a pair is listed more than once, the last
+/// entry wins.
+fn parse_user_offsets(raw: &str) -> Vec<(DriveVendorModel, ReadOffset)> {
+	let mut out = BTreeMap::new();
+	for line in raw.lines() {
+		if line.starts_with('#') || line.trim().is_empty() { continue; }
+		if let Some((vm, offset)) = parse_user_offset_line(line) {
+			out.insert(vm, offset);
+		}
+		else {
+			Msg::warning(format!("Invalid {USER_OFFSETS_FILE} line: {line}")).eprint();
+		}
+	}
+
+	out.into_iter().collect()
+}
+
+/// # Parse a Single User Drive Offset.
+///
+/// To make the file easier to edit, null bytes are represented as ellipses,
+/// so the line should be 24 characters of (padded) vendor/model, followed
+/// by the offset.
+fn parse_user_offset_line(line: &str) -> Option<(DriveVendorModel, ReadOffset)> {
+	let line = line.replace('…', "\0");
+	if ! line.is_ascii() || line.len() < 26 { return None; }
+
+	let (vm, offset) = line.split_at(DRIVE_VENDOR_LEN + DRIVE_MODEL_LEN);
+	let mut vm: [u8; 24] = vm.as_bytes().try_into().ok()?;
+	vm.make_ascii_uppercase();
+
+	// The model is required.
+	if vm[DRIVE_VENDOR_LEN] == 0 { return None; }
+
+	let offset = offset.trim();
+	if offset.is_empty() { return None; }
+	let offset = ReadOffset::try_from(offset).ok()?;
+
+	Some((DriveVendorModel(vm), offset))
+}
+
+
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -242,6 +377,41 @@ mod test {
 		assert_eq!(vm.to_string(), "PIONEER BD-RW BDR-XD05");
 		assert_eq!(vm.detect_offset(), Some(ReadOffset(667)));
 		assert_eq!(vm.detect_cache(), Some(4096));
+
+		// The offset should still be found without a vendor.
+		let vm = DriveVendorModel::new("", "BD-RW   BDR-XD05")
+			.expect("Unable to create DriveVendorModel.");
+		assert_eq!(vm.vendor(), "");
+		assert_eq!(vm.detect_offset(), Some(ReadOffset(667)));
+
+		// But not if the model is bunk.
+		let vm = DriveVendorModel::new("", "Nope Nope Nope")
+			.expect("Unable to create DriveVendorModel.");
+		assert_eq!(vm.detect_offset(), None);
+	}
+
+	#[test]
+	fn t_user_offsets() {
+		let raw = "# Comment.\n\
+			PIONEER…BD-RW   BDR-XD05 6\n\
+			pioneer…BD-RW   BDR-XD05 -12\n\
+			……………………NOPE………………………… nope\n\
+			\n\
+			…………………………………………………………………………\n\
+			HL-DT-STBD-RE  WH16NS40… 6\n";
+		let parsed = parse_user_offsets(raw);
+		assert_eq!(parsed.len(), 2);
+
+		// The later Pioneer entry should win.
+		let vm = DriveVendorModel::new("Pioneer", "BD-RW   BDR-XD05")
+			.expect("Unable to create DriveVendorModel.");
+		let idx = parsed.binary_search_by_key(&vm, |(k, _)| *k)
+			.expect("Missing Pioneer.");
+		assert_eq!(parsed[idx].1, ReadOffset(-12));
+
+		let vm = DriveVendorModel::new("HL-DT-ST", "BD-RE  WH16NS40")
+			.expect("Unable to create DriveVendorModel.");
+		assert!(parsed.binary_search_by_key(&vm, |(k, _)| *k).is_ok());
 	}
 
 	#[test]
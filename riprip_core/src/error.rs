@@ -3,6 +3,7 @@
 */
 
 use cdtoc::TocError;
+use dactyl::NiceU64;
 use fyi_msg::Msg;
 use std::{
 	error::Error,
@@ -39,13 +40,18 @@ BASIC SETTINGS:
     -p, --passes <NUM>
                       Automate re-ripping by executing up to <NUM> passes for
                       each track while any samples remain unread or
-                      unconfirmed. [default: 1; max: 16]
+                      unconfirmed. Use "auto" to keep going (up to the max)
+                      until a pass changes nothing. [default: 1; max: 16]
     -t, --tracks <NUM(s),RNG>
                       Rip one or more specific tracks (rather than the whole
                       disc). Multiple tracks can be separated by commas (2,3),
                       specified as an inclusive range (2-3), and/or given their
                       own -t/--track (-t 2 -t 3). Track 0 can be used to rip
-                      the HTOA, if any. [default: the whole disc]
+                      the HTOA, if any. Audio tracks are always counted from
+                      one — as AccurateRip, CUETools, and the exported file
+                      names count them — even if the disc's own numbering
+                      starts later or leads with a data track.
+                      [default: the whole disc]
 
 WHEN ALL ELSE FAILS:
         --backwards   Reverse the sector read order when ripping a track,
@@ -53,16 +59,69 @@ WHEN ALL ELSE FAILS:
         --flip-flop   Alternate the sector read order between passes, forwards
                       then backwards then forwards then backwards… This has no
                       effect unless -p/--passes is at least two.
+        --confused-only
+                      Only re-read sectors containing "confused" samples —
+                      those with many contradictory "good" values — until
+                      they become likely, leaving everything else alone.
+                      Pair with --sync to help break the tie.
+        --merge-state <DIR>
+                      Fold the rip states from <DIR> — the scratch directory
+                      of another rip of the same disc, e.g. with a different
+                      drive — into the current ones before ripping. Both rips
+                      must have used the correct offsets for their drives.
+                      States that have already been merged are skipped.
+        --no-accuraterip
+                      Skip the AccurateRip database when verifying rips, e.g.
+                      when offline or the service is down.
+        --no-ctdb     Skip the CUETools database when verifying rips, e.g.
+                      when offline or the service is down.
+        --no-overread Skip the ten sectors of padding normally read on either
+                      side of each track. This can help drives that choke on
+                      reads past the lead-out, but makes shifted CUETools
+                      matches less reliable.
         --no-resume   Ignore any previous rip states, starting over from
                       scratch.
+        --from-track <NUM>
+                      Resume the previous rip states for tracks before <NUM>
+                      as usual, but start track <NUM> and everything after it
+                      over from scratch, i.e. --no-resume for those tracks
+                      only. This has no effect on --whole-disc rips.
         --reset       Flip "likely" samples back to "maybe", keeping their
                       values, but resetting all counts to one. This is a softer
                       alternative to --no-resume, and will not affect tracks
                       confirmed by AccurateRip/CUETools.
+        --retry-shitlisted
+                      Give sectors that triggered hard read errors another
+                      chance at the start of each pass instead of avoiding
+                      them for the rest of the run, e.g. after reseating or
+                      cleaning the disc.
+        --skip-corrupt
+                      Skip (and list) any tracks whose rip states are too
+                      corrupt to load, rather than aborting the whole rip.
+        --c2-granularity <MODE>
+                      How much of a sector a C2 error casts doubt upon:
+                      sample trusts everything C2 doesn't flag; sector keeps
+                      the unflagged values — filling in blanks — but won't
+                      let them count toward confirming existing ones. A
+                      middle ground between the default and --strict.
+                      [default: sample]
         --strict      Consider C2 errors an all-or-nothing proposition for the
                       sector as a whole, marking all samples bad if any of them
                       are bad. This is most effective when applied consistently
                       from the initial rip and onward.
+        --strict-after <NUM>
+                      Same as --strict, but only from pass <NUM> onward, so
+                      earlier passes can accept partial sector data and later
+                      ones shake out inconsistencies. [default: 0 (disabled)]
+        --first-sector <LSN>
+                      Rip an arbitrary range of sectors — as numbered in the
+                      disc summary — to a single file, rather than tracks. This
+                      can help salvage audio the table of contents doesn't
+                      account for, but cannot be verified, and cannot be
+                      combined with -t/--tracks. [default: 0]
+        --last-sector <LSN>
+                      The last sector (inclusive) of a manual range rip.
+                      [default: the last audio sector]
 
 DRIVE SETTINGS:
     -c, --cache <NUM> Drive cache can interfere with re-read accuracy. If your
@@ -70,12 +129,34 @@ DRIVE SETTINGS:
                       size so Rip Rip can try to mitigate it. Values with an
                       M suffix are treated as MiB, otherwise KiB are assumed.
                       [default: auto or 0; max: 65,535]
+        --cache-bust-mode <MODE>
+                      How to clear the drive cache between passes. The fill
+                      mode reads enough sectors to cycle out the whole
+                      buffer; seek reads a single sector from the far end of
+                      the disc instead, which is much faster if the drive
+                      drops its buffer on long seeks. [default: fill]
     -d, --dev <PATH>  The device path for the optical drive containing the CD
-                      of interest, like /dev/cdrom. [default: auto]
+                      of interest, like /dev/cdrom. Give it twice — each
+                      pointing at a different drive holding its own copy of
+                      the disc — to alternate track passes between the two,
+                      cross-checking their reads. This requires at least two
+                      passes. (--whole-disc and sector range rips only use
+                      the first.) [default: auto]
     -o, --offset <SAMPLES>
                       The AccurateRip, et al, sample read offset to apply to
-                      data retrieved from the drive.
+                      data retrieved from the drive. When using two drives,
+                      a second -o/--offset applies to the second one.
                       [default: auto or 0; range: ±5880]
+        --on-existing <MODE>
+                      What to do when a track's WAV already exists but wasn't
+                      exported by Rip Rip, e.g. one left by another tool:
+                      overwrite it, skip the export (keeping the old file),
+                      or save to a numbered suffix like {CDDB ID}__01-1.wav
+                      instead. [default: overwrite]
+        --speed <NUM> Ask the drive(s) to read at <NUM>x speed. Slower reads
+                      — e.g. 1x — often recover more from damaged discs. If
+                      a drive rejects the value, a warning is printed and
+                      the rip carries on. [default: 0 (leave as is)]
 
 UNUSUAL SETTINGS:
         --confidence <NUM>
@@ -85,32 +166,224 @@ UNUSUAL SETTINGS:
                       personally fucked up the database(s) with prior bad rips,
                       otherwise the default should be fine. Haha.
                       [default: 3; range: 1..=10]
+        --ctdb-single Accept a CUETools confidence of 1 — a single, unverified
+                      submission — as a match, regardless of --confidence.
+                      Such matches are flagged as low-confidence in the
+                      summary.
+        --ctdb-shift <SAMPLES>
+                      Search for CUETools matches at sample shifts up to
+                      ±<SAMPLES> from the read offset, to account for pressing
+                      differences. Lower values speed up verification; zero
+                      only checks the exact offset.
+                      [default: 5880; range: 0..=5880]
+        --decay <NUM> Halve the counts of samples that haven't been seen in
+                      <NUM> passes (including those from previous runs),
+                      giving fresher reads a chance to outvote stale ones.
+                      This can help with drives whose output drifts over time.
+                      [default: 0 (disabled); max: 32]
+        --dump-c2     Append the raw C2 error bits for every sector read to a
+                      per-track .c2 file in the scratch directory, for drive
+                      diagnostics. Each record is the LSN (i32 LE), pass
+                      number (u8), and 294-byte C2 bitmap. This generates a
+                      lot of data.
+        --durable-saves
+                      Fsync state and track files as they're saved so they'll
+                      survive a power loss. This is slower, but worth it if
+                      your power is unreliable.
+        --htoa <MODE> What to do with the HTOA, if any: rip and save it as its
+                      own track, merge it into the front of track one's
+                      WAV (as its pregap), or ignore it entirely. Merging
+                      implies ripping it whenever track one is ripped.
+                      [default: separate; options: separate, merge, ignore]
+        --min-passes <NUM>
+                      Force at least <NUM> passes over each track, re-reading
+                      every sector — confirmed or not — each time, to build
+                      up local confidence on top of AccurateRip/CUETools.
+                      Confirmed tracks are only skipped once these are done.
+                      [default: 0; max: 16]
+        --min-track-sectors <NUM>
+                      Skip tracks shorter than <NUM> sectors — e.g. stray
+                      index markers rather than real audio — instead of
+                      ripping and verifying them. [default: 0]
+        --paranoid    After the last pass, re-read every sector of every track —
+                      even confirmed ones — one final time, and warn about any
+                      that contradict the current data. Nothing is changed
+                      unless a contradiction is found.
         --sync        Confirm sector positioning with subchannel data (when
                       available) to make sure the drive is actually reading
                       from the right place, and ignore the data if not. This is
                       prone to false-positives — subchannel data is easily
                       corrupted — so only recommended when disc rot, rather
                       than wear-and-tear, is the sole cause of your woes.
+        --sync-combined
+                      Same as --sync, but request the data, C2, and subchannel
+                      together in a single read instead of two separate ones,
+                      for drives that support it. Drives that don't will fall
+                      back to the usual method.
+        --trim-htoa   Trim leading and trailing silence from the exported HTOA
+                      (track 0), if any. The rip state and regular tracks are
+                      left alone.
+        --whole-disc  Rip all of the audio as one continuous stream, then slice
+                      it into tracks at export. This reads the overlap between
+                      adjacent tracks only once, which can help CUETools
+                      matching for gapless albums. Logging, lossy copies, C2
+                      dumps, and paranoid re-reads are not supported in this
+                      mode. Passes stop once every track is verified. Note
+                      that the whole disc is held in memory, which can run to
+                      several gigabytes.
 
 MISCELLANEOUS:
     -h, --help        Print help information to STDOUT and exit.
-    -v, --verbose     Print detailed sector quality information to STDOUT, so
-                      it can e.g. be piped to a file for review, like:
-                      riprip -v > issues.log
+    -v, --verbose     Print detailed sector quality information — and read
+                      latencies — to STDOUT, so it can e.g. be piped to a
+                      file for review, like: riprip -v > issues.log
     -V, --version     Print version information to STDOUT and exit.
+        --also-lossy  Save an Ogg Vorbis copy of each track alongside the WAV,
+                      for casual listening. This happens after the final pass,
+                      or as soon as a track is confirmed.
+        --append-tracks
+                      When re-ripping, leave a previously-exported track WAV
+                      alone if the new rip is worse than it was, e.g. after
+                      an interrupted --no-resume experiment. This doesn't
+                      apply to --whole-disc rips.
+        --batch       Keep going: after each rip, eject the disc, wait for the
+                      next one, and rip that too (with the same options)
+                      until interrupted with CTRL+C. Only the first disc's
+                      summary needs confirming. Discs that fail or can't be
+                      verified are skipped, but reflected in the final exit
+                      code; discs already ripped in the batch are ignored if
+                      reinserted. Fatal problems like a full disk stop the
+                      batch immediately.
+        --bwf         Include a Broadcast Wave (bext) chunk in each exported
+                      WAV, noting the rip date, drive, and AccurateRip/CTDB
+                      confidences, for archival provenance.
+        --cache-test  Measure the drive's read buffer by timing re-reads of
+                      one sector after progressively larger amounts of
+                      other data, then suggest a matching -c/--cache and
+                      exit. Handy for drives missing from (or wrong in) the
+                      built-in list.
+        --clean       Remove the rip state files for the current disc and exit.
+                      Exported tracks and other discs' data are left alone.
+        --clean-all   Remove all rip state files — for every disc — and exit.
+        --interpolate As a last resort, paper over any remaining bad or unread
+                      samples with a straight line between their nearest good
+                      neighbors when saving the WAVs. The result is NOT an
+                      accurate copy, and is marked as such in the WAV's INFO
+                      comment. The rip state itself is left alone.
+        --log-files   Append each track's problematic sectors and heatmap to
+                      its own {CDDB ID}__{TRACK}.log file in the scratch
+                      directory after every pass. This works with or without
+                      -v/--verbose.
         --no-rip      Print the basic drive and disc information to STDERR and
                       exit (without ripping anything).
         --no-summary  Skip the drive and disc summary and jump straight to
                       ripping.
+        --offset-report
+                      Print the drive's vendor/model, the read offset and
+                      cache size detected for it — noting where each came
+                      from and any -o/--offset or -c/--cache override —
+                      then exit.
+        --probe-offset
+                      Rip the first selected track once — without saving
+                      anything — then sweep it against CUETools at every
+                      shift within ±5880 samples — the full overread,
+                      regardless of --ctdb-shift — and recommend the
+                      -o/--offset that matches best, then exit. Handy for
+                      drives missing from the offset database.
+        --sha256      After saving each track (and album) WAV, hash the file
+                      as written and save the result to a .sha256 sidecar
+                      next to it, ready for checking with sha256sum -c.
+        --single-file Also join the HTOA (if any) and tracks into a single
+                      gapless WAV — with a matching cue sheet — once all
+                      tracks have been ripped. (Incompatible with --trim-htoa
+                      unless the HTOA is merged or ignored.)
         --status      Print the status of the individual track rips (that you
                       presumably already started) to STDERR and exit. Note that
                       only the --no-summary, --confidence, and -r/--rereads
                       options have any meaning in this mode.
+        --status-oneline
+                      Same as --status, but print a single line to STDOUT —
+                      CDDB ID, confirmed tracks, and worst unconfirmed track —
+                      without the drive and disc summary. Handy for
+                      watch-style monitoring.
+        --submit-file Once every track has been verified, save the
+                      AccurateRip v1/v2 checksums to a dBAR-style
+                      {CDDB ID}__ar-submit.bin alongside the cue sheet, for
+                      contributing back via a supported tool. Nothing is
+                      uploaded.
+        --toc         Save a cdrdao-style .toc file — with barcode, ISRCs, and
+                      CD-Text, where available — alongside the .cue sheet once
+                      all tracks have been ripped.
+        --unlock      If the lock preventing concurrent rips of the same disc
+                      is held, try again after a moment, then report the
+                      process holding it. (Locks are released automatically
+                      when their holders exit, so they are never overridden.)
+        --verified-only
+                      Only export tracks — WAVs, lossy copies, etc. — once
+                      they've been verified by AccurateRip and/or CUETools,
+                      leaving the rest in the cache for more passes later.
+                      The HTOA, being unverifiable, is never exported in this
+                      mode.
+        --wav-info    Include a RIFF LIST/INFO chunk in each exported WAV,
+                      noting the AccurateRip/CTDB confidences, pass count, and
+                      Rip Rip version, so the files can vouch for themselves.
+        --bits <NUM>  Save the WAVs with 24 or 32 bits per sample instead of
+                      16, for DAWs that prefer them. The samples are simply
+                      left-shifted, so this is lossless. [default: 16]
+        --verify-toc <CDTOC>
+                      Re-verify previously exported tracks against AccurateRip
+                      and CUETools using the CDTOC from the disc summary, then
+                      exit. No disc is required, but the tracks must be in
+                      ./_riprip with their original file names.
+        --verify-existing <DIR>
+                      Use with --verify-toc to re-verify track WAVs from some
+                      other folder — e.g. those produced by another ripper —
+                      instead. The files are sorted by name and paired with
+                      the tracks in order. If they were saved without the
+                      drive's read offset applied, pass it along with -o.
+    -y, --yes         Skip the confirmation prompt for --clean/--clean-all.
+
+DIRECTORIES:
+    Exported tracks are saved to ./_riprip, and rip states and such to
+    ./_riprip/scratch. These can be changed independently — e.g. to keep the
+    states on a faster disk — with the environment variables RIPRIP_OUTPUT_DIR
+    and RIPRIP_SCRATCH_DIR respectively. If the working directory has been
+    deleted, the system's temporary directory is used instead.
+
+    To verify rips with pre-downloaded checksums, point RIPRIP_CHECKSUM_DIR
+    at a directory of AccurateRip (dBAR-….bin) and CUETools ({CTDB ID}.xml)
+    responses; anything missing from it is looked up online as usual. Set
+    RIPRIP_CHECKSUM_MODE to offline to leave the network alone entirely.
+
+    Online lookups can be sent with a custom user agent and extra headers —
+    "Name: value", one per line — via RIPRIP_USER_AGENT and
+    RIPRIP_HTTP_HEADERS respectively.
+
+COLORS:
+    The quality colors can be overridden with the environment variables
+    RIPRIP_COLOR_BAD, RIPRIP_COLOR_MAYBE, RIPRIP_COLOR_LIKELY, and
+    RIPRIP_COLOR_CONFIRMED, using ANSI SGR codes like 31 or 38;5;208.
+
+EXIT CODES:
+    0    Success.
+    1    Miscellaneous failure.
+    2    Invalid command line options.
+    3    No disc (or drive), or the disc's table of contents is unreadable.
+    4    The drive couldn't read the disc with the chosen settings.
+    5    Another rip is already in progress for the disc.
+    6    The rip finished, but not every track could be verified.
+    7    One or more discs in a --batch could not be ripped.
+    130  User abort.
 
 EARLY EXIT:
     If you don't have time to let a rip finish naturally, press "#, "\x1b[38;5;208mCTRL\x1b[0m+\x1b[38;5;208mC\x1b[0m to stop
     it early. Your progress will still be saved, there just won't be as much of
     it. Haha.
+
+PAUSING:
+    To free up the drive for a bit without ending the session, send the
+    process a SIGUSR1 — e.g. kill -USR1 <PID> — to pause between sectors, and
+    another to pick back up where it left off.
 ");
 
 
@@ -142,21 +415,36 @@ pub enum RipRipError {
 	/// # CD read operation terminal failure.
 	CdReadUnsupported,
 
+	/// # Unable to delete file.
+	Delete(String),
+
 	/// # Invalid device.
 	Device(String),
 
 	/// # Unable to open device.
 	DeviceOpen(Option<String>),
 
+	/// # Disc Changed.
+	DiscChanged,
+
+	/// # Out of Disk Space (Path, Bytes Saved).
+	DiskFull(String, u64),
+
 	/// # Unsupported Disc.
 	DiscMode,
 
 	/// # Invalid drive model.
 	DriveModel,
 
+	/// # Drive rejected the read speed.
+	DriveSpeed(u16),
+
 	/// # Invalid drive vendor.
 	DriveVendor,
 
+	/// # Drive refused to eject.
+	Eject,
+
 	/// # Unable to get first track number.
 	FirstTrackNum,
 
@@ -166,6 +454,12 @@ pub enum RipRipError {
 	/// # Unable to get leadout.
 	Leadout,
 
+	/// # Rip Locked.
+	Locked,
+
+	/// # Lossy Encoding.
+	Lossy(u8),
+
 	/// # Noop.
 	Noop,
 
@@ -181,15 +475,30 @@ pub enum RipRipError {
 	/// # Numbers can't be converted to the necessary types.
 	RipOverflow,
 
+	/// # Invalid Sector Range.
+	SectorRange,
+
 	/// # State Corruption.
 	StateCorrupt(u8),
 
+	/// # State Merge.
+	StateMerge(u8),
+
 	/// # State Save.
 	StateSave(u8),
 
+	/// # Invalid Second Drive.
+	SecondDrive(String),
+
 	/// # Subchannel Desync.
 	SubchannelDesync,
 
+	/// # Empty ToC.
+	TocEmpty,
+
+	/// # Too Many Tracks.
+	TooManyTracks(u8),
+
 	/// # Invalid/unsupported track format.
 	TrackFormat(u8),
 
@@ -199,9 +508,16 @@ pub enum RipRipError {
 	/// # Invalid track number.
 	TrackNumber(u8),
 
+	/// # Wrong Number of WAVs.
+	WavCount(usize, usize),
+
 	/// # Writing to disk.
 	Write(String),
 
+	#[cfg(feature = "bin")]
+	/// # Batch Failures.
+	BatchFailed(usize),
+
 	#[cfg(feature = "bin")]
 	/// # Invalid CLI arg.
 	CliArg(String),
@@ -217,6 +533,10 @@ pub enum RipRipError {
 	#[cfg(feature = "bin")]
 	/// # Print Version (Not an Error).
 	PrintVersion,
+
+	#[cfg(feature = "bin")]
+	/// # Unverified Rip (Not Quite an Error).
+	Unverified,
 }
 
 impl Error for RipRipError {}
@@ -242,18 +562,29 @@ impl fmt::Display for RipRipError {
 			Self::CdRead => f.write_str("Read error."),
 			Self::CdReadUnsupported => f.write_str("Unable to read CD; settings are probably wrong."),
 			Self::Cdtoc(s) => write!(f, "{s}"),
+			Self::Delete(ref s) => write!(f, "Unable to delete {s}."),
 			Self::Device(ref s) => write!(f, "Invalid device path {s}."),
 			Self::DeviceOpen(ref s) =>
 				if let Some(s) = s { write!(f, "Unable to open connection with {s}.") }
 				else {
 					f.write_str("Unable to open connection with default optical drive.")
 				},
+			Self::DiscChanged => f.write_str("The disc was removed or changed mid-rip; aborting."),
+			Self::DiskFull(ref s, n) => write!(
+				f,
+				"Ran out of disk space saving {s} ({} bytes were saved before that); free some up and try again.",
+				NiceU64::from(*n),
+			),
 			Self::DiscMode => f.write_str("Missing or unsupported disc type."),
 			Self::DriveModel => f.write_str("Invalid drive model."),
+			Self::DriveSpeed(n) => write!(f, "The drive rejected the {n}x read speed; leaving it as is."),
 			Self::DriveVendor => f.write_str("Invalid drive vendor."),
+			Self::Eject => f.write_str("The drive was unable to eject the disc."),
 			Self::FirstTrackNum => f.write_str("Unable to obtain the first track index."),
 			Self::Killed => f.write_str("User abort."),
 			Self::Leadout => f.write_str("Unable to obtain leadout."),
+			Self::Locked => f.write_str("Another rip is in progress for this disc; rerun with --unlock to see which process holds the lock."),
+			Self::Lossy(n) => write!(f, "Unable to save a lossy copy of track #{n}."),
 			Self::Noop => f.write_str("There's nothing to do!"),
 			Self::NoTrack(n) =>
 				if *n == 0 { f.write_str("There is no HTOA on this disc.") }
@@ -261,14 +592,27 @@ impl fmt::Display for RipRipError {
 			Self::NumTracks => f.write_str("Unable to obtain the track total."),
 			Self::ReadOffset => f.write_str("Invalid read offset."),
 			Self::RipOverflow => f.write_str("The numbers are too big for this system architecture."),
-			Self::StateCorrupt(n) => write!(f, "The state data for track #{n} is corrupt; rerip this track with --no-resume to start over."),
+			Self::SectorRange => f.write_str("Invalid sector range."),
+			Self::StateCorrupt(n) => write!(f, "The state data for track #{n} (and its backup) is corrupt; rerip this track with --no-resume to start over."),
+			Self::StateMerge(n) => write!(f, "The other state data for track #{n} is corrupt or belongs to a different disc; it could not be merged."),
 			Self::StateSave(n) => write!(f, "Unable to save the state data for track #{n}."),
+			Self::SecondDrive(ref s) => write!(f, "The second drive ({s}) must be a different device than the first — both given explicitly — holding a copy of the same disc."),
 			Self::SubchannelDesync => f.write_str("Subchannel desync."),
+			Self::TocEmpty => f.write_str("The disc's table of contents looks invalid — there's no room for audio between the lead-in and lead-out — so there's nothing to rip. Try reinserting the disc, or using a different drive."),
+			Self::TooManyTracks(n) => write!(f, "The drive reports {n} tracks, but audio CDs can have at most 99; the table of contents is probably garbage."),
 			Self::TrackFormat(n) => write!(f, "Unsupported track type ({n})."),
 			Self::TrackLba(n) => write!(f, "Unable to obtain LBA ({n})."),
 			Self::TrackNumber(n) => write!(f, "Invalid track number ({n})."),
+			Self::WavCount(a, b) => write!(f, "Expected {a} WAV files, but found {b}."),
 			Self::Write(ref s) => write!(f, "Unable to write to {s}."),
 
+			#[cfg(feature = "bin")]
+			Self::BatchFailed(n) => write!(
+				f,
+				"The batch finished, but {n} disc{} could not be ripped.",
+				if *n == 1 { "" } else { "s" },
+			),
+
 			#[cfg(feature = "bin")]
 			Self::CliArg(s) => write!(f, "Invalid CLI option: {s}"),
 
@@ -280,6 +624,45 @@ impl fmt::Display for RipRipError {
 
 			#[cfg(feature = "bin")]
 			Self::PrintVersion => f.write_str(concat!("Rip Rip Hooray! v", env!("CARGO_PKG_VERSION"))),
+
+			#[cfg(feature = "bin")]
+			Self::Unverified => f.write_str("The rip finished, but not every track could be verified."),
+		}
+	}
+}
+
+#[cfg(feature = "bin")]
+impl RipRipError {
+	#[must_use]
+	/// # Exit Code.
+	///
+	/// Return the process exit code corresponding to the error, as documented
+	/// in the help screen, so scripts can tell the different kinds of
+	/// failure apart.
+	pub const fn exit_code(&self) -> u8 {
+		match self {
+			Self::PrintHelp | Self::PrintVersion => 0,
+			Self::CliArg(_) | Self::CliParse(_) => 2,
+			Self::Device(_) |
+			Self::DeviceOpen(_) |
+			Self::DiscChanged |
+			Self::DiscMode |
+			Self::FirstTrackNum |
+			Self::Leadout |
+			Self::NumTracks |
+			Self::SecondDrive(_) |
+			Self::TocEmpty |
+			Self::TooManyTracks(_) |
+			Self::TrackLba(_) => 3,
+			Self::C2Mode296 |
+			Self::CdRead |
+			Self::CdReadUnsupported |
+			Self::SubchannelDesync => 4,
+			Self::Locked => 5,
+			Self::Unverified => 6,
+			Self::BatchFailed(_) => 7,
+			Self::Killed => 130,
+			_ => 1,
 		}
 	}
 }
@@ -54,44 +54,113 @@
 #![expect(clippy::redundant_pub_crate, reason = "Unresolvable.")]
 
 mod abort;
+mod archive;
 mod barcode;
 mod cache;
 mod cdio;
 mod cdtext;
 mod chk;
+mod color;
 mod disc;
 mod drive;
 mod error;
 mod rip;
+mod sha256;
 
 pub use abort::KillSwitch;
+pub use archive::{
+	verify_archive,
+	verify_existing,
+};
+pub(crate) use archive::{
+	read_wav,
+	read_wav_pregap,
+	wav_pcm,
+};
 pub use barcode::Barcode;
+pub use cache::{
+	output_dir,
+	output_dir_fallback,
+};
 pub(crate) use cache::{
-	cache_path,
+	album_path,
+	ar_submit_path,
+	backup_path,
+	c2_path,
 	cache_prefix,
+	CacheLock,
 	CacheWriter,
+	log_path,
+	lossy_path,
+	merged_path,
+	scratch_files,
+	scratch_path,
+	sectors_path,
+	set_durable_saves,
+	sectors_state_path,
 	state_path,
 	track_path,
+	write_error,
 };
 pub(crate) use cdio::LibcdioInstance;
 pub use cdtext::CDTextKind;
+pub use chk::{
+	accuraterip_id_from_toc,
+	ctdb_id_from_toc,
+};
 pub(crate) use chk::{
+	accuraterip_crcs,
+	accuraterip_ignored,
+	accuraterip_submission,
 	chk_accuraterip,
+	chk_accuraterip_listed,
 	chk_ctdb,
+	chk_leadout_quirk,
 };
+use color::Color;
 pub use disc::Disc;
+pub(crate) use disc::{
+	ar_unlisted_note,
+	fmt_ar,
+	fmt_ctdb,
+};
 pub use drive::{
 	DriveVendorModel,
 	ReadOffset,
+	user_offsets_path,
 };
 pub use error::RipRipError;
 pub(crate) use rip::{
 	buf::RipBuffer,
-	data::RipState,
+	data::{
+		accessible_range,
+		RipState,
+		save_album_wav,
+		save_sha256,
+		track_exported,
+	},
+	result::saved_rips,
+	probe::probe_offset,
 	sample::RipSample,
+	sectors::rip_sectors,
+	htoa_silence,
+	htoa_tracks,
+	track_confirmed,
+	track_too_short,
 	Ripper,
+	whole::rip_whole_disc,
+};
+pub use rip::{
+	opts::{
+		C2Granularity,
+		CacheBustMode,
+		ExistingMode,
+		HtoaMode,
+		RipOptions,
+	},
+	result::RipTrackResult,
 };
-pub use rip::opts::RipOptions;
+pub(crate) use sha256::Sha256;
 use std::{
 	collections::BTreeMap,
 	path::PathBuf,
@@ -112,12 +181,14 @@ type SavedRips = BTreeMap<u8, (PathBuf, Option<(u8, u8)>, Option<u16>)>;
 
 /// # Cache Base.
 ///
-/// The cache root is thus `CWD/CACHE_BASE`.
+/// The cache root is thus `CWD/CACHE_BASE`, unless overridden by the
+/// `RIPRIP_OUTPUT_DIR` environment variable.
 pub const CACHE_BASE: &str = "_riprip";
 
 /// # Cache Scratch.
 ///
-/// The scratch folder for non-track data, e.g. `CWD/CACHE_BASE/CACHE_SCRATCH`.
+/// The scratch folder for non-track data, e.g. `CWD/CACHE_BASE/CACHE_SCRATCH`,
+/// unless overridden by the `RIPRIP_SCRATCH_DIR` environment variable.
 const CACHE_SCRATCH: &str = "scratch";
 
 
@@ -126,16 +197,16 @@ const CACHE_SCRATCH: &str = "scratch";
 // ---------------
 
 /// # Color: Bad.
-const COLOR_BAD: &str = "91";
+static COLOR_BAD: Color = Color::new("RIPRIP_COLOR_BAD", "91");
 
 /// # Color: Maybe.
-const COLOR_MAYBE: &str = "38;5;208";
+static COLOR_MAYBE: Color = Color::new("RIPRIP_COLOR_MAYBE", "38;5;208");
 
 /// # Color: Likely.
-const COLOR_LIKELY: &str = "93";
+static COLOR_LIKELY: Color = Color::new("RIPRIP_COLOR_LIKELY", "93");
 
 /// # Color: Confirmed.
-const COLOR_CONFIRMED: &str = "92";
+static COLOR_CONFIRMED: Color = Color::new("RIPRIP_COLOR_CONFIRMED", "92");
 
 
 
@@ -189,6 +260,11 @@ const CD_DATA_C2_SIZE: u16 = CD_DATA_SIZE + CD_C2_SIZE;
 /// # Combined size of data/subchannel.
 const CD_DATA_SUBCHANNEL_SIZE: u16 = CD_DATA_SIZE + CD_SUBCHANNEL_SIZE;
 
+/// # Combined size of data/c2/subchannel.
+///
+/// Per the MMC spec, the C2 comes before the subchannel.
+const CD_DATA_C2_SUBCHANNEL_SIZE: u16 = CD_DATA_C2_SIZE + CD_SUBCHANNEL_SIZE;
+
 
 
 // Misc
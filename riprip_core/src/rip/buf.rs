@@ -4,6 +4,7 @@
 
 use crate::{
 	CD_DATA_C2_SIZE,
+	CD_DATA_C2_SUBCHANNEL_SIZE,
 	CD_DATA_SIZE,
 	CD_DATA_SUBCHANNEL_SIZE,
 	KillSwitch,
@@ -23,15 +24,53 @@ use std::ops::Range;
 /// All sorts of different buffer sizes are needed for different contexts. This
 /// struct eliminates a lot of the headache of figuring all that out.
 ///
-/// It is sized to accommodate the biggest dataset — audio + C2 — but gets
-/// sub-sliced for smaller reads too. One buffer for all!
-pub(crate) struct RipBuffer([u8; CD_DATA_C2_SIZE as usize]);
+/// It is sized to accommodate the biggest dataset — audio + C2 + subchannel
+/// — but gets sub-sliced for smaller reads too. One buffer for all!
+///
+/// It also keeps track of a few read-related odds and ends.
+pub(crate) struct RipBuffer {
+	/// # Buffer.
+	buf: [u8; CD_DATA_C2_SUBCHANNEL_SIZE as usize],
+
+	/// # Verified Sync Reads.
+	///
+	/// Subchannel-synced reads with a timecode to check against.
+	synced: u32,
+
+	/// # Unverified Sync Reads.
+	///
+	/// Subchannel-synced reads without a timecode; not every subchannel
+	/// frame carries one.
+	unsynced: u32,
+
+	/// # C2 Disabled.
+	///
+	/// The drive doesn't support C2.
+	no_c2: bool,
+
+	/// # C2 Fallback Warned.
+	no_c2_warned: bool,
+
+	/// # Combined Sync Reads Disabled.
+	///
+	/// The drive doesn't support C2 and subchannel in the same read.
+	no_combined: bool,
+}
 
 /// # Setters.
 impl RipBuffer {
 	#[inline]
 	/// # New Instance.
-	pub(crate) const fn new() -> Self { Self([0; CD_DATA_C2_SIZE as usize]) }
+	pub(crate) const fn new() -> Self {
+		Self {
+			buf: [0; CD_DATA_C2_SUBCHANNEL_SIZE as usize],
+			synced: 0,
+			unsynced: 0,
+			no_c2: false,
+			no_c2_warned: false,
+			no_combined: false,
+		}
+	}
 
 	#[inline]
 	/// # Cache Bust.
@@ -43,10 +82,10 @@ impl RipBuffer {
 		len: u32,
 		rng: &Range<i32>,
 		leadout: i32,
-		backwards: bool,
+		opts: &RipOptions,
 		killed: &KillSwitch,
 	) {
-		cdio.cache_bust(self.data_slice_mut(), len, rng, leadout, backwards, killed);
+		cdio.cache_bust(self.data_slice_mut(), len, rng, leadout, opts, killed);
 	}
 
 	/// # Read Sector.
@@ -54,7 +93,11 @@ impl RipBuffer {
 	/// Read a single sector from the disc into the buffer.
 	///
 	/// Depending on the options, this will fetch some combination of audio
-	/// data, C2 error pointers, and subchannel (for timestamp verification).
+	/// data, C2 error pointers, and subchannel (for timestamp verification),
+	/// either together or in separate reads.
+	///
+	/// The pass number is used to determine whether or not strict C2 handling
+	/// applies.
 	///
 	/// Returns `true` if no C2 or sync errors were reported.
 	///
@@ -62,17 +105,35 @@ impl RipBuffer {
 	///
 	/// This will return any I/O related errors encountered, or if timestamp
 	/// verification fails, a desync error.
-	pub(crate) fn read_sector(&mut self, cdio: &LibcdioInstance, lsn: i32, opts: &RipOptions)
-	-> Result<bool, RipRipError> {
+	pub(crate) fn read_sector(
+		&mut self,
+		cdio: &LibcdioInstance,
+		lsn: i32,
+		opts: &RipOptions,
+		pass: u8,
+	) -> Result<bool, RipRipError> {
+		let strict = opts.strict_pass(pass);
+		let c2 = opts.c2() && ! self.no_c2;
+
 		// Subchannel sync?
 		if opts.sync() {
-			self.read_subchannel(cdio, lsn)?;
+			// Everything at once?
+			if c2 && opts.sync_combined() && ! self.no_combined {
+				match self.read_c2_subchannel(cdio, lsn, strict) {
+					// Fall back to separate reads from here on out.
+					Err(RipRipError::CdReadUnsupported) => { self.no_combined = true; },
+					res => return res,
+				}
+			}
+
+			if self.read_subchannel(cdio, lsn)? { self.synced = self.synced.saturating_add(1); }
+			else { self.unsynced = self.unsynced.saturating_add(1); }
 
 			// Hash the data so we can compare it with the C2 version.
 			let hash = crc32fast::hash(self.data_slice());
 
 			// Read again with C2 details.
-			let good = self.read_c2(cdio, lsn, opts)?;
+			let good = self.read_c2(cdio, lsn, strict, c2)?;
 
 			// Make sure we got the same data both times.
 			if hash == crc32fast::hash(self.data_slice()) { Ok(good) }
@@ -80,7 +141,7 @@ impl RipBuffer {
 			else { Err(RipRipError::CdRead) }
 		}
 		// Normal read.
-		else { self.read_c2(cdio, lsn, opts) }
+		else { self.read_c2(cdio, lsn, strict, c2) }
 	}
 
 	/// # Read C2.
@@ -90,35 +151,84 @@ impl RipBuffer {
 	/// If strict mode is in effect and there are any C2 errors, all samples
 	/// will be marked as having an error.
 	///
+	/// If the drive doesn't support C2 — or `c2` is `false` — the data is
+	/// read on its own and assumed to be error-free. The former also disables
+	/// C2 for all subsequent reads.
+	///
 	/// Returns true if no C2 errors were reported.
-	fn read_c2(&mut self, cdio: &LibcdioInstance, lsn: i32, opts: &RipOptions)
+	fn read_c2(&mut self, cdio: &LibcdioInstance, lsn: i32, strict: bool, c2: bool)
 	-> Result<bool, RipRipError> {
+		if ! c2 { return self.read_data(cdio, lsn); }
+
 		// Just in case the read is bogus, let's flip all C2 to bad beforehand.
 		self.set_bad();
 
 		// Okay, read away!
-		cdio.read_cd_c2(&mut self.0, lsn)?;
+		match cdio.read_cd_c2(&mut self.buf[..usize::from(CD_DATA_C2_SIZE)], lsn) {
+			Ok(()) => {},
+			// Fall back to a plain read, but only the first time around.
+			Err(RipRipError::CdReadUnsupported) if ! self.no_c2 => {
+				let out = self.read_data(cdio, lsn)?;
+				self.no_c2 = true;
+				return Ok(out);
+			},
+			Err(e) => return Err(e),
+		}
 
 		// How'd we do?
 		let good = self.all_good();
 
 		// If we're in strict mode and there's any error, set all bits
 		// to error.
-		if opts.strict() && ! good { self.set_bad(); }
+		if strict && ! good { self.set_bad(); }
 
 		Ok(good)
 	}
 
+	/// # Read C2 + Subchannel.
+	///
+	/// Read the sector with C2 error pointers _and_ subchannel in one go,
+	/// verifying the timecode and applying strict mode the same as the
+	/// separate reads would.
+	///
+	/// Returns true if no C2 errors were reported.
+	fn read_c2_subchannel(&mut self, cdio: &LibcdioInstance, lsn: i32, strict: bool)
+	-> Result<bool, RipRipError> {
+		// Just in case the read is bogus, let's flip all C2 to bad beforehand.
+		self.set_bad();
+
+		if cdio.read_cd_c2_subchannel(&mut self.buf, lsn)? { self.synced = self.synced.saturating_add(1); }
+		else { self.unsynced = self.unsynced.saturating_add(1); }
+
+		let good = self.all_good();
+		if strict && ! good { self.set_bad(); }
+		Ok(good)
+	}
+
+	/// # Read Data.
+	///
+	/// Read the sector without C2 error pointers, marking every sample good.
+	///
+	/// Returns `true`.
+	fn read_data(&mut self, cdio: &LibcdioInstance, lsn: i32)
+	-> Result<bool, RipRipError> {
+		for v in self.c2_slice_mut() { *v = 0; }
+		cdio.read_cd_data(self.data_slice_mut(), lsn)?;
+		Ok(true)
+	}
+
 	/// # Read Subchannel.
 	///
 	/// Read the sector and verify the subchannel's timecode matches the sector
 	/// we're requesting.
 	///
 	/// In the case of a desync, the data will be added to the state as "bad".
+	///
+	/// Returns `false` if there was no timecode to verify against.
 	fn read_subchannel(&mut self, cdio: &LibcdioInstance, lsn: i32)
-	-> Result<(), RipRipError> {
+	-> Result<bool, RipRipError> {
 		cdio.read_subchannel(
-			&mut self.0[..usize::from(CD_DATA_SUBCHANNEL_SIZE)],
+			&mut self.buf[..usize::from(CD_DATA_SUBCHANNEL_SIZE)],
 			lsn,
 		)
 	}
@@ -132,10 +242,48 @@ impl RipBuffer {
 	/// sector.
 	pub(crate) const fn samples(&self) -> RipBufferIter {
 		RipBufferIter {
-			set: &self.0,
+			set: &self.buf,
 			pos: 0,
 		}
 	}
+
+	/// # C2 Hash.
+	///
+	/// Return a CRC32 hash of the C2 error bits of the last-read sector, or
+	/// `None` if there weren't any errors. This makes it cheap to tell
+	/// whether subsequent reads failed in exactly the same way.
+	pub(crate) fn c2_hash(&self) -> Option<u32> {
+		if self.all_good() { None }
+		else { Some(crc32fast::hash(self.c2_slice())) }
+	}
+
+	/// # C2 Unsupported?
+	///
+	/// Returns `true` if C2 was disabled mid-run because the drive doesn't
+	/// support it.
+	pub(crate) const fn c2_unsupported(&self) -> bool { self.no_c2 }
+
+	/// # Take C2 Fallback.
+	///
+	/// Returns `true` the first time this is called after C2 was disabled
+	/// because the drive doesn't support it, so the user can be warned.
+	pub(crate) const fn take_c2_fallback(&mut self) -> bool {
+		if self.no_c2 && ! self.no_c2_warned {
+			self.no_c2_warned = true;
+			true
+		}
+		else { false }
+	}
+
+	/// # Unverified Sync Reads.
+	///
+	/// Return the number of subchannel-synced reads that lacked a timecode —
+	/// and so couldn't be verified — along with the total number of synced
+	/// reads, or `None` if everything checked out.
+	pub(crate) const fn sync_unverified(&self) -> Option<(u32, u32)> {
+		if self.unsynced == 0 { None }
+		else { Some((self.unsynced, self.synced.saturating_add(self.unsynced))) }
+	}
 }
 
 /// # Internal.
@@ -144,23 +292,32 @@ impl RipBuffer {
 	///
 	/// Returns `true` if all C2 bits are happy and error-free.
 	fn all_good(&self) -> bool {
-		self.0.iter().skip(usize::from(CD_DATA_SIZE)).all(|v| 0.eq(v))
+		self.c2_slice().iter().all(|v| 0.eq(v))
+	}
+
+	/// # C2 Slice.
+	///
+	/// Return the portion of the buffer containing the C2 error bits.
+	pub(super) fn c2_slice(&self) -> &[u8] {
+		&self.buf[usize::from(CD_DATA_SIZE)..usize::from(CD_DATA_C2_SIZE)]
 	}
 
 	/// # C2 Slice Mut.
 	///
 	/// Return the portion of the buffer containing the C2 error bits.
-	fn c2_slice_mut(&mut self) -> &mut [u8] { &mut self.0[usize::from(CD_DATA_SIZE)..] }
+	fn c2_slice_mut(&mut self) -> &mut [u8] {
+		&mut self.buf[usize::from(CD_DATA_SIZE)..usize::from(CD_DATA_C2_SIZE)]
+	}
 
 	/// # Data Slice.
 	///
 	/// Return the portion of the buffer containing the audio data.
-	fn data_slice(&self) -> &[u8] { &self.0[..usize::from(CD_DATA_SIZE)] }
+	fn data_slice(&self) -> &[u8] { &self.buf[..usize::from(CD_DATA_SIZE)] }
 
 	/// # Data Slice Mut.
 	///
 	/// Return the portion of the buffer containing the audio data.
-	fn data_slice_mut(&mut self) -> &mut [u8] { &mut self.0[..usize::from(CD_DATA_SIZE)] }
+	fn data_slice_mut(&mut self) -> &mut [u8] { &mut self.buf[..usize::from(CD_DATA_SIZE)] }
 
 	#[inline]
 	/// # Mark All C2 Bad.
@@ -178,7 +335,7 @@ impl RipBuffer {
 /// this will always produce exactly `588` results.
 pub(crate) struct RipBufferIter<'a> {
 	/// # Samples.
-	set: &'a [u8; CD_DATA_C2_SIZE as usize],
+	set: &'a [u8; CD_DATA_C2_SUBCHANNEL_SIZE as usize],
 
 	/// # Current Index.
 	pos: usize,
@@ -233,13 +390,35 @@ mod test {
 	use super::*;
 	use crate::NULL_SAMPLE;
 
+	#[test]
+	fn t_sync_unverified() {
+		let mut buf = RipBuffer::new();
+		assert_eq!(buf.sync_unverified(), None);
+
+		buf.synced = 5;
+		assert_eq!(buf.sync_unverified(), None);
+
+		buf.unsynced = 3;
+		assert_eq!(buf.sync_unverified(), Some((3, 8)));
+	}
+
+	#[test]
+	fn t_take_c2_fallback() {
+		let mut buf = RipBuffer::new();
+		assert!(! buf.take_c2_fallback());
+
+		buf.no_c2 = true;
+		assert!(buf.take_c2_fallback());
+		assert!(! buf.take_c2_fallback());
+	}
+
 	#[test]
 	fn t_buf_iters() {
 		let mut buf = RipBuffer::new();
-		buf.0[4..8].copy_from_slice(&[1, 1, 1, 1]);
-		buf.0[usize::from(CD_DATA_SIZE)] = 0b0000_1111;
-		buf.0[usize::from(CD_DATA_SIZE) + 1] = 0b1111_1111;
-		buf.0[usize::from(CD_DATA_SIZE) + 2] = 0b1111_0000;
+		buf.buf[4..8].copy_from_slice(&[1, 1, 1, 1]);
+		buf.buf[usize::from(CD_DATA_SIZE)] = 0b0000_1111;
+		buf.buf[usize::from(CD_DATA_SIZE) + 1] = 0b1111_1111;
+		buf.buf[usize::from(CD_DATA_SIZE) + 2] = 0b1111_0000;
 
 		// Test the goodness.
 		assert!(! buf.all_good());
@@ -259,12 +438,19 @@ mod test {
 		assert!(iter.next().is_none());
 
 		// Make sure goodness/badness works.
-		for v in &mut buf.0 { *v = 0; }
+		for v in &mut buf.buf { *v = 0; }
 		assert!(buf.all_good());
 		assert!(buf.samples().all(|(_, err)| ! err), "Missing goodness!");
 
 		buf.set_bad();
 		assert!(! buf.all_good());
 		assert!(buf.samples().all(|(_, err)| err), "Missing error!");
+
+		// The subchannel tail shouldn't be mistaken for C2.
+		for v in &mut buf.buf { *v = 0; }
+		buf.buf[usize::from(CD_DATA_C2_SIZE)..].fill(0b1111_1111);
+		assert_eq!(buf.c2_slice().len(), 294);
+		assert!(buf.all_good());
+		assert!(buf.samples().all(|(_, err)| ! err), "Subchannel leaked into C2!");
 	}
 }
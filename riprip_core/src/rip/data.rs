@@ -6,33 +6,55 @@ use cdtoc::{
 	Toc,
 	TocKind,
 	Track,
+	TrackPosition,
 };
 use crate::{
+	backup_path,
 	BYTES_PER_SAMPLE,
+	cache_prefix,
 	CacheWriter,
+	ExistingMode,
+	HtoaMode,
+	lossy_path,
+	merged_path,
+	NULL_SAMPLE,
 	ReadOffset,
 	RipOptions,
 	RipRipError,
 	RipSample,
+	Sample,
 	SAMPLE_OVERREAD,
 	SAMPLES_PER_SECTOR,
+	sectors_path,
+	sectors_state_path,
+	Sha256,
 	state_path,
 	track_path,
+	write_error,
 };
 use std::{
+	borrow::Cow,
 	fs::File,
 	io::{
 		BufReader,
 		BufWriter,
 	},
+	num::{
+		NonZeroU8,
+		NonZeroU32,
+	},
 	ops::Range,
-	path::PathBuf,
+	path::{
+		Path,
+		PathBuf,
+	},
 };
 use super::{
 	OffsetRipIter,
 	sample::RipSector,
 	TrackQuality,
 };
+use utc2k::FmtUtc2k;
 
 
 
@@ -46,7 +68,41 @@ const BUFFER_SIZE: usize = 16 * 1024;
 /// This is used to identify `RipState` files, as well as the format "version"
 /// used at the time of their construction, making sure we don't waste time
 /// trying to shove bytes into the wrong format.
-const MAGIC: [u8; 8] = *b"RRip0003";
+const MAGIC: [u8; 8] = *b"RRip0006";
+
+/// # Magic Bytes (V5).
+///
+/// States saved before the export file name suffix was recorded. These are
+/// still readable; their export bytes are just one short.
+const MAGIC_V5: [u8; 8] = *b"RRip0005";
+
+/// # Magic Bytes (V4).
+///
+/// States saved before the export quality was recorded. These are still
+/// readable; they just lack the export bytes.
+const MAGIC_V4: [u8; 8] = *b"RRip0004";
+
+/// # Magic Bytes (Legacy).
+///
+/// States saved before the header summary was introduced. These are still
+/// readable; they just lack a `StateSummary`.
+const MAGIC_V3: [u8; 8] = *b"RRip0003";
+
+/// # Summary Size.
+///
+/// The number of header bytes reserved for the `StateSummary`.
+const SUMMARY_SIZE: usize = 25;
+
+/// # Export Size.
+///
+/// The number of header bytes reserved for the quality of the last export.
+const EXPORT_SIZE: usize = 18;
+
+/// # Export Size (V5).
+///
+/// The number of header bytes reserved for the export quality in `MAGIC_V5`
+/// states, which lacked the suffix.
+const EXPORT_SIZE_V5: usize = 17;
 
 /// # Wave Header.
 ///
@@ -68,6 +124,25 @@ const WAVE_HEADER: [u8; 44] = [
 	0, 0, 0, 0,        // Size of the data portion (all that comes next).
 ];
 
+/// # Wave Header (Any Depth).
+///
+/// Return a copy of `WAVE_HEADER` with the format fields adjusted for `bits`
+/// per sample, which should be `16`, `24`, or `32`.
+const fn wave_header(bits: u8) -> [u8; 44] {
+	let align = (bits / 8) * 2;
+	let rate = 44_100 * align as u32;
+	let rate = rate.to_le_bytes();
+
+	let mut out = WAVE_HEADER;
+	out[28] = rate[0];
+	out[29] = rate[1];
+	out[30] = rate[2];
+	out[31] = rate[3];
+	out[32] = align;
+	out[34] = bits;
+	out
+}
+
 
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -96,6 +171,12 @@ pub(crate) struct RipState {
 	/// # Track Number.
 	track: Track,
 
+	/// # Custom Sector Range.
+	///
+	/// Manual sector range rips work just like track rips, except the range
+	/// (and file names) are derived from this instead of `track`.
+	custom: Option<Range<i32>>,
+
 	/// # Disc Range.
 	disc_rng: Range<i32>,
 
@@ -105,8 +186,35 @@ pub(crate) struct RipState {
 	/// # Sample Data.
 	data: Vec<RipSample>,
 
+	/// # Sector Todo.
+	///
+	/// This holds a flag for each of the (offset-adjusted) sectors yielded by
+	/// `offset_rip_iter`, `true` if any of its samples still need work. It
+	/// allows the rip to jump straight to the sectors that matter instead of
+	/// rescanning the likely ones each and every pass.
+	todo: Vec<bool>,
+
 	/// # New Rip?
 	new: bool,
+
+	/// # Recovered From Backup?
+	recovered: bool,
+
+	/// # Status Summary.
+	summary: Option<StateSummary>,
+
+	/// # Last Export.
+	///
+	/// The quality counts — as in `TrackQuality::as_array` — of the track
+	/// as of its last export, and the file name suffix used for it (zero for
+	/// none), carried over from the previous state (resumed or not).
+	exported: Option<([u32; 4], u8)>,
+
+	/// # Legacy State?
+	///
+	/// This is `true` if the previous state predates the export record, in
+	/// which case any file at the destination is presumed to be ours.
+	legacy: bool,
 }
 
 impl RipState {
@@ -126,15 +234,64 @@ impl RipState {
 	/// user opts not to start over.
 	pub(crate) fn new(toc: &Toc, track: Track, opts: &RipOptions)
 	-> Result<Self, RipRipError> {
-		let disc_rng = accessible_range(toc, opts.offset())
-			.ok_or(RipRipError::RipOverflow)?;
+		let disc_rng = accessible_range(toc, opts.offset())?;
 		let mut out = Self {
 			toc: toc.clone(),
 			track,
+			custom: None,
 			disc_rng,
 			rip_rng: 0..0,
 			data: Vec::new(),
+			todo: Vec::new(),
 			new: true,
+			recovered: false,
+			summary: None,
+			exported: None,
+			legacy: false,
+		};
+		out.init(track, opts)?;
+		Ok(out)
+	}
+
+	/// # New (Sector Range).
+	///
+	/// Same as `RipState::new`, but for an arbitrary (normalized) sector
+	/// range rather than a track.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the range is empty or out of bounds, in
+	/// addition to all the usual `RipState::new` errors.
+	pub(crate) fn new_sectors(toc: &Toc, rng: Range<i32>, opts: &RipOptions)
+	-> Result<Self, RipRipError> {
+		// The range must fit within the disc.
+		let leadout = i32::try_from(toc.audio_leadout_normalized())
+			.map_err(|_| RipRipError::RipOverflow)?;
+		if rng.is_empty() || rng.start < 0 || leadout < rng.end {
+			return Err(RipRipError::SectorRange);
+		}
+
+		// We still need a track for reference; might as well use the one the
+		// range starts in.
+		let track = u32::try_from(rng.start).ok()
+			.and_then(|start| toc.audio_tracks().find(|t| t.sector_range_normalized().contains(&start)))
+			.or_else(|| toc.audio_tracks().next())
+			.ok_or(RipRipError::SectorRange)?;
+
+		let disc_rng = accessible_range(toc, opts.offset())?;
+		let mut out = Self {
+			toc: toc.clone(),
+			track,
+			custom: Some(rng),
+			disc_rng,
+			rip_rng: 0..0,
+			data: Vec::new(),
+			todo: Vec::new(),
+			new: true,
+			recovered: false,
+			summary: None,
+			exported: None,
+			legacy: false,
 		};
 		out.init(track, opts)?;
 		Ok(out)
@@ -167,12 +324,13 @@ impl RipState {
 	/// integer types, the cache is invalid, or the cache is corrupt and the
 	/// user opts not to start over.
 	fn init(&mut self, track: Track, opts: &RipOptions) -> Result<(), RipRipError> {
-		use std::io::Read;
-
 		// Assume this is new until we learn differently.
 		self.new = true;
 		self.track = track;
-		self.rip_rng = track_rng_to_rip_range(track).ok_or(RipRipError::RipOverflow)?;
+		self.summary = None;
+		self.rip_rng = self.custom.clone()
+			.map_or_else(|| track_rng_to_rip_range(track), sector_rng_to_rip_range)
+			.ok_or(RipRipError::RipOverflow)?;
 
 		// Let's test the rip range as bytes in various integer sizes to make
 		// sure we can freely cast last on.
@@ -187,39 +345,32 @@ impl RipState {
 		let len = self.rip_rng.len();
 		self.data.try_reserve_exact(len).map_err(|_| RipRipError::RipOverflow)?;
 
-		// Load it from a previous session?
-		if opts.resume() {
-			let idx = track.number();
-			let src = state_path(&self.toc, track)?;
-			if let Ok(file) = File::open(src) {
-				let mut file = BufReader::with_capacity(BUFFER_SIZE, file);
-
-				// Magic header.
-				let mut buf = [0_u8; MAGIC.len()];
-				if file.read_exact(&mut buf).is_err() || buf != MAGIC {
-					return Err(RipRipError::StateCorrupt(idx));
-				}
-
-				// We'll check this after the data is read.
-				let mut buf = [0_u8; 4];
-				file.read_exact(&mut buf)
-					.map_err(|_| RipRipError::StateCorrupt(idx))?;
-				let hash = u32::from_le_bytes(buf);
-
-				// Load the data.
-				let mut sector = RipSector::new();
-				for _ in (0..len).step_by(usize::from(SAMPLES_PER_SECTOR)) {
-					let iter = sector.deserialize_from(&mut file)
-						.ok_or(RipRipError::StateCorrupt(idx))?;
-					self.data.extend(iter);
-				}
-
-				// Check the hash now to verify the toc, track, data are
-				// (reasonably) what we expected.
-				if hash != self.quick_hash() {
-					return Err(RipRipError::StateCorrupt(idx));
-				}
-
+		// Hold onto the last export's quality, even if we aren't resuming.
+		let src = self.state_path()?;
+		self.exported = load_exported(&src);
+		self.legacy = self.exported.is_none() && legacy_state(&src);
+
+		// Load it from a previous session? If the state is corrupt, try the
+		// backup from the save before.
+		if opts.resume_track(track.number()) {
+			let loaded = match self.load_state(&src, len) {
+				Err(RipRipError::StateCorrupt(idx)) => {
+					self.data.truncate(0);
+					let bak = backup_path(&src);
+					if ! matches!(self.load_state(&bak, len), Ok(true)) {
+						return Err(RipRipError::StateCorrupt(idx));
+					}
+
+					// Get rid of the bad copy so the next save doesn't
+					// rotate it over the good one.
+					let _res = std::fs::remove_file(&src);
+					self.recovered = true;
+					true
+				},
+				res => res?,
+			};
+
+			if loaded {
 				// This isn't new, obviously.
 				self.new = false;
 
@@ -227,7 +378,7 @@ impl RipState {
 				if opts.reset() && self.reset() { self.save_state()?; }
 
 				// We're good!
-				return Ok(());
+				return self.init_todo(opts);
 			}
 		}
 
@@ -238,20 +389,242 @@ impl RipState {
 		}
 
 		// Done!
+		self.init_todo(opts)
+	}
+
+	/// # Load State.
+	///
+	/// Read previously-saved sample data from `src` into the (empty) buffer,
+	/// returning `true` if successful, or `false` if there was no file to
+	/// read.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the file exists but is corrupt.
+	fn load_state(&mut self, src: &Path, len: usize) -> Result<bool, RipRipError> {
+		use std::io::Read;
+
+		let Ok(file) = File::open(src) else { return Ok(false); };
+		let mut file = BufReader::with_capacity(BUFFER_SIZE, file);
+		let idx = self.track.number();
+
+		// Magic header.
+		let mut magic = [0_u8; MAGIC.len()];
+		if file.read_exact(&mut magic).is_err() || ! [MAGIC, MAGIC_V5, MAGIC_V4, MAGIC_V3].contains(&magic) {
+			return Err(RipRipError::StateCorrupt(idx));
+		}
+
+		// We'll check this after the data is read.
+		let mut buf = [0_u8; 4];
+		file.read_exact(&mut buf)
+			.map_err(|_| RipRipError::StateCorrupt(idx))?;
+		let hash = u32::from_le_bytes(buf);
+
+		// The summary, if any, comes next.
+		let mut summary = [0_u8; SUMMARY_SIZE];
+		if magic != MAGIC_V3 {
+			file.read_exact(&mut summary)
+				.map_err(|_| RipRipError::StateCorrupt(idx))?;
+		}
+
+		// As does the export quality, but that was already handled by
+		// `RipState::init`.
+		if magic == MAGIC {
+			file.read_exact(&mut [0_u8; EXPORT_SIZE])
+				.map_err(|_| RipRipError::StateCorrupt(idx))?;
+		}
+		else if magic == MAGIC_V5 {
+			file.read_exact(&mut [0_u8; EXPORT_SIZE_V5])
+				.map_err(|_| RipRipError::StateCorrupt(idx))?;
+		}
+
+		// Load the data.
+		let mut sector = RipSector::new();
+		for _ in (0..len).step_by(usize::from(SAMPLES_PER_SECTOR)) {
+			let iter = sector.deserialize_from(&mut file)
+				.ok_or(RipRipError::StateCorrupt(idx))?;
+			self.data.extend(iter);
+		}
+
+		// Check the hash now to verify the toc, track, data are
+		// (reasonably) what we expected.
+		if hash == self.quick_hash() {
+			self.summary = StateSummary::from_bytes(hash, &summary);
+			Ok(true)
+		}
+		else { Err(RipRipError::StateCorrupt(idx)) }
+	}
+
+	/// # Merge State.
+	///
+	/// Fold the samples from another rip's state data for the same track —
+	/// e.g. one made with a different drive — into this one, saving and
+	/// returning `true` if anything changed.
+	///
+	/// `src` should be the other rip's scratch directory; if it has no state
+	/// for this track, nothing happens.
+	///
+	/// Saved samples are already offset-corrected, so the two line up so long
+	/// as each rip used the correct offset for its drive.
+	///
+	/// Merged states are recorded in a `.merged` ledger next to ours, and
+	/// skipped if they come around again, as folding in the same reads twice
+	/// would inflate their counts. (The ledger is cleared whenever a new state
+	/// replaces ours.)
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the other state is corrupt or belongs to
+	/// a different disc or track, or bubble up any I/O errors encountered
+	/// while saving.
+	pub(crate) fn merge_state(&mut self, src: &Path, opts: &RipOptions)
+	-> Result<bool, RipRipError> {
+		use std::io::Write;
+
+		let idx = self.track.number();
+		let dst = self.state_path()?;
+		let src = dst.file_name()
+			.map(|name| src.join(name))
+			.ok_or(RipRipError::StateMerge(idx))?;
+
+		// Skip it if there's nothing there or it has already been merged.
+		let Ok(key) = std::fs::canonicalize(&src) else { return Ok(false); };
+		let key = key.to_string_lossy().into_owned();
+		let ledger = merged_path(&dst);
+		if std::fs::read_to_string(&ledger).is_ok_and(|s| s.lines().any(|l| l == key)) {
+			return Ok(false);
+		}
+
+		// Load the other state using the same framework as ours.
+		let len = self.rip_rng.len();
+		let mut other = Self {
+			toc: self.toc.clone(),
+			track: self.track,
+			custom: self.custom.clone(),
+			disc_rng: self.disc_rng.clone(),
+			rip_rng: self.rip_rng.clone(),
+			data: Vec::new(),
+			todo: Vec::new(),
+			new: false,
+			recovered: false,
+			summary: None,
+			exported: None,
+			legacy: false,
+		};
+		other.data.try_reserve_exact(len).map_err(|_| RipRipError::RipOverflow)?;
+		match other.load_state(&src, len) {
+			Ok(true) => {},
+			Ok(false) => return Ok(false),
+			Err(_) => return Err(RipRipError::StateMerge(idx)),
+		}
+
+		// Fold it in!
+		let before = self.quick_hash();
+		for (a, b) in self.data.iter_mut().zip(&other.data) { a.merge(b); }
+		if before == self.quick_hash() { return Ok(false); }
+		self.new = false;
+		self.init_todo(opts)?;
+		self.save_state()?;
+
+		// Make a note of it.
+		let err = || RipRipError::StateSave(idx);
+		File::options().create(true).append(true).open(&ledger)
+			.and_then(|mut f| writeln!(f, "{key}"))
+			.map_err(|e| write_error(&e, &ledger, err))?;
+
+		Ok(true)
+	}
+
+	/// # Initialize Sector Todo.
+	///
+	/// (Re)build the list of (offset-adjusted) sectors that contain samples
+	/// in need of work, i.e. anything not yet likely or confirmed.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if there's a bug in the programming, but that
+	/// shouldn't happen. ;)
+	pub(super) fn init_todo(&mut self, opts: &RipOptions) -> Result<(), RipRipError> {
+		let (_, idx_rng) = self.offset_rip_ranges(opts.offset())?;
+		self.todo.truncate(0);
+		self.todo.extend(
+			self.data[idx_rng.clone()].chunks_exact(usize::from(SAMPLES_PER_SECTOR))
+				.map(|s| sector_todo(s, opts))
+		);
+		if opts.no_overread() { self.skip_overread(idx_rng.start); }
 		Ok(())
 	}
+
+	/// # Skip Overread.
+	///
+	/// Clear the todo flags for any sectors that fall entirely within the
+	/// `SAMPLE_OVERREAD` padding on either side of the range, so they never
+	/// get read. This is used by `RipOptions::no_overread`.
+	///
+	/// Sectors straddling the boundary still contain samples we need, so are
+	/// left alone.
+	fn skip_overread(&mut self, idx_start: usize) {
+		let pad = usize::from(SAMPLE_OVERREAD);
+		let len = self.data.len();
+		let mut start = idx_start;
+		for todo in &mut self.todo {
+			let end = start + usize::from(SAMPLES_PER_SECTOR);
+			if end <= pad || len - pad <= start { *todo = false; }
+			start = end;
+		}
+	}
 }
 
 impl RipState {
+	/// # Mark Everything Todo.
+	///
+	/// Flag every sector as needing work, regardless of its samples, so the
+	/// next iteration covers the whole range. This is used for paranoid
+	/// re-reads.
+	///
+	/// The padding is still skipped if `RipOptions::no_overread` is set.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if there's a bug in the programming, but that
+	/// shouldn't happen. ;)
+	pub(super) fn mark_todo(&mut self, opts: &RipOptions) -> Result<(), RipRipError> {
+		self.todo.fill(true);
+		if opts.no_overread() {
+			let (_, idx_rng) = self.offset_rip_ranges(opts.offset())?;
+			self.skip_overread(idx_rng.start);
+		}
+		Ok(())
+	}
+
+	/// # Decay Counts.
+	///
+	/// Age all maybe samples by one pass, halving the counts of any that
+	/// haven't been seen in `max` passes so their sectors can be reread.
+	///
+	/// If anything was halved, the sector todos will be rebuilt to match.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if there's a bug in the programming, but that
+	/// shouldn't happen. ;)
+	pub(crate) fn decay(&mut self, max: u8, opts: &RipOptions)
+	-> Result<(), RipRipError> {
+		let mut any = false;
+		for v in &mut self.data {
+			if v.decay(max) { any = true; }
+		}
+		if any { self.init_todo(opts) }
+		else { Ok(()) }
+	}
+
 	/// # Reset Counts.
 	///
 	/// Drop all maybe counts to one so their sectors can be reread. Returns
 	/// `true` if anything winds up getting changed.
 	fn reset(&mut self) -> bool {
 		let before = self.quick_hash();
-		for v in &mut self.data {
-			if let RipSample::Maybe(v) = v { v.reset(); }
-		}
+		for v in &mut self.data { v.reset(); }
 		before != self.quick_hash()
 	}
 
@@ -270,31 +643,84 @@ impl RipState {
 		use std::io::Write;
 
 		// The destination path.
-		let dst = state_path(&self.toc, self.track)
+		let dst = self.state_path()
 			.map_err(|_| RipRipError::StateSave(self.track.number()))?;
 
+		// A new state has nothing merged into it yet.
+		if self.new { let _res = std::fs::remove_file(merged_path(&dst)); }
+
 		// Serialize -> compress -> write to tmpfile.
-		let mut writer = CacheWriter::new(&dst)?;
+		let mut writer = CacheWriter::new(&dst)?.with_backup();
 		{
 			let mut buf = BufWriter::with_capacity(BUFFER_SIZE, writer.writer());
 			let idx = self.track.number();
 
 			// The first twelve bytes are reserved for some magic header bits
-			// and a CRC32 hash of the toc, track, and data.
+			// and a CRC32 hash of the toc, track, and data, followed by the
+			// summary, if it is still current, and the last export quality.
+			let hash = self.quick_hash();
+			let summary = StateSummary::to_bytes(
+				self.summary.as_ref().filter(|s| s.hash == hash)
+			);
+			let err = || RipRipError::StateSave(idx);
 			buf.write_all(MAGIC.as_slice())
-				.and_then(|()| buf.write_all(self.quick_hash().to_le_bytes().as_slice()))
-				.map_err(|_| RipRipError::StateSave(idx))?;
+				.and_then(|()| buf.write_all(hash.to_le_bytes().as_slice()))
+				.and_then(|()| buf.write_all(summary.as_slice()))
+				.and_then(|()| buf.write_all(exported_to_bytes(self.exported).as_slice()))
+				.map_err(|e| write_error(&e, &dst, err))?;
 
 			// Everything else is the sample data…
 			let mut sector = RipSector::new();
 			for v in self.data.chunks_exact(usize::from(SAMPLES_PER_SECTOR)) {
-				sector.serialize_into(v, &mut buf).ok_or(RipRipError::StateSave(idx))?;
+				sector.serialize_into(v, &mut buf).map_err(|e| write_error(&e, &dst, err))?;
 			}
+			buf.flush().map_err(|e| write_error(&e, &dst, err))?;
 		}
 		// Save the tmpfile to dst.
 		writer.finish()
 	}
 
+	/// # Save State (Softly).
+	///
+	/// Same as `RipState::save_state`, but for the intermediate saves whose
+	/// failures aren't worth dying over, so only `RipRipError::DiskFull` is
+	/// bubbled up. (A full disk isn't going to fix itself between tracks.)
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the disk is out of space.
+	pub(crate) fn save_state_soft(&self) -> Result<(), RipRipError> {
+		match self.save_state() {
+			Err(e @ RipRipError::DiskFull(..)) => Err(e),
+			_ => Ok(()),
+		}
+	}
+
+	/// # Set Summary.
+	///
+	/// Update the status summary to reflect the current data — saved with the
+	/// next `RipState::save_state` — returning `true` if it changed.
+	pub(super) fn set_summary(
+		&mut self,
+		rereads: (u8, u8),
+		quality: TrackQuality,
+		ar: Option<(u8, u8)>,
+		ctdb: Option<u16>,
+	) -> bool {
+		let new = StateSummary {
+			hash: self.quick_hash(),
+			rereads,
+			quality: quality.as_array(),
+			ar,
+			ctdb,
+		};
+		if self.summary == Some(new) { false }
+		else {
+			self.summary = Some(new);
+			true
+		}
+	}
+
 	/// # Save Track.
 	///
 	/// Write the best-available copy of the track to WAV format, and return
@@ -304,40 +730,242 @@ impl RipState {
 	///
 	/// This will bubble up any I/O-related errors encountered, but should be
 	/// fine.
-	pub(crate) fn save_track(&self) -> Result<PathBuf, RipRipError> {
-		use std::io::Write;
+	///
+	/// If `RipOptions::bwf` and/or `RipOptions::wav_info` are set, `bext`
+	/// and/or `LIST`/`INFO` chunks are included, noting the AccurateRip and
+	/// CTDB confidences from `chk`, if any.
+	///
+	/// If `RipOptions::interpolate` is set, any bad or unread samples are
+	/// papered over in the output (and the `LIST`/`INFO` chunk is forced so
+	/// the fact can be noted).
+	///
+	/// The `quality` of each export is recorded in the state header, with
+	/// the state re-saved whenever that record changes. If
+	/// `RipOptions::append_tracks` is set and an earlier export of better
+	/// quality is still sitting there, it is left alone instead.
+	///
+	/// Files at the destination that _weren't_ exported by this state — per
+	/// that same record — are handled according to `RipOptions::existing`.
+	pub(super) fn save_track(
+		&mut self,
+		opts: &RipOptions,
+		quality: TrackQuality,
+		chk: (Option<(u8, u8)>, Option<u16>),
+	) -> Result<PathBuf, RipRipError> {
+		let dst = self.custom.as_ref().map_or_else(
+			|| track_path(&self.toc, self.track),
+			|rng| sectors_path(&self.toc, rng),
+		)?;
+
+		// The CDDB prefix should keep discs from stepping on each other's
+		// files, but it's worth making sure before deciding what's ours.
+		if ! dst.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(cache_prefix(&self.toc))) {
+			return Err(RipRipError::Bug("Export path is missing the CDDB prefix."));
+		}
 
-		let dst = track_path(&self.toc, self.track)?;
-		let data = self.track_slice();
+		// Figure out where this is going.
+		let (dst, suffix) = match self.exported {
+			// We've been here before.
+			Some((_, suffix)) => (suffixed_path(&dst, suffix), suffix),
+			// Anything already there must have come from somewhere else,
+			// unless the state is too old to say.
+			None if dst.is_file() && ! self.legacy => match opts.existing() {
+				ExistingMode::Overwrite => (dst, 0),
+				ExistingMode::Skip => return Ok(dst),
+				ExistingMode::Suffix => (1..=u8::MAX)
+					.map(|n| (suffixed_path(&dst, n), n))
+					.find(|(p, _)| ! p.exists())
+					.ok_or_else(|| RipRipError::Write(dst.to_string_lossy().into_owned()))?,
+			},
+			None => (dst, 0),
+		};
 
-		// The data length is easy: four bytes per sample.
-		let data_len = u32::try_from(data.len())
-			.ok()
-			.and_then(|n| n.checked_mul(u32::from(BYTES_PER_SAMPLE)))
-			.ok_or_else(|| RipRipError::Write(dst.to_string_lossy().into_owned()))?;
+		// Don't replace a better export with a worse one.
+		if
+			opts.append_tracks() &&
+			dst.is_file() &&
+			self.exported.is_some_and(|(old, _)| quality.is_worse_than(&TrackQuality::from_array(old)))
+		{
+			return Ok(dst);
+		}
 
-		// The file length excludes "RIFF" and the four bytes specifying the
-		// file length.
-		let file_len = 44 - 8 + data_len;
+		// Trim the silence from the HTOA, maybe.
+		let mut rng = self.inner_index_track_rng();
+		if opts.trim_htoa() {
+			if let Some((start, end)) = self.htoa_silence() {
+				rng = rng.start + start..rng.end - end;
+			}
+		}
 
-		// Write the data!
-		let mut writer = CacheWriter::new(&dst)?;
+		// Or tack it onto the front of the first track.
+		let (data, rng) = self.merged_htoa(opts)?.map_or_else(
+			|| (Cow::Borrowed(self.data.as_slice()), rng.clone()),
+			|mut data| {
+				data.extend_from_slice(&self.data[rng.clone()]);
+				let len = data.len();
+				(Cow::Owned(data), 0..len)
+			},
+		);
+
+		let dst = save_data(dst, &self.toc, self.track, &data, rng, opts, chk)?;
+		self.set_exported(quality, suffix)?;
+		Ok(dst)
+	}
+
+	/// # Merged HTOA.
+	///
+	/// If `HtoaMode::Merge` is in effect and this is the first track of a
+	/// disc with an HTOA, load the HTOA's state and return its samples so
+	/// they can be prepended to the export.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the HTOA state exists but cannot be
+	/// loaded.
+	fn merged_htoa(&self, opts: &RipOptions) -> Result<Option<Vec<RipSample>>, RipRipError> {
+		if
+			self.custom.is_some() ||
+			! self.track.position().is_first() ||
+			! matches!(opts.htoa_mode(), HtoaMode::Merge)
 		{
-			let mut buf = BufWriter::with_capacity(BUFFER_SIZE, writer.writer());
+			return Ok(None);
+		}
+		let Some(htoa) = self.toc.htoa() else { return Ok(None); };
+
+		// Make sure we don't accidentally alter anything.
+		let opts = opts.with_resume(true).with_reset(false);
+		let state = Self::new(&self.toc, htoa, &opts)?;
+		Ok(Some(state.track_slice().to_vec()))
+	}
 
-			// The header comes first; we just need to fill out the
-			// size-related blocks before pushing it.
-			let mut header = WAVE_HEADER;
-			header[4..8].copy_from_slice(file_len.to_le_bytes().as_slice());
-			header[40..].copy_from_slice(data_len.to_le_bytes().as_slice());
-			buf.write_all(header.as_slice())
-				.map_err(|_| RipRipError::Write(dst.to_string_lossy().into_owned()))?;
+	/// # Set Export Quality.
+	///
+	/// Record the quality and file name suffix of the track's latest export,
+	/// saving the state if that changes anything.
+	///
+	/// ## Errors
+	///
+	/// As with `RipState::save_state_soft`, only a full disk is bubbled up.
+	fn set_exported(&mut self, quality: TrackQuality, suffix: u8) -> Result<(), RipRipError> {
+		let exported = Some((quality.as_array(), suffix));
+		if self.exported == exported { return Ok(()); }
+		self.exported = exported;
+		self.save_state_soft()
+	}
 
-			// Now it's just straight PCM funtimes!
-			for v in data {
-				buf.write_all(v.as_slice())
-					.map_err(|_| RipRipError::Write(dst.to_string_lossy().into_owned()))?;
+	/// # Save Disc Track.
+	///
+	/// Same as `RipState::save_track`, but for an individual track sliced
+	/// out of a whole-disc rip. (HTOA trimming is not supported here, but
+	/// merging is.)
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the track isn't covered by the rip, or
+	/// bubble up any I/O-related errors encountered.
+	pub(crate) fn save_disc_track(
+		&self,
+		track: Track,
+		opts: &RipOptions,
+		chk: (Option<(u8, u8)>, Option<u16>),
+	) -> Result<PathBuf, RipRipError> {
+		let dst = track_path(&self.toc, track)?;
+		let pad = usize::from(SAMPLE_OVERREAD);
+
+		// A merged HTOA is the first track's pregap.
+		let htoa = self.toc.htoa().filter(|_|
+			track.position().is_first() && matches!(opts.htoa_mode(), HtoaMode::Merge)
+		);
+		let data = htoa.map_or_else(
+			|| self.disc_track_slice(track).map(Cow::Borrowed),
+			|htoa| self.disc_track_slice(htoa)
+				.zip(self.disc_track_slice(track))
+				.map(|(a, b)| {
+					let mut out = a[..a.len() - pad].to_vec();
+					out.extend_from_slice(&b[pad..]);
+					Cow::Owned(out)
+				}),
+		)
+			.ok_or_else(|| RipRipError::NoTrack(track.number()))?;
+		save_data(dst, &self.toc, track, &data, pad..data.len() - pad, opts, chk)
+	}
+
+	/// # Save Lossy Copy.
+	///
+	/// Encode the best-available copy of the track to Ogg Vorbis, and return
+	/// the path for reference.
+	///
+	/// The file name gets the same suffix, if any, as the track's last WAV
+	/// export.
+	///
+	/// As with `RipState::save_track`, a merged HTOA is included at the start
+	/// of the first track.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the encoding or writing fails.
+	pub(crate) fn save_lossy(&self, opts: &RipOptions) -> Result<PathBuf, RipRipError> {
+		use std::io::Write;
+		use vorbis_rs::VorbisEncoderBuilder;
+
+		/// # Channels.
+		const CHANNELS: NonZeroU8 = NonZeroU8::new(2).unwrap();
+
+		/// # Sample Rate.
+		const SAMPLE_RATE: NonZeroU32 = NonZeroU32::new(44_100).unwrap();
+
+		/// # Samples Per Block.
+		const BLOCK: usize = 4096;
+
+		let idx = self.track.number();
+		let dst = suffixed_path(
+			&lossy_path(&self.toc, self.track)?,
+			self.exported.map_or(0, |(_, suffix)| suffix),
+		);
+		let data = self.merged_htoa(opts)?.map_or_else(
+			|| Cow::Borrowed(self.track_slice()),
+			|mut data| {
+				data.extend_from_slice(self.track_slice());
+				Cow::Owned(data)
+			},
+		);
+
+		// Write the data!
+		let mut writer = CacheWriter::new(&dst)?;
+		{
+			let buf = BufWriter::with_capacity(BUFFER_SIZE, writer.writer());
+
+			// Use the quick hash for the stream serial so repeat encodings of
+			// the same data come out the same.
+			let serial = i32::from_le_bytes(self.quick_hash().to_le_bytes());
+			let mut encoder = VorbisEncoderBuilder::new_with_serial(
+				SAMPLE_RATE,
+				CHANNELS,
+				buf,
+				serial,
+			)
+				.build()
+				.map_err(|_| RipRipError::Lossy(idx))?;
+
+			// Vorbis wants planar floats rather than interleaved PCM.
+			let mut left = Vec::with_capacity(BLOCK);
+			let mut right = Vec::with_capacity(BLOCK);
+			for chunk in data.chunks(BLOCK) {
+				left.truncate(0);
+				right.truncate(0);
+				for v in chunk {
+					let [l1, l2, r1, r2] = v.as_array();
+					left.push(f32::from(i16::from_le_bytes([l1, l2])) / 32_768.0);
+					right.push(f32::from(i16::from_le_bytes([r1, r2])) / 32_768.0);
+				}
+				encoder.encode_audio_block([left.as_slice(), right.as_slice()])
+					.map_err(|_| RipRipError::Lossy(idx))?;
 			}
+
+			encoder.finish()
+				.map_err(|_| RipRipError::Lossy(idx))?
+				.flush()
+				.map_err(|e| write_error(&e, &dst, || RipRipError::Write(dst.to_string_lossy().into_owned())))?;
 		}
 		writer.finish()?;
 		Ok(dst)
@@ -359,8 +987,9 @@ impl RipState {
 impl RipState {
 	/// # Offset Rip Iterator.
 	///
-	/// Return an offset-aware iterator of the sector LSNs to read from, and
-	/// the mutable slices to write the responses back to.
+	/// Return an offset-aware iterator of the sector LSNs to read from, the
+	/// mutable slices to write the responses back to, and the corresponding
+	/// todo flags, skipping any sectors that don't need any more work.
 	///
 	/// ## Errors.
 	///
@@ -368,11 +997,30 @@ impl RipState {
 	/// shouldn't happen. ;)
 	pub(super) fn offset_rip_iter(&mut self, opts: &RipOptions)
 	-> Result<OffsetRipIter, RipRipError> {
+		let (lsn_rng, idx_rng) = self.offset_rip_ranges(opts.offset())?;
+		OffsetRipIter::new(
+			lsn_rng,
+			&mut self.data[idx_rng],
+			&mut self.todo,
+			opts.backwards(),
+		)
+	}
+
+	/// # Offset Rip Ranges.
+	///
+	/// Return the (offset-adjusted) range of sector LSNs to read from, along
+	/// with the corresponding index range of the data to write to.
+	///
+	/// ## Errors.
+	///
+	/// This will return an error if there's a bug in the programming, but that
+	/// shouldn't happen. ;)
+	fn offset_rip_ranges(&self, offset: ReadOffset)
+	-> Result<(Range<i32>, Range<usize>), RipRipError> {
 		// Let's start with the read parts.
 		let sector_range = self.sector_rip_range();
 		let mut lsn_start = sector_range.start;
 		let mut lsn_end = sector_range.end;
-		let offset = opts.offset();
 		let sectors_abs = i32::from(offset.sectors_abs());
 
 		// Negative offsets require the data be pushed forward to "start" at
@@ -400,10 +1048,21 @@ impl RipState {
 			return Err(RipRipError::Bug("Invalid OffsetRipIter ending index."));
 		}
 
-		OffsetRipIter::new(
-			lsn_start..lsn_end,
-			&mut self.data[idx_start..idx_end],
-			opts.backwards(),
+		Ok((lsn_start..lsn_end, idx_start..idx_end))
+	}
+
+	/// # State Path.
+	///
+	/// Return the path to the state file for the track or custom range.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if there are problems determining the cache
+	/// location.
+	fn state_path(&self) -> Result<PathBuf, RipRipError> {
+		self.custom.as_ref().map_or_else(
+			|| state_path(&self.toc, self.track),
+			|rng| sectors_state_path(&self.toc, rng),
 		)
 	}
 
@@ -420,6 +1079,18 @@ impl RipState {
 		self.rip_rng.end.wrapping_div(SAMPLES_PER_SECTOR as i32)
 	}
 
+	/// # HTOA Silence.
+	///
+	/// Return the number of leading and trailing silent samples in the HTOA —
+	/// excluding the offset padding — or `None` if this isn't a (regular)
+	/// HTOA rip.
+	pub(crate) fn htoa_silence(&self) -> Option<(usize, usize)> {
+		if self.track.is_htoa() && self.custom.is_none() {
+			Some(silence(self.track_slice()))
+		}
+		else { None }
+	}
+
 	/// # Table of Contents.
 	///
 	/// Return the Table of Contents.
@@ -436,7 +1107,22 @@ impl RipState {
 	/// range.
 	pub(super) fn track_quality(&self, opts: &RipOptions) -> TrackQuality {
 		let slice = self.track_slice();
-		TrackQuality::new(slice, opts.rereads())
+		let pos =
+			if self.custom.is_none() { self.track.position() }
+			else { TrackPosition::Invalid };
+		TrackQuality::new(slice, opts.rereads(), pos)
+	}
+
+	/// # Disc Track Slice.
+	///
+	/// Return the samples for an individual track — including the usual
+	/// `SAMPLE_OVERREAD` padding on either side — from a whole-disc rip, or
+	/// `None` if the track isn't fully covered.
+	pub(crate) fn disc_track_slice(&self, track: Track) -> Option<&[RipSample]> {
+		let rng = track_rng_to_rip_range(track)?;
+		let start = usize::try_from(rng.start.checked_sub(self.rip_rng.start)?).ok()?;
+		let end = usize::try_from(rng.end.checked_sub(self.rip_rng.start)?).ok()?;
+		self.data.get(start..end)
 	}
 
 	/// # Track Slice.
@@ -455,6 +1141,17 @@ impl RipState {
 	/// Returns `true` if the data was not seeded from a previous state.
 	pub(crate) const fn is_new(&self) -> bool { self.new }
 
+	/// # Take Recovered.
+	///
+	/// Returns `true` if the data was seeded from the backup because the
+	/// primary state was corrupt, resetting the flag so it is only reported
+	/// once.
+	pub(crate) const fn take_recovered(&mut self) -> bool {
+		let out = self.recovered;
+		self.recovered = false;
+		out
+	}
+
 	/// # Quick Hash.
 	///
 	/// Hash the contents of the ripped data. This provides an easy metric for
@@ -464,6 +1161,7 @@ impl RipState {
 		let mut hasher = crc32fast::Hasher::new();
 		self.toc.hash(&mut hasher);
 		self.track.hash(&mut hasher);
+		if let Some(rng) = self.custom.as_ref() { rng.hash(&mut hasher); }
 		self.data.hash(&mut hasher);
 		hasher.finalize()
 	}
@@ -471,47 +1169,1014 @@ impl RipState {
 
 
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// # State Summary.
+///
+/// A snapshot of a track's quality counts and AccurateRip/CTDB results, saved
+/// to the state header alongside the data hash so `--status` can report
+/// without loading — and walking — all of the samples.
+///
+/// The counts depend on the re-read thresholds, so those are saved too.
+pub(super) struct StateSummary {
+	/// # Data Hash.
+	hash: u32,
+
+	/// # Re-Read Thresholds.
+	rereads: (u8, u8),
+
+	/// # Quality (Bad, Maybe, Likely, Confirmed).
+	quality: [u32; 4],
+
+	/// # AccurateRip.
+	ar: Option<(u8, u8)>,
+
+	/// # CTDB.
+	ctdb: Option<u16>,
+}
+
+impl StateSummary {
+	/// # Load.
+	///
+	/// Read the summary from the header of the track's saved state, if there
+	/// is one, without bothering with the data.
+	pub(super) fn load(toc: &Toc, track: Track) -> Option<Self> {
+		use std::io::Read;
+
+		let mut file = File::open(state_path(toc, track).ok()?).ok()?;
+		let mut buf = [0_u8; MAGIC.len() + 4 + SUMMARY_SIZE];
+		file.read_exact(&mut buf).ok()?;
+		if ! [MAGIC, MAGIC_V5, MAGIC_V4].iter().any(|m| buf[..MAGIC.len()] == *m) { return None; }
+
+		let (hash, summary) = buf[MAGIC.len()..].split_at(4);
+		Self::from_bytes(
+			u32::from_le_bytes(hash.try_into().ok()?),
+			summary.try_into().ok()?,
+		)
+	}
+
+	/// # From Bytes.
+	///
+	/// Parse the header bytes, returning `None` if no summary was saved.
+	fn from_bytes(hash: u32, src: &[u8; SUMMARY_SIZE]) -> Option<Self> {
+		if src[0] == 0 { return None; }
+
+		let mut quality = [0_u32; 4];
+		for (q, chunk) in quality.iter_mut().zip(src[3..19].chunks_exact(4)) {
+			*q = u32::from_le_bytes(chunk.try_into().ok()?);
+		}
+
+		Some(Self {
+			hash,
+			rereads: (src[1], src[2]),
+			quality,
+			ar: if src[19] == 0 { None } else { Some((src[20], src[21])) },
+			ctdb: if src[22] == 0 { None } else { Some(u16::from_le_bytes([src[23], src[24]])) },
+		})
+	}
+
+	/// # To Bytes.
+	///
+	/// Serialize the summary for the header, or all zeroes if `None`.
+	fn to_bytes(src: Option<&Self>) -> [u8; SUMMARY_SIZE] {
+		let mut out = [0_u8; SUMMARY_SIZE];
+		if let Some(s) = src {
+			out[0] = 1;
+			out[1] = s.rereads.0;
+			out[2] = s.rereads.1;
+			for (chunk, q) in out[3..19].chunks_exact_mut(4).zip(s.quality) {
+				chunk.copy_from_slice(q.to_le_bytes().as_slice());
+			}
+			if let Some((v1, v2)) = s.ar {
+				out[19] = 1;
+				out[20] = v1;
+				out[21] = v2;
+			}
+			if let Some(v) = s.ctdb {
+				out[22] = 1;
+				out[23..].copy_from_slice(v.to_le_bytes().as_slice());
+			}
+		}
+		out
+	}
+
+	/// # Re-Read Thresholds.
+	pub(super) const fn rereads(&self) -> (u8, u8) { self.rereads }
+
+	/// # Quality.
+	pub(super) const fn quality(&self) -> TrackQuality {
+		TrackQuality::from_array(self.quality)
+	}
+
+	/// # AccurateRip.
+	pub(super) const fn ar(&self) -> Option<(u8, u8)> { self.ar }
+
+	/// # CTDB.
+	pub(super) const fn ctdb(&self) -> Option<u16> { self.ctdb }
+}
+
+
+
 /// # Accessible Range.
 ///
 /// Find the region of the disc (containing audio) that is accessible to the
 /// drive, given its offset.
-fn accessible_range(toc: &Toc, offset: ReadOffset) -> Option<Range<i32>> {
+///
+/// ## Errors
+///
+/// This will return an error if the numbers overflow, or there's nothing left
+/// in between, i.e. the table of contents is degenerate.
+pub(crate) fn accessible_range(toc: &Toc, offset: ReadOffset)
+-> Result<Range<i32>, RipRipError> {
 	// The base leadin will usually be zero, but if there's a data session
 	// before the first track, we'll want to start with the actual audio.
 	let mut leadin =
 		if matches!(toc.kind(), TocKind::DataFirst) {
 			i32::try_from(toc.audio_leadin_normalized()).ok()
-				.and_then(|n| n.checked_mul(i32::from(SAMPLES_PER_SECTOR)))?
+				.and_then(|n| n.checked_mul(i32::from(SAMPLES_PER_SECTOR)))
+				.ok_or(RipRipError::RipOverflow)?
 		}
 		else { 0 };
 
 	// The leadout is what it is.
 	let mut leadout = i32::try_from(toc.audio_leadout_normalized()).ok()
-		.and_then(|n| n.checked_mul(i32::from(SAMPLES_PER_SECTOR)))?;
+		.and_then(|n| n.checked_mul(i32::from(SAMPLES_PER_SECTOR)))
+		.ok_or(RipRipError::RipOverflow)?;
 
 	// A negative offset won't be able to reach the beginning.
 	if offset.is_negative() {
-		leadin = leadin.checked_add(i32::from(offset.samples_abs()))?;
+		leadin = leadin.checked_add(i32::from(offset.samples_abs()))
+			.ok_or(RipRipError::RipOverflow)?;
 	}
 	// A positive offset won't be able to reach the end.
 	else {
-		leadout = leadout.checked_sub(i32::from(offset.samples_abs()))?;
+		leadout = leadout.checked_sub(i32::from(offset.samples_abs()))
+			.ok_or(RipRipError::RipOverflow)?;
 	}
 
-	// Can't imagine this would ever not be the case, but might as well check.
-	if leadin < leadout { Some(leadin..leadout) }
-	else { None }
+	// Everything might compute and still leave nothing to rip if the ToC is
+	// garbage.
+	if leadin < leadout { Ok(leadin..leadout) }
+	else { Err(RipRipError::TocEmpty) }
 }
 
-/// # Track Range to Rip Range.
-fn track_rng_to_rip_range(track: Track) -> Option<Range<i32>> {
-	let rng = track.sector_range_normalized();
+/// # Silence.
+///
+/// Count the leading and trailing silent samples. If the data is nothing but
+/// silence, it is all counted as leading.
+fn silence(data: &[RipSample]) -> (usize, usize) {
+	let start = data.iter().take_while(|v| v.is_silent()).count();
+	let end =
+		if start == data.len() { 0 }
+		else { data.iter().rev().take_while(|v| v.is_silent()).count() };
+	(start, end)
+}
+
+/// # Save Album WAV.
+///
+/// Join the PCM data from the previously-exported WAVs in `srcs` — in order —
+/// into a single gapless WAV at `dst`, returning the path for reference.
+///
+/// ## Errors
+///
+/// This will return an error if any of the sources are missing or invalid,
+/// or the combined data is too big for a WAV.
+pub(crate) fn save_album_wav(dst: PathBuf, srcs: &[PathBuf])
+-> Result<PathBuf, RipRipError> {
+	use std::io::{
+		Seek,
+		SeekFrom,
+		Write,
+	};
+
+	let err = || RipRipError::Write(dst.to_string_lossy().into_owned());
+	let mut data_len = 0_u32;
+	let mut bits = None;
+	let mut writer = CacheWriter::new(&dst)?;
+	{
+		let mut buf = BufWriter::with_capacity(BUFFER_SIZE, writer.writer());
+
+		// We won't know the sizes until the end, so start with a blank header.
+		buf.write_all(WAVE_HEADER.as_slice()).map_err(|e| write_error(&e, &dst, err))?;
+
+		// Copy over the PCM from each source, making sure they're all the
+		// same depth.
+		for src in srcs {
+			let raw = std::fs::read(src).map_err(|_| err())?;
+			let (depth, pcm) = crate::wav_pcm(&raw).ok_or_else(err)?;
+			if *bits.get_or_insert(depth) != depth { return Err(err()); }
+			data_len = u32::try_from(pcm.len()).ok()
+				.and_then(|n| data_len.checked_add(n))
+				.filter(|n| n.checked_add(44 - 8).is_some())
+				.ok_or_else(err)?;
+			buf.write_all(pcm).map_err(|e| write_error(&e, &dst, err))?;
+		}
+
+		// Now go back and fill out the header properly.
+		let mut header = wave_header(bits.unwrap_or(16));
+		header[4..8].copy_from_slice((44 - 8 + data_len).to_le_bytes().as_slice());
+		header[40..].copy_from_slice(data_len.to_le_bytes().as_slice());
+		buf.seek(SeekFrom::Start(0))
+			.and_then(|_| buf.write_all(header.as_slice()))
+			.and_then(|()| buf.flush())
+			.map_err(|e| write_error(&e, &dst, err))?;
+	}
+	writer.finish()?;
+	Ok(dst)
+}
+
+/// # Save WAV.
+///
+/// Write the samples to `dst` in WAV format — left-shifting them into
+/// bigger containers if `bits` is `24` or `32` — returning the path for
+/// reference.
+///
+/// ## Errors
+///
+/// This will bubble up any I/O-related errors encountered, but should be
+/// fine.
+fn save_wav<S: AsRef<[u8]>>(
+	dst: PathBuf,
+	data: &[S],
+	extra: Option<&[u8]>,
+	bits: u8,
+) -> Result<PathBuf, RipRipError> {
+	use std::io::Write;
+
+	// The data length is easy: one channel's worth of bytes times two per
+	// sample.
+	let width = usize::from(bits / 8);
+	let header = u32::try_from(data.len())
+		.ok()
+		.and_then(|n| n.checked_mul(u32::from(bits / 4)))
+		.and_then(|n| wav_header(n, extra, bits))
+		.ok_or_else(|| RipRipError::Write(dst.to_string_lossy().into_owned()))?;
+
+	// Write the data!
+	let err = || RipRipError::Write(dst.to_string_lossy().into_owned());
+	let mut writer = CacheWriter::new(&dst)?;
+	{
+		let mut buf = BufWriter::with_capacity(BUFFER_SIZE, writer.writer());
+
+		// The header comes first.
+		buf.write_all(header.as_slice()).map_err(|e| write_error(&e, &dst, err))?;
+
+		// Now it's just straight PCM funtimes!
+		if width == 2 {
+			for v in data {
+				buf.write_all(v.as_ref()).map_err(|e| write_error(&e, &dst, err))?;
+			}
+		}
+		// Unless we're upconverting, in which case the bytes need to be
+		// nudged over a bit first.
+		else {
+			let mut wide = [0_u8; 8];
+			for v in data {
+				upconvert(v.as_ref(), &mut wide[..width * 2]);
+				buf.write_all(&wide[..width * 2]).map_err(|e| write_error(&e, &dst, err))?;
+			}
+		}
+		buf.flush().map_err(|e| write_error(&e, &dst, err))?;
+	}
+	writer.finish()?;
+	Ok(dst)
+}
+
+/// # Save Data.
+///
+/// Write the `rng` portion of `data` to `dst` in WAV format, interpolating
+/// over the bad bits first if `RipOptions::interpolate` is set, and including
+/// any extra chunks the options call for.
+///
+/// The full `data` is passed along — rather than a pre-sliced track — so the
+/// padding can lend its samples to any gaps at the very start or end.
+///
+/// ## Errors
+///
+/// This will bubble up any I/O-related errors encountered, but should be
+/// fine.
+fn save_data(
+	dst: PathBuf,
+	toc: &Toc,
+	track: Track,
+	data: &[RipSample],
+	rng: Range<usize>,
+	opts: &RipOptions,
+	chk: (Option<(u8, u8)>, Option<u16>),
+) -> Result<PathBuf, RipRipError> {
+	if opts.interpolate() {
+		if let Some((fixed, total)) = interpolate(data, rng.clone()) {
+			let extra = wav_chunks(toc, track, opts, chk, total);
+			let dst = save_wav(dst, &fixed, extra.as_deref(), opts.bits())?;
+			if opts.sha256() { save_sha256(&dst)?; }
+			return Ok(dst);
+		}
+	}
+
+	let extra = wav_chunks(toc, track, opts, chk, 0);
+	let dst = save_wav(dst, &data[rng], extra.as_deref(), opts.bits())?;
+	if opts.sha256() { save_sha256(&dst)?; }
+	Ok(dst)
+}
+
+/// # Save SHA-256 Sidecar.
+///
+/// Hash the file at `src` — as it exists on disk, after saving — and write
+/// the result to a `sha256sum`-compatible sidecar next to it, returning the
+/// sidecar's path for reference.
+///
+/// ## Errors
+///
+/// This will bubble up any I/O-related errors encountered, but should be
+/// fine.
+pub(crate) fn save_sha256(src: &Path) -> Result<PathBuf, RipRipError> {
+	use std::io::{
+		Read,
+		Write,
+	};
+
+	let name = src.file_name()
+		.and_then(|n| n.to_str())
+		.ok_or_else(|| RipRipError::Write(src.to_string_lossy().into_owned()))?;
+	let mut dst = src.as_os_str().to_owned();
+	dst.push(".sha256");
+	let dst = PathBuf::from(dst);
+
+	// Hash the file.
+	let err = || RipRipError::Write(dst.to_string_lossy().into_owned());
+	let mut hash = Sha256::new();
+	let mut file = File::open(src).map_err(|_| err())?;
+	let mut buf = vec![0_u8; BUFFER_SIZE];
+	loop {
+		let len = file.read(&mut buf).map_err(|_| err())?;
+		if len == 0 { break; }
+		hash.update(&buf[..len]);
+	}
+
+	// Write it out in the usual "<hex>  <name>" format.
+	let mut line = String::with_capacity(68 + name.len());
+	for b in hash.finish() {
+		line.push(char::from(b"0123456789abcdef"[usize::from(b >> 4)]));
+		line.push(char::from(b"0123456789abcdef"[usize::from(b & 15)]));
+	}
+	line.push_str("  ");
+	line.push_str(name);
+	line.push('\n');
+
+	let mut writer = CacheWriter::new(&dst)?;
+	writer.writer().write_all(line.as_bytes()).map_err(|e| write_error(&e, &dst, err))?;
+	writer.finish()?;
+	Ok(dst)
+}
+
+/// # Track Exported?
+///
+/// Returns `true` if the state for the track has a record of an export, and
+/// the file it was exported to is still there.
+pub(crate) fn track_exported(toc: &Toc, track: Track) -> bool {
+	let Some((_, suffix)) = state_path(toc, track).ok().and_then(|src| load_exported(&src)) else {
+		return false;
+	};
+	track_path(toc, track).is_ok_and(|dst| suffixed_path(&dst, suffix).is_file())
+}
+
+/// # Load Export Quality.
+///
+/// Read the last export quality from the header of the state at `src`, if
+/// there is one, without bothering with anything else.
+fn load_exported(src: &Path) -> Option<([u32; 4], u8)> {
+	use std::io::Read;
+
+	/// # Header Size (V5).
+	const V5: usize = MAGIC.len() + 4 + SUMMARY_SIZE + EXPORT_SIZE_V5;
+
+	// V5 states are one byte short, but the missing suffix was always zero.
+	let mut file = File::open(src).ok()?;
+	let mut buf = [0_u8; MAGIC.len() + 4 + SUMMARY_SIZE + EXPORT_SIZE];
+	file.read_exact(&mut buf[..V5]).ok()?;
+	if buf[..MAGIC.len()] == MAGIC { file.read_exact(&mut buf[V5..]).ok()?; }
+	else if buf[..MAGIC.len()] != MAGIC_V5 { return None; }
+	exported_from_bytes(buf[MAGIC.len() + 4 + SUMMARY_SIZE..].try_into().ok()?)
+}
+
+/// # Legacy State?
+///
+/// Returns `true` if the state at `src` was saved before exports were
+/// recorded at all.
+fn legacy_state(src: &Path) -> bool {
+	use std::io::Read;
+
+	let mut magic = [0_u8; MAGIC.len()];
+	File::open(src).and_then(|mut f| f.read_exact(&mut magic)).is_ok() &&
+	(magic == MAGIC_V4 || magic == MAGIC_V3)
+}
+
+/// # Export Quality From Bytes.
+///
+/// Parse the header bytes, returning `None` if nothing was recorded.
+fn exported_from_bytes(src: &[u8; EXPORT_SIZE]) -> Option<([u32; 4], u8)> {
+	if src[0] == 0 { return None; }
+
+	let mut out = [0_u32; 4];
+	for (q, chunk) in out.iter_mut().zip(src[1..17].chunks_exact(4)) {
+		*q = u32::from_le_bytes(chunk.try_into().ok()?);
+	}
+	Some((out, src[17]))
+}
+
+/// # Export Quality To Bytes.
+///
+/// Serialize the export quality for the header, or all zeroes if `None`.
+fn exported_to_bytes(src: Option<([u32; 4], u8)>) -> [u8; EXPORT_SIZE] {
+	let mut out = [0_u8; EXPORT_SIZE];
+	if let Some((quality, suffix)) = src {
+		out[0] = 1;
+		for (chunk, q) in out[1..17].chunks_exact_mut(4).zip(quality) {
+			chunk.copy_from_slice(q.to_le_bytes().as_slice());
+		}
+		out[17] = suffix;
+	}
+	out
+}
+
+/// # Suffixed Path.
+///
+/// Return `src` with `-{suffix}` tacked onto the end of the file stem, e.g.
+/// `{CDDB ID}__01-2.wav`, or `src` itself if the suffix is zero.
+fn suffixed_path(src: &Path, suffix: u8) -> PathBuf {
+	if suffix == 0 { return src.to_path_buf(); }
+
+	let stem = src.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+	let ext = src.extension().map(|s| s.to_string_lossy()).unwrap_or_default();
+	src.with_file_name(format!("{stem}-{suffix}.{ext}"))
+}
+
+/// # Upconvert Sample.
+///
+/// Left-shift the two 16-bit channels of `src` into the 24- or 32-bit
+/// channels of `dst`, which should be six or eight bytes long respectively.
+/// Being little-endian, this just means zero-padding the low end.
+fn upconvert(src: &[u8], dst: &mut [u8]) {
+	let width = dst.len() / 2;
+	for (s, d) in src.chunks_exact(2).zip(dst.chunks_exact_mut(width)) {
+		let (lo, hi) = d.split_at_mut(width - 2);
+		lo.fill(0);
+		hi.copy_from_slice(s);
+	}
+}
+
+/// # Interpolate.
+///
+/// Return a copy of the `rng` portion of `data` with any bad or unread
+/// samples replaced by a straight line — per channel — between the nearest
+/// non-bad neighbors on either side, or a flat copy of the one neighbor if
+/// the run butts up against the edge of `data`.
+///
+/// The number of samples so replaced is returned alongside the data, or
+/// `None` if there were none.
+fn interpolate(data: &[RipSample], rng: Range<usize>) -> Option<(Vec<Sample>, usize)> {
+	/// # Channel Value.
+	fn channel(sample: Sample, idx: usize) -> i64 {
+		i64::from(i16::from_le_bytes([sample[idx], sample[idx + 1]]))
+	}
+
+	let slice = data.get(rng.clone())?;
+	if ! slice.iter().any(RipSample::is_bad) { return None; }
+
+	let mut out: Vec<Sample> = slice.iter().map(RipSample::as_array).collect();
+	let mut total = 0;
+	let mut idx = rng.start;
+	while idx < rng.end {
+		if ! data[idx].is_bad() {
+			idx += 1;
+			continue;
+		}
+
+		// Find the full extent of the run, which might begin before the
+		// range if this is the first one.
+		let mut start = idx;
+		while start != 0 && data[start - 1].is_bad() { start -= 1; }
+		let mut end = idx + 1;
+		while end < data.len() && data[end].is_bad() { end += 1; }
+
+		// Find the anchors.
+		let before = start.checked_sub(1).map(|i| data[i].as_array());
+		let after = data.get(end).map(RipSample::as_array);
+		let (a, b) = match (before, after) {
+			(Some(a), Some(b)) => (a, b),
+			(Some(a), None) => (a, a),
+			(None, Some(b)) => (b, b),
+			(None, None) => (NULL_SAMPLE, NULL_SAMPLE),
+		};
+
+		// Draw the line.
+		let steps = i64::try_from(end - start + 1).unwrap_or(i64::MAX);
+		for i in idx..end.min(rng.end) {
+			let step = i64::try_from(i - start + 1).unwrap_or(0);
+			let mut sample = NULL_SAMPLE;
+			for c in [0, 2] {
+				let (va, vb) = (channel(a, c), channel(b, c));
+				let v = i16::try_from(va + (vb - va) * step / steps).unwrap_or(0);
+				sample[c..c + 2].copy_from_slice(v.to_le_bytes().as_slice());
+			}
+			out[i - rng.start] = sample;
+			total += 1;
+		}
+
+		idx = end;
+	}
+
+	Some((out, total))
+}
+
+/// # WAV Header.
+///
+/// Return the WAV header for `data_len` bytes of `bits`-deep PCM, with the
+/// `extra` chunks — `bext`, `LIST`, etc., if any — slotted in between the
+/// format and data chunks.
+///
+/// Returns `None` if the sizes are too big for a WAV.
+fn wav_header(data_len: u32, extra: Option<&[u8]>, bits: u8) -> Option<Vec<u8>> {
+	let extra = extra.unwrap_or_default();
+
+	// The file length excludes "RIFF" and the four bytes specifying the
+	// file length.
+	let file_len = u32::try_from(extra.len()).ok()
+		.and_then(|n| n.checked_add(44 - 8))
+		.and_then(|n| n.checked_add(data_len))?;
+
+	let header = wave_header(bits);
+	let mut out = Vec::with_capacity(44 + extra.len());
+	out.extend_from_slice(&header[..36]);
+	out[4..8].copy_from_slice(file_len.to_le_bytes().as_slice());
+	out.extend_from_slice(extra);
+	out.extend_from_slice(&header[36..40]);
+	out.extend_from_slice(data_len.to_le_bytes().as_slice());
+	Some(out)
+}
+
+/// # Extra WAV Chunks.
+///
+/// Return the optional `bext` and `LIST`/`INFO` chunks, ready to be slotted
+/// into the header, or `None` if neither is wanted.
+///
+/// The latter is always included if any samples were `interpolated`, so
+/// there's a record of the fakery.
+fn wav_chunks(
+	toc: &Toc,
+	track: Track,
+	opts: &RipOptions,
+	chk: (Option<(u8, u8)>, Option<u16>),
+	interpolated: usize,
+) -> Option<Vec<u8>> {
+	let info = opts.wav_info() || interpolated != 0;
+	if ! opts.bwf() && ! info { return None; }
+
+	let mut out = Vec::new();
+	if opts.bwf() { out.extend_from_slice(&bext_chunk(toc, track, opts, chk)); }
+	if info { out.extend_from_slice(&info_chunk(opts, chk, interpolated)); }
+	Some(out)
+}
+
+/// # INFO List Chunk.
+///
+/// Build a `LIST`/`INFO` chunk — ID and size included — with the
+/// AccurateRip/CTDB confidences, pass count, and number of `interpolated`
+/// samples (if any) in an `ICMT` comment, and Rip Rip's name and version in
+/// `ISFT`.
+fn info_chunk(
+	opts: &RipOptions,
+	chk: (Option<(u8, u8)>, Option<u16>),
+	interpolated: usize,
+) -> Vec<u8> {
+	/// # Software.
+	const SOFTWARE: &str = concat!("Rip Rip Hooray! v", env!("CARGO_PKG_VERSION"));
+
+	/// # Push Subchunk.
+	///
+	/// Strings are null-terminated, and padded to an even length (which
+	/// doesn't count towards the size).
+	fn push(out: &mut Vec<u8>, id: [u8; 4], src: &str) {
+		let len = src.len() + 1;
+		out.extend_from_slice(id.as_slice());
+		out.extend_from_slice(u32::try_from(len).unwrap_or(0).to_le_bytes().as_slice());
+		out.extend_from_slice(src.as_bytes());
+		out.resize(out.len() + 1 + len % 2, 0);
+	}
+
+	// Sort out the comment.
+	let mut comment = format!(
+		"AccurateRip: {}; CTDB: {}; Passes: {}{}",
+		chk.0.map_or_else(|| "--".to_owned(), |(v1, v2)| format!("{v1}+{v2}")),
+		chk.1.map_or_else(|| "--".to_owned(), |v| v.to_string()),
+		if opts.auto_passes() { "up to " } else { "" },
+		opts.passes(),
+	);
+	if interpolated != 0 {
+		comment.push_str("; Interpolated: ");
+		comment.push_str(&interpolated.to_string());
+		comment.push_str(" samples");
+	}
+
+	let mut out = Vec::with_capacity(96);
+	out.extend_from_slice(b"LIST\0\0\0\0INFO");
+	push(&mut out, *b"ICMT", &comment);
+	push(&mut out, *b"ISFT", SOFTWARE);
+	let size = u32::try_from(out.len() - 8).unwrap_or(0);
+	out[4..8].copy_from_slice(size.to_le_bytes().as_slice());
+	out
+}
+
+/// # Broadcast Wave Extension Chunk.
+///
+/// Build a (version 1) `bext` chunk — ID and size included — for archival
+/// provenance, crediting Rip Rip, the drive(s), and the AccurateRip/CTDB
+/// confidences, if any, with the current (UTC) time as the origination date.
+fn bext_chunk(
+	toc: &Toc,
+	track: Track,
+	opts: &RipOptions,
+	chk: (Option<(u8, u8)>, Option<u16>),
+) -> Vec<u8> {
+	use std::fmt::Write;
+
+	/// # Fixed Field Size.
+	///
+	/// Everything but the coding history.
+	const FIXED: usize = 602;
+
+
+	/// # Push Fixed-Width ASCII.
+	///
+	/// Truncate or null-pad the value to fit the field.
+	fn push(out: &mut Vec<u8>, src: &str, len: usize) {
+		let src = &src.as_bytes()[..src.len().min(len)];
+		out.extend_from_slice(src);
+		out.resize(out.len() + len - src.len(), 0);
+	}
+
+	// Sort out the description.
+	let mut desc = format!("Track {:02} of CDDB {}", track.number(), toc.cddb_id());
+	match opts.drives() {
+		[Some(a), Some(b)] => { let _res = write!(&mut desc, "; Drives: {a}, {b}"); },
+		[Some(d), None] | [None, Some(d)] => { let _res = write!(&mut desc, "; Drive: {d}"); },
+		[None, None] => {},
+	}
+	if let Some((v1, v2)) = chk.0 {
+		let _res = write!(&mut desc, "; AccurateRip: {v1}+{v2}");
+	}
+	if let Some(v) = chk.1 { let _res = write!(&mut desc, "; CTDB: {v}"); }
+	desc.retain(|c| c.is_ascii());
+
+	// And the coding history.
+	let history = format!(
+		"A=PCM,F=44100,W={},M=stereo,T=Rip Rip Hooray! v{}\r\n",
+		opts.bits(),
+		env!("CARGO_PKG_VERSION"),
+	);
+
+	// Chunks need to be an even size.
+	let size = FIXED + history.len() + history.len() % 2;
+	let now = FmtUtc2k::now();
+
+	let mut out = Vec::with_capacity(8 + size);
+	out.extend_from_slice(b"bext");
+	out.extend_from_slice(u32::try_from(size).unwrap_or(0).to_le_bytes().as_slice());
+	push(&mut out, &desc, 256);
+	push(&mut out, "Rip Rip Hooray!", 32);
+	push(&mut out, &format!("{}-{:02}", toc.cddb_id(), track.number()), 32);
+	push(&mut out, now.date(), 10);
+	push(&mut out, now.time(), 8);
+	out.extend_from_slice(&[0; 8]);                        // Time reference.
+	out.extend_from_slice(1_u16.to_le_bytes().as_slice()); // Version.
+	out.resize(out.len() + 64 + 190, 0);                   // UMID, reserved.
+	out.extend_from_slice(history.as_bytes());
+	out.resize(8 + size, 0);
+	out
+}
+
+/// # Sector Todo?
+///
+/// Returns `true` if the sector still needs work, i.e. any of its samples are
+/// not yet likely. If `RipOptions::confused_only` is set, only the confused
+/// samples are considered.
+pub(super) fn sector_todo(sector: &[RipSample], opts: &RipOptions) -> bool {
+	let rereads = opts.rereads();
+	if opts.confused_only() {
+		sector.iter().any(|v| v.is_confused() && ! v.is_likely(rereads))
+	}
+	else { ! sector.iter().all(|v| v.is_likely(rereads)) }
+}
+
+/// # Sector Range to Rip Range.
+///
+/// Convert a (normalized) sector range to the padded sample range.
+fn sector_rng_to_rip_range(rng: Range<i32>) -> Option<Range<i32>> {
 	let rng =
-		i32::try_from(rng.start).ok()
-			.and_then(|n| n.checked_mul(i32::from(SAMPLES_PER_SECTOR)))
+		rng.start.checked_mul(i32::from(SAMPLES_PER_SECTOR))
 			.and_then(|n| n.checked_sub(i32::from(SAMPLE_OVERREAD)))?..
-		i32::try_from(rng.end).ok()
-			.and_then(|n| n.checked_mul(i32::from(SAMPLES_PER_SECTOR)))
+		rng.end.checked_mul(i32::from(SAMPLES_PER_SECTOR))
 			.and_then(|n| n.checked_add(i32::from(SAMPLE_OVERREAD)))?;
 	Some(rng)
 }
+
+/// # Track Range to Rip Range.
+fn track_rng_to_rip_range(track: Track) -> Option<Range<i32>> {
+	let rng = track.sector_range_normalized();
+	sector_rng_to_rip_range(
+		i32::try_from(rng.start).ok()?..i32::try_from(rng.end).ok()?
+	)
+}
+
+
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn t_accessible_range() {
+		let toc = Toc::from_cdtoc("4+96+2D2B+6256+B327+D84A").expect("Invalid TOC.");
+		let end = i32::try_from(toc.audio_leadout_normalized()).expect("Leadout overflow.") * 588;
+		assert_eq!(accessible_range(&toc, ReadOffset::default()), Ok(0..end));
+
+		let offset = ReadOffset::try_from(b"5".as_slice()).expect("Read offset 5 failed.");
+		assert_eq!(accessible_range(&toc, offset), Ok(0..end - 5));
+
+		// A single sector won't survive a big enough offset.
+		let toc = Toc::from_parts(vec![150], None, 151).expect("Invalid TOC.");
+		let offset = ReadOffset::try_from(b"700".as_slice()).expect("Read offset 700 failed.");
+		assert_eq!(accessible_range(&toc, offset), Err(RipRipError::TocEmpty));
+	}
+
+	#[test]
+	fn t_bwf() {
+		let toc = Toc::from_cdtoc("4+96+2D2B+6256+B327+D84A").expect("Invalid TOC.");
+		let track = toc.audio_track(2).expect("Missing track.");
+		let opts = RipOptions::default().with_bits(24);
+		let bext = bext_chunk(&toc, track, &opts, (Some((3, 5)), None));
+		assert_eq!(&bext[..4], b"bext");
+		let size = u32::from_le_bytes([bext[4], bext[5], bext[6], bext[7]]);
+		assert_eq!(bext.len(), 8 + size as usize);
+		assert_eq!(bext.len() % 2, 0, "Chunks must be even.");
+		assert!(bext[8..264].starts_with(b"Track 02 of CDDB "));
+		assert!(bext[610..].starts_with(b"A=PCM,F=44100,W=24,"));
+
+		// The PCM should still be findable with or without the chunk.
+		let pcm = [1_u8, 2, 3, 4, 5, 6, 7, 8];
+		for bext in [None, Some(bext.as_slice())] {
+			let mut raw = wav_header(8, bext, 16).expect("Invalid header.");
+			raw.extend_from_slice(&pcm);
+			let file_len = u32::from_le_bytes([raw[4], raw[5], raw[6], raw[7]]);
+			assert_eq!(raw.len(), file_len as usize + 8);
+			assert_eq!(crate::wav_pcm(&raw), Some((16, pcm.as_slice())));
+		}
+	}
+
+	#[test]
+	fn t_wave_header() {
+		// The base should be unchanged at sixteen bits.
+		assert_eq!(wave_header(16), WAVE_HEADER);
+
+		for (bits, align) in [(24_u8, 6_u8), (32, 8)] {
+			let header = wave_header(bits);
+			assert_eq!(header[32], align);
+			assert_eq!(header[34], bits);
+			assert_eq!(
+				u32::from_le_bytes([header[28], header[29], header[30], header[31]]),
+				44_100 * u32::from(align),
+			);
+
+			// The PCM should still be findable.
+			let mut raw = wav_header(u32::from(align), None, bits).expect("Invalid header.");
+			let mut pcm = [0_u8; 8];
+			upconvert(&[1, 2, 3, 4], &mut pcm[..usize::from(align)]);
+			raw.extend_from_slice(&pcm[..usize::from(align)]);
+			assert_eq!(crate::wav_pcm(&raw), Some((bits, &pcm[..usize::from(align)])));
+		}
+	}
+
+	#[test]
+	fn t_upconvert() {
+		let src = (-2_i16).to_le_bytes().into_iter()
+			.chain(1_000_i16.to_le_bytes())
+			.collect::<Vec<u8>>();
+
+		let mut dst = [9_u8; 6];
+		upconvert(&src, &mut dst);
+		assert_eq!(i32::from_le_bytes([0, dst[0], dst[1], dst[2]]) >> 8, -2 << 8);
+		assert_eq!(i32::from_le_bytes([0, dst[3], dst[4], dst[5]]) >> 8, 1_000 << 8);
+
+		let mut dst = [9_u8; 8];
+		upconvert(&src, &mut dst);
+		assert_eq!(i32::from_le_bytes([dst[0], dst[1], dst[2], dst[3]]), -2 << 16);
+		assert_eq!(i32::from_le_bytes([dst[4], dst[5], dst[6], dst[7]]), 1_000 << 16);
+	}
+
+	#[test]
+	fn t_info_chunk() {
+		let opts = RipOptions::default().with_passes(3);
+		let info = info_chunk(&opts, (Some((3, 5)), None), 0);
+		assert_eq!(&info[..4], b"LIST");
+		let size = u32::from_le_bytes([info[4], info[5], info[6], info[7]]);
+		assert_eq!(info.len(), 8 + size as usize);
+		assert_eq!(info.len() % 2, 0, "Chunks must be even.");
+		assert_eq!(&info[8..16], b"INFOICMT");
+
+		// The comment.
+		let len = u32::from_le_bytes([info[16], info[17], info[18], info[19]]) as usize;
+		let comment = &info[20..20 + len];
+		assert_eq!(comment, b"AccurateRip: 3+5; CTDB: --; Passes: 3\0");
+
+		// The software should come next.
+		let next = 20 + len + len % 2;
+		assert_eq!(&info[next..next + 4], b"ISFT");
+
+		// And the PCM should still be findable.
+		let pcm = [1_u8, 2, 3, 4];
+		let mut raw = wav_header(4, Some(info.as_slice()), 16).expect("Invalid header.");
+		raw.extend_from_slice(&pcm);
+		assert_eq!(crate::wav_pcm(&raw), Some((16, pcm.as_slice())));
+
+		// Interpolation should be noted.
+		let info = info_chunk(&opts, (None, Some(2)), 12);
+		let len = u32::from_le_bytes([info[16], info[17], info[18], info[19]]) as usize;
+		assert_eq!(
+			&info[20..20 + len],
+			b"AccurateRip: --; CTDB: 2; Passes: 3; Interpolated: 12 samples\0",
+		);
+	}
+
+	#[test]
+	fn t_interpolate() {
+		/// # Sample.
+		const fn s(l: i16, r: i16) -> Sample {
+			let l = l.to_le_bytes();
+			let r = r.to_le_bytes();
+			[l[0], l[1], r[0], r[1]]
+		}
+
+		let good = |l, r| RipSample::from(s(l, r));
+		let data = [
+			RipSample::Tbd,
+			good(0, 100),
+			RipSample::Bad(s(5, 5)),
+			RipSample::Tbd,
+			RipSample::Tbd,
+			good(400, -100),
+			RipSample::Bad(s(5, 5)),
+		];
+
+		// Nothing to do.
+		assert_eq!(interpolate(&data, 1..2), None);
+
+		// The middle bits should form a line.
+		assert_eq!(
+			interpolate(&data, 1..6),
+			Some((
+				vec![s(0, 100), s(100, 50), s(200, 0), s(300, -50), s(400, -100)],
+				3,
+			)),
+		);
+
+		// The edges should copy their only neighbor, even if the run starts
+		// before the range.
+		assert_eq!(
+			interpolate(&data, 0..7),
+			Some((
+				vec![
+					s(0, 100), s(0, 100), s(100, 50), s(200, 0), s(300, -50),
+					s(400, -100), s(400, -100),
+				],
+				5,
+			)),
+		);
+		assert_eq!(
+			interpolate(&data, 3..5),
+			Some((vec![s(200, 0), s(300, -50)], 2)),
+		);
+	}
+
+	#[test]
+	fn t_state_summary() {
+		assert_eq!(StateSummary::to_bytes(None), [0_u8; SUMMARY_SIZE]);
+		assert_eq!(StateSummary::from_bytes(5, &[0_u8; SUMMARY_SIZE]), None);
+
+		for (ar, ctdb) in [
+			(None, None),
+			(Some((3, 0)), None),
+			(None, Some(512)),
+			(Some((0, 12)), Some(1)),
+		] {
+			let summary = StateSummary {
+				hash: 0xDEAD_BEEF,
+				rereads: (2, 20),
+				quality: [1, 2, 3, 4_000_000],
+				ar,
+				ctdb,
+			};
+			let bytes = StateSummary::to_bytes(Some(&summary));
+			assert_eq!(StateSummary::from_bytes(0xDEAD_BEEF, &bytes), Some(summary));
+		}
+	}
+
+	#[test]
+	fn t_decay_hash() {
+		let toc = Toc::from_cdtoc("4+96+2D2B+6256+B327+D84A").expect("Invalid TOC.");
+		let track = toc.audio_track(1).expect("Missing track.");
+		let mut state = RipState {
+			toc,
+			track,
+			custom: None,
+			disc_rng: 0..0,
+			rip_rng: 0..0,
+			data: vec![RipSample::from([1, 2, 3, 4]); usize::from(SAMPLES_PER_SECTOR)],
+			todo: Vec::new(),
+			new: false,
+			recovered: false,
+			summary: None,
+			exported: None,
+			legacy: false,
+		};
+
+		// A decay pass with no new reads only ages the samples, which
+		// shouldn't count as a change.
+		let before = state.quick_hash();
+		state.decay(8, &RipOptions::default()).expect("Decay failed.");
+		state.decay(8, &RipOptions::default()).expect("Decay failed.");
+		assert_eq!(state.quick_hash(), before);
+	}
+
+	#[test]
+	fn t_exported() {
+		assert_eq!(exported_to_bytes(None), [0_u8; EXPORT_SIZE]);
+		assert_eq!(exported_from_bytes(&[0_u8; EXPORT_SIZE]), None);
+
+		let quality = [1, 2, 3, 4_000_000];
+		let bytes = exported_to_bytes(Some((quality, 3)));
+		assert_eq!(exported_from_bytes(&bytes), Some((quality, 3)));
+
+		// More bad samples is worse, otherwise fewer good ones is.
+		let old = TrackQuality::from_array(quality);
+		assert!(TrackQuality::from_array([2, 1, 3, 4_000_000]).is_worse_than(&old));
+		assert!(TrackQuality::from_array([1, 3, 2, 4_000_000]).is_worse_than(&old));
+		assert!(! TrackQuality::from_array([1, 2, 3, 4_000_000]).is_worse_than(&old));
+		assert!(! TrackQuality::from_array([0, 3, 3, 4_000_000]).is_worse_than(&old));
+		assert!(! TrackQuality::from_array([1, 2, 0, 4_000_003]).is_worse_than(&old));
+	}
+
+	#[test]
+	fn t_load_exported() {
+		let dir = std::env::temp_dir().join(format!("riprip-exported-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).expect("Unable to create temporary directory.");
+		let src = dir.join("state");
+		let quality = [1, 2, 3, 4_000_000];
+		let bytes = exported_to_bytes(Some((quality, 3)));
+
+		// Current.
+		let mut raw = Vec::new();
+		raw.extend_from_slice(MAGIC.as_slice());
+		raw.extend_from_slice(&[0_u8; 4 + SUMMARY_SIZE]);
+		raw.extend_from_slice(bytes.as_slice());
+		std::fs::write(&src, &raw).expect("Unable to write state.");
+		assert_eq!(load_exported(&src), Some((quality, 3)));
+		assert!(! legacy_state(&src));
+
+		// V5 had no suffix.
+		raw[..MAGIC.len()].copy_from_slice(MAGIC_V5.as_slice());
+		std::fs::write(&src, &raw[..raw.len() - 1]).expect("Unable to write state.");
+		assert_eq!(load_exported(&src), Some((quality, 0)));
+		assert!(! legacy_state(&src));
+
+		// And V4 had nothing at all.
+		raw[..MAGIC.len()].copy_from_slice(MAGIC_V4.as_slice());
+		std::fs::write(&src, &raw[..MAGIC.len() + 4 + SUMMARY_SIZE]).expect("Unable to write state.");
+		assert_eq!(load_exported(&src), None);
+		assert!(legacy_state(&src));
+
+		let _res = std::fs::remove_dir_all(&dir);
+		assert!(! legacy_state(&src));
+	}
+
+	#[test]
+	fn t_suffixed_path() {
+		let src = Path::new("/tmp/_riprip/abc__01.wav");
+		assert_eq!(suffixed_path(src, 0), src);
+		assert_eq!(suffixed_path(src, 2), Path::new("/tmp/_riprip/abc__01-2.wav"));
+	}
+
+	#[test]
+	fn t_silence() {
+		let null = RipSample::from([0, 0, 0, 0]);
+		let loud = RipSample::from([1, 2, 3, 4]);
+
+		assert_eq!(silence(&[]), (0, 0));
+		assert_eq!(silence(&[null.clone(), null.clone()]), (2, 0));
+		assert_eq!(
+			silence(&[null.clone(), loud.clone(), null.clone(), loud.clone(), null.clone(), null.clone()]),
+			(1, 2),
+		);
+
+		// Unread samples aren't silence.
+		assert_eq!(silence(&[RipSample::Tbd, null.clone(), loud, null]), (0, 1));
+	}
+}
@@ -0,0 +1,94 @@
+/*!
+# Rip Rip Hooray: C2 Dump
+*/
+
+use cdtoc::{
+	Toc,
+	Track,
+};
+use crate::{
+	c2_path,
+	RipBuffer,
+	RipRipError,
+	write_error,
+};
+use std::{
+	fs::File,
+	io::{
+		BufWriter,
+		Write,
+	},
+	path::PathBuf,
+};
+
+
+
+/// # C2 Dump.
+///
+/// This appends the raw C2 error bits from each sector read to a per-track
+/// binary file for later review. Each record is the read LSN (little-endian
+/// `i32`), the pass number (`u8`), and the C2 bitmap, as-is from the drive.
+///
+/// Nothing is interpreted or condensed; this is strictly a diagnostic tap.
+pub(super) struct C2Dump {
+	/// # Destination Path.
+	dst: PathBuf,
+
+	/// # Writer.
+	file: BufWriter<File>,
+}
+
+impl C2Dump {
+	/// # New Instance.
+	///
+	/// Open (or create) the dump file for the track in append mode.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the path cannot be determined or opened.
+	pub(super) fn new(toc: &Toc, track: Track) -> Result<Self, RipRipError> {
+		let dst = c2_path(toc, track)?;
+		let err = || RipRipError::Write(dst.to_string_lossy().into_owned());
+
+		// Make sure the scratch directory exists.
+		let parent = dst.parent().ok_or_else(err)?;
+		if ! parent.is_dir() {
+			std::fs::create_dir_all(parent).map_err(|e| write_error(&e, &dst, err))?;
+		}
+
+		let file = File::options()
+			.create(true)
+			.append(true)
+			.open(&dst)
+			.map_err(|e| write_error(&e, &dst, err))?;
+
+		Ok(Self { dst, file: BufWriter::new(file) })
+	}
+
+	/// # Push Record.
+	///
+	/// Write the C2 bits from the buffer's last read.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the data cannot be written.
+	pub(super) fn push(&mut self, lsn: i32, pass: u8, buf: &RipBuffer)
+	-> Result<(), RipRipError> {
+		self.file.write_all(&lsn.to_le_bytes())
+			.and_then(|()| self.file.write_all(&[pass]))
+			.and_then(|()| self.file.write_all(buf.c2_slice()))
+			.map_err(|e| write_error(&e, &self.dst, || RipRipError::Write(self.dst.to_string_lossy().into_owned())))
+	}
+
+	/// # Finish.
+	///
+	/// Flush any buffered records to disk.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if the data cannot be written.
+	pub(super) fn finish(mut self) -> Result<(), RipRipError> {
+		self.file.flush()
+			.map_err(|e| write_error(&e, &self.dst, || RipRipError::Write(self.dst.to_string_lossy().into_owned())))
+	}
+}
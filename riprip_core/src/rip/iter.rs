@@ -12,6 +12,7 @@ use std::{
 	ops::Range,
 	slice::{
 		ChunksExactMut,
+		IterMut,
 		RChunksExactMut,
 	},
 };
@@ -21,13 +22,18 @@ use std::{
 #[derive(Debug)]
 /// # Offset Rip Iterator.
 ///
-/// This iterator yields the sector LSNs to read from, and the mutable sector
-/// slices (offset-adjusted) to write back to.
+/// This iterator yields the sector LSNs to read from, the mutable sector
+/// slices (offset-adjusted) to write back to, and the mutable todo flags
+/// tracking whether or not those sectors still need work.
 ///
 /// This will usually be slightly shorter than the full, padded rip range for
 /// the track, as the offset will likely prevent reading and/or writing all the
 /// way up to both edges.
 ///
+/// Sectors whose todo flags are `false` — i.e. those containing nothing but
+/// likely/confirmed samples — are skipped over entirely, without having to
+/// look at any of their samples.
+///
 /// Depending on the settings, it might run start to end, or end to start, but
 /// either way ultimately yields the same data.
 pub(super) struct OffsetRipIter<'a> {
@@ -36,20 +42,31 @@ pub(super) struct OffsetRipIter<'a> {
 
 	/// # Writer.
 	write: EitherChunksIter<'a>,
+
+	/// # Todo Flags.
+	todo: EitherFlagsIter<'a>,
+
+	/// # Remaining Todos.
+	remaining: usize,
 }
 
 impl<'a> OffsetRipIter<'a> {
 	/// # New.
 	///
-	/// Start up a new iterator given the range, slice, and direction.
+	/// Start up a new iterator given the range, slice, todo flags, and
+	/// direction.
 	///
 	/// ## Errors.
 	///
 	/// This method contains a lot of pseudo-assertions that will trigger an
 	/// error if there's a bug, but since that shouldn't ever happen, it should
 	/// be fine. ;)
-	pub(super) fn new(lsn: Range<i32>, slice: &'a mut[RipSample], backwards: bool)
-	-> Result<Self, RipRipError> {
+	pub(super) fn new(
+		lsn: Range<i32>,
+		slice: &'a mut[RipSample],
+		todo: &'a mut[bool],
+		backwards: bool,
+	) -> Result<Self, RipRipError> {
 		// Get the read part going first.
 		let read = EitherRangeIter::new(lsn, backwards);
 
@@ -59,24 +76,45 @@ impl<'a> OffsetRipIter<'a> {
 		}
 		let write = EitherChunksIter::new(slice, backwards);
 
+		// There should be one flag per sector.
+		if todo.len() != read.len() {
+			return Err(RipRipError::Bug("OffsetRipIter lsn and todo have different lengths!"));
+		}
+		let remaining = todo.iter().filter(|v| **v).count();
+		let todo = EitherFlagsIter::new(todo, backwards);
+
 		// We're good if the lengths match.
 		if write.len() == read.len() {
-			Ok(Self { read, write })
+			Ok(Self { read, write, todo, remaining })
 		}
 		// Otherwise it's a bug.
 		else {
 			Err(RipRipError::Bug("OffsetRipIter lsn and slice have different lengths!"))
 		}
 	}
+
+	/// # Skipped.
+	///
+	/// Return the number of sectors that will be skipped over because there
+	/// is nothing left to do for them.
+	pub(super) fn skipped(&self) -> usize { self.read.len() - self.remaining }
 }
 
 impl<'a> Iterator for OffsetRipIter<'a> {
-	type Item = (i32, &'a mut[RipSample]);
+	type Item = (i32, &'a mut[RipSample], &'a mut bool);
 
 	fn next(&mut self) -> Option<Self::Item> {
-		let a = self.read.next()?;
-		let b = self.write.next()?;
-		Some((a, b))
+		while self.remaining != 0 {
+			let a = self.read.next()?;
+			let b = self.write.next()?;
+			let c = self.todo.next()?;
+			if *c {
+				self.remaining -= 1;
+				return Some((a, b, c));
+			}
+		}
+
+		None
 	}
 
 	fn size_hint(&self) -> (usize, Option<usize>) {
@@ -86,7 +124,7 @@ impl<'a> Iterator for OffsetRipIter<'a> {
 }
 
 impl ExactSizeIterator for OffsetRipIter<'_> {
-	fn len(&self) -> usize { self.read.len() }
+	fn len(&self) -> usize { self.remaining }
 }
 
 
@@ -139,6 +177,54 @@ impl ExactSizeIterator for EitherChunksIter<'_> {
 
 
 
+#[derive(Debug)]
+/// # Forwards/Backwards Flags.
+///
+/// This enum exists solely to allow us to hold either a forwards or backwards
+/// mutable flag iterator under a single variable type. All iterator business
+/// is passed straight on through.
+enum EitherFlagsIter<'a> {
+	/// # Forward Iter.
+	Forward(IterMut<'a, bool>),
+
+	/// # Backward Iter.
+	Backward(Rev<IterMut<'a, bool>>),
+}
+
+impl<'a> EitherFlagsIter<'a> {
+	/// # New Instance.
+	fn new(raw: &'a mut[bool], backwards: bool) -> Self {
+		if backwards { Self::Backward(raw.iter_mut().rev()) }
+		else { Self::Forward(raw.iter_mut()) }
+	}
+}
+
+impl<'a> Iterator for EitherFlagsIter<'a> {
+	type Item = &'a mut bool;
+	fn next(&mut self) -> Option<Self::Item> {
+		match self {
+			Self::Forward(i) => i.next(),
+			Self::Backward(i) => i.next(),
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.len();
+		(len, Some(len))
+	}
+}
+
+impl ExactSizeIterator for EitherFlagsIter<'_> {
+	fn len(&self) -> usize {
+		match self {
+			Self::Forward(i) => i.len(),
+			Self::Backward(i) => i.len(),
+		}
+	}
+}
+
+
+
 #[derive(Debug, Clone)]
 /// # Forwards/Backwards Range.
 ///
@@ -200,4 +286,30 @@ mod test {
 		b.reverse();
 		assert_eq!(a, b, "Sets should match after reversing one of them!");
 	}
+
+	#[test]
+	fn t_offset_rip_iter_todo() {
+		let mut data = vec![RipSample::Tbd; usize::from(SAMPLES_PER_SECTOR) * 4];
+		let mut todo = [true, false, true, false];
+
+		for backwards in [false, true] {
+			let iter = OffsetRipIter::new(5..9, &mut data, &mut todo, backwards)
+				.expect("OffsetRipIter failed.");
+			assert_eq!(iter.skipped(), 2, "Expected two skipped sectors.");
+			assert_eq!(iter.len(), 2, "Expected two pending sectors.");
+
+			let mut lsns: Vec<i32> = iter.map(|(lsn, _, _)| lsn).collect();
+			if backwards { lsns.reverse(); }
+			assert_eq!(lsns, [5, 7], "Only the todo sectors should be yielded.");
+		}
+
+		// Clear a flag through the iterator and make sure it sticks.
+		for (lsn, _, todo) in OffsetRipIter::new(5..9, &mut data, &mut todo, false).expect("OffsetRipIter failed.") {
+			if lsn == 7 { *todo = false; }
+		}
+		assert_eq!(todo, [true, false, false, false]);
+
+		// Mismatched lengths are a bug.
+		assert!(OffsetRipIter::new(5..8, &mut data, &mut todo, false).is_err());
+	}
 }
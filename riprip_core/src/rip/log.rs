@@ -2,27 +2,62 @@
 # Rip Rip Hooray: Log
 */
 
-use cdtoc::Track;
-use crate::RipRipError;
+use cdtoc::{
+	Toc,
+	Track,
+};
+use crate::{
+	log_path,
+	RipRipError,
+};
 use dactyl::NiceElapsed;
 use std::{
+	collections::BTreeSet,
 	fmt,
-	io::Write,
+	fs::File,
+	io::{
+		BufWriter,
+		Write,
+	},
 	num::NonZeroU8,
-	time::Instant,
+	time::{
+		Duration,
+		Instant,
+	},
 };
+use super::quality::TrackHeatmap;
 use utc2k::FmtUtc2k;
 
 
 
+/// # Read Latency Buckets (Upper Bounds, Milliseconds).
+///
+/// Reads at or beyond the last bound land in one final catch-all bucket.
+const READ_BUCKETS: [u64; 8] = [5, 10, 25, 50, 100, 250, 500, 1000];
+
+/// # Slowest Reads to Keep.
+const READ_SLOWEST: usize = 10;
+
+
+
 /// # Super Basic Log.
 ///
 /// This holds the log-worthy details from an individual pass, printing the
-/// records out — to STDOUT — en masse at the end of the run.
+/// records out — to STDOUT and/or per-track sidecar files — en masse at the
+/// end of the run.
 ///
 /// Aside from helping to ensure consistent formatting, this also keeps the
 /// ordering consistent.
-pub(super) struct RipLog {
+pub(super) struct RipLog<'a> {
+	/// # Print to STDOUT?
+	stdout: bool,
+
+	/// # Per-Track Files.
+	///
+	/// When set, each track's heatmap and sectors are also appended to its
+	/// own sidecar in the scratch directory.
+	files: Option<&'a Toc>,
+
 	/// # Pass Number, Timestamp.
 	pass: Option<(NonZeroU8, Instant)>,
 
@@ -33,22 +68,43 @@ pub(super) struct RipLog {
 	///
 	/// This holds each track number, LSN, sample count, and status.
 	sectors: Vec<(u8, i32, u16, RipLogSampleKind)>,
+
+	/// # Heatmaps.
+	///
+	/// This holds each track number and its end-of-pass status heatmap.
+	heatmaps: Vec<(u8, TrackHeatmap)>,
+
+	/// # Read Latency Histogram.
+	///
+	/// Sector read counts by duration, per `READ_BUCKETS` (plus overflow).
+	latency: [u32; READ_BUCKETS.len() + 1],
+
+	/// # Slowest Reads.
+	///
+	/// This holds the track number, LSN, and duration of the slowest reads,
+	/// slowest first.
+	slowest: Vec<(u8, i32, Duration)>,
 }
 
-impl Drop for RipLog {
+impl Drop for RipLog<'_> {
 	/// # Final Print (Maybe).
 	///
 	/// This will print any remaining log data before retiring.
 	fn drop(&mut self) { self.flush(); }
 }
 
-impl RipLog {
+impl<'a> RipLog<'a> {
 	/// # New Instance.
-	pub(super) const fn new() -> Self {
+	pub(super) const fn new(stdout: bool, files: Option<&'a Toc>) -> Self {
 		Self {
+			stdout,
+			files,
 			pass: None,
 			events: Vec::new(),
 			sectors: Vec::new(),
+			heatmaps: Vec::new(),
+			latency: [0; READ_BUCKETS.len() + 1],
+			slowest: Vec::new(),
 		}
 	}
 
@@ -62,6 +118,9 @@ impl RipLog {
 		// Unnecessary but unhurtful.
 		self.events.truncate(0);
 		self.sectors.truncate(0);
+		self.heatmaps.truncate(0);
+		self.latency.fill(0);
+		self.slowest.truncate(0);
 
 		let next = self.pass.map_or(NonZeroU8::MIN, |(p, _)| p.saturating_add(1));
 		self.pass.replace((next, Instant::now()));
@@ -81,6 +140,27 @@ impl RipLog {
 		self.events.push((RipLogEventKind::Err((lsn, err)), FmtUtc2k::now()));
 	}
 
+	/// # Add Read Time.
+	///
+	/// Record how long the read of `lsn` took. Slow reads often precede C2
+	/// errors, so the distribution — and the worst offenders — can help tell
+	/// a localized scratch apart from general rot.
+	///
+	/// This is only used for STDOUT logging.
+	pub(super) fn add_read(&mut self, track: Track, lsn: i32, time: Duration) {
+		if ! self.stdout { return; }
+
+		let ms = u64::try_from(time.as_millis()).unwrap_or(u64::MAX);
+		let idx = READ_BUCKETS.iter().position(|&b| ms < b).unwrap_or(READ_BUCKETS.len());
+		self.latency[idx] += 1;
+
+		let pos = self.slowest.partition_point(|(_, _, t)| time <= *t);
+		if pos < READ_SLOWEST {
+			self.slowest.insert(pos, (track.number(), lsn, time));
+			self.slowest.truncate(READ_SLOWEST);
+		}
+	}
+
 	/// # Add Bad Sample Count.
 	///
 	/// Record the number of bad samples (`total`) associated with `lsn`.
@@ -108,53 +188,172 @@ impl RipLog {
 		));
 	}
 
+	/// # Add Heatmap.
+	///
+	/// Record the status heatmap for a track at the end of its pass.
+	pub(super) fn add_heatmap(&mut self, track: Track, map: TrackHeatmap) {
+		self.heatmaps.push((track.number(), map));
+	}
+
 	/// # Flush.
 	///
-	/// Print the held data, if any, to STDOUT, and drain it so a new pass can
-	/// start fresh.
+	/// Print the held data, if any, to STDOUT and/or the per-track files, and
+	/// drain it so a new pass can start fresh.
+	fn flush(&mut self) {
+		let Some((pass, start)) = self.pass.take() else { return; };
+		let elapsed = NiceElapsed::from(start);
+		self.sectors.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+
+		if let Some(toc) = self.files { self.flush_files(toc, pass, &elapsed); }
+		if self.stdout { self.flush_stdout(pass, &elapsed); }
+
+		self.events.truncate(0);
+		self.sectors.truncate(0);
+		self.heatmaps.truncate(0);
+		self.latency.fill(0);
+		self.slowest.truncate(0);
+	}
+
+	/// # Flush (Files).
+	///
+	/// Append each track's heatmap and problematic sectors to its own log file
+	/// in the scratch directory. Events aren't specific to any one track, so
+	/// are left out.
+	///
+	/// As with STDOUT, write errors are silently ignored.
+	fn flush_files(&self, toc: &Toc, pass: NonZeroU8, elapsed: &NiceElapsed) {
+		let tracks: BTreeSet<u8> = self.heatmaps.iter().map(|(t, _)| *t)
+			.chain(self.sectors.iter().map(|(t, _, _, _)| *t))
+			.collect();
+
+		for track in tracks {
+			let Ok(dst) = log_path(toc, track) else { continue; };
+			if let Some(parent) = dst.parent() {
+				if ! parent.is_dir() && std::fs::create_dir_all(parent).is_err() { continue; }
+			}
+			let Ok(file) = File::options().create(true).append(true).open(&dst) else { continue; };
+			let mut handle = BufWriter::new(file);
+
+			let sectors = self.sectors.iter().filter(|(t, _, _, _)| *t == track);
+			let _res = writeln!(
+				&mut handle,
+				"##
+## Pass {pass}: {elapsed}
+## Problematic Sectors: {}
+## Problematic Samples: {}
+##",
+				sectors.clone().count(),
+				sectors.clone().fold(0_usize, |acc, (_, _, v, _)| acc + usize::from(*v)),
+			);
+
+			// Heatmap.
+			for (_, map) in self.heatmaps.iter().filter(|(t, _)| *t == track) {
+				let _res = writeln!(
+					&mut handle,
+					"## Track {track:02}: {map:#}\n## Key:      {}\n##",
+					TrackHeatmap::LEGEND,
+				);
+			}
+
+			// Sample issues.
+			for (_, lsn, samples, kind) in sectors {
+				let _res = writeln!(&mut handle, "{track:02}  {lsn:06}  {samples:03}  {}", kind.as_str());
+			}
+
+			// Write it!
+			let _res = handle.flush();
+		}
+	}
+
+	/// # Flush (STDOUT).
+	///
+	/// Print everything to STDOUT.
 	///
 	/// This uses a locked writer so content should appear in the correct
 	/// order, but one never knows with terminals…
-	fn flush(&mut self) {
+	fn flush_stdout(&self, pass: NonZeroU8, elapsed: &NiceElapsed) {
 		// Header.
-		let Some((pass, start)) = self.pass.take() else { return; };
 		let writer = std::io::stdout();
 		let mut handle = writer.lock();
 		let _res = writeln!(
 			&mut handle,
 			"##
-## Pass {pass}: {}
+## Pass {pass}: {elapsed}
 ## Problematic Sectors: {}
 ## Problematic Samples: {}
 ##",
-			NiceElapsed::from(start),
 			self.sectors.len(),
 			self.sectors.iter().fold(0_usize, |acc, (_, _, v, _)| acc + usize::from(*v))
 		);
 
 		// Miscellaneous events.
 		if ! self.events.is_empty() {
-			for (event, time) in self.events.drain(..) {
+			for (event, time) in &self.events {
 				let _res = writeln!(&mut handle, "## [{time}] {event}");
 			}
 			let _res =writeln!(&mut handle, "##");
 		}
 
-		// Sample issues.
-		if ! self.sectors.is_empty() {
-			self.sectors.sort_unstable_by(|a, b| a.1.cmp(&b.1));
-			for (track, lsn, samples, kind) in self.sectors.drain(..) {
-				let _res = writeln!(
-					&mut handle,
-					"{track:02}  {lsn:06}  {samples:03}  {}",
-					kind.as_str(),
-				);
+		// Track heatmaps.
+		if ! self.heatmaps.is_empty() {
+			for (track, map) in &self.heatmaps {
+				let _res = writeln!(&mut handle, "## Track {track:02}: {map}");
 			}
+			let _res = writeln!(&mut handle, "## Key:      {}\n##", TrackHeatmap::LEGEND);
+		}
+
+		// Read latency.
+		self.flush_stdout_latency(&mut handle);
+
+		// Sample issues.
+		for (track, lsn, samples, kind) in &self.sectors {
+			let _res = writeln!(
+				&mut handle,
+				"{track:02}  {lsn:06}  {samples:03}  {}",
+				kind.as_str(),
+			);
 		}
 
 		// Write it!
 		let _res = handle.flush();
 	}
+
+	/// # Flush Read Latency (STDOUT).
+	///
+	/// Print the read latency histogram and the slowest reads — noting any
+	/// that ended up with problematic samples — if there were any reads.
+	fn flush_stdout_latency<W: Write>(&self, handle: &mut W) {
+		let peak = self.latency.iter().copied().max().unwrap_or(0);
+		if peak == 0 { return; }
+
+		let _res = writeln!(handle, "## Read Latency:");
+		for (idx, count) in self.latency.iter().copied().enumerate() {
+			let label = READ_BUCKETS.get(idx).map_or_else(
+				|| format!(">={}ms", READ_BUCKETS[READ_BUCKETS.len() - 1]),
+				|b| format!("<{b}ms"),
+			);
+			let bar = (u64::from(count) * 40).div_ceil(u64::from(peak));
+			let _res = writeln!(
+				handle,
+				"## {label:>8}  {count:>7}  {}",
+				"#".repeat(usize::try_from(bar).unwrap_or(40)),
+			);
+		}
+		let _res = writeln!(handle, "##");
+
+		let _res = writeln!(handle, "## Slowest Reads:");
+		for (track, lsn, time) in &self.slowest {
+			let kind = self.sectors.iter()
+				.find(|(t, l, _, _)| t == track && l == lsn)
+				.map_or("", |(_, _, _, k)| k.as_str());
+			let _res = writeln!(
+				handle,
+				"## {track:02}  {lsn:06}  {:>7.3}s  {kind}",
+				time.as_secs_f64(),
+			);
+		}
+		let _res = writeln!(handle, "##");
+	}
 }
 
 
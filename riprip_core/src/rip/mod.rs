@@ -4,11 +4,17 @@
 
 pub(super) mod buf;
 pub(super) mod data;
+mod dump;
 mod iter;
 mod log;
 pub(super) mod opts;
+pub(super) mod probe;
 mod quality;
+pub(super) mod result;
 pub(super) mod sample;
+pub(super) mod sectors;
+mod watch;
+pub(super) mod whole;
 
 
 use cdtoc::{
@@ -16,6 +22,7 @@ use cdtoc::{
 	Track,
 };
 use crate::{
+	accessible_range,
 	chk_accuraterip,
 	chk_ctdb,
 	COLOR_BAD,
@@ -23,18 +30,22 @@ use crate::{
 	COLOR_LIKELY,
 	COLOR_MAYBE,
 	Disc,
+	HtoaMode,
 	KillSwitch,
 	LibcdioInstance,
+	ReadOffset,
 	RipBuffer,
 	RipOptions,
 	RipRipError,
+	RipSample,
 	RipState,
-	SavedRips,
+	SAMPLES_PER_SECTOR,
 	SECTOR_OVERREAD,
 	state_path,
 };
 use dactyl::{
 	NiceElapsed,
+	NiceFloat,
 	NiceU32,
 	NiceU8,
 	traits::NiceInflection,
@@ -43,19 +54,64 @@ use fyi_msg::{
 	Msg,
 	Progless,
 };
+use data::{
+	sector_todo,
+	StateSummary,
+};
+use dump::C2Dump;
 use iter::OffsetRipIter;
 use log::RipLog;
-use quality::TrackQuality;
+use quality::{
+	TrackHeatmap,
+	TrackQuality,
+};
+use result::RipTrackResult;
+use watch::ReadWatch;
 use std::{
 	borrow::Cow,
 	collections::BTreeMap,
 	num::NonZeroU32,
-	path::PathBuf,
+	path::{
+		Path,
+		PathBuf,
+	},
 	time::Instant,
 };
 
 
 
+/// # Stable C2 Passes.
+///
+/// The number of consecutive passes a sector must return identical C2 errors
+/// before it is considered stable.
+const C2_STABLE_PASSES: u8 = 3;
+
+/// # Stable C2 Run.
+///
+/// The minimum number of consecutive stable sectors needed to suspect
+/// intentional corruption (copy protection) rather than damage.
+const C2_STABLE_RUN: usize = 10;
+
+/// # Missed C2 Threshold.
+///
+/// The number of samples that have to turn contentious on C2-clean reads —
+/// with C2 never once reporting an error — before the drive's C2 support is
+/// called into question. This is one sector's worth.
+const C2_MISSED_MIN: u32 = 588;
+
+/// # Stuck Passes.
+///
+/// The number of consecutive passes a sector's samples must come back exactly
+/// the same — no new values, no new counts — before it is considered stuck
+/// and left alone for the rest of the rip.
+const STUCK_PASSES: u8 = 5;
+
+/// # Verification Results.
+///
+/// The AccurateRip and CUETools confidences, the CUETools match shift (if
+/// non-zero), and the indices of the AccurateRip entries matched.
+type Verification = (Option<(u8, u8)>, Option<u16>, Option<i16>, Vec<usize>);
+
 /// # Sassy Setup Messages.
 const STANDBY: [&str; 2] = [
 	"Reconnoitering the rip…",
@@ -78,9 +134,21 @@ pub(crate) struct Ripper<'a> {
 	/// # Options.
 	opts: RipOptions,
 
+	/// # Read Offset (First Drive).
+	///
+	/// The `opts` offset gets swapped out when alternating between drives, so
+	/// we need to remember the original.
+	offset: ReadOffset,
+
 	/// # Track Details.
 	tracks: BTreeMap<u8, RipEntry>,
 
+	/// # Skipped (Short) Tracks.
+	///
+	/// The tracks dropped for being shorter than `RipOptions::min_sectors`,
+	/// so the rip can mention them.
+	short: Vec<u8>,
+
 	/// # Total Sectors (across all passes, plus one)
 	total: u32,
 }
@@ -99,10 +167,21 @@ impl<'a> Ripper<'a> {
 		// existing entries. We'll also be printing a temporary message since
 		// it might take a while.
 		let toc = disc.toc();
+		let opts = &htoa_tracks(toc, *opts);
 		let padding = u32::from(SECTOR_OVERREAD) * 2 - u32::from(opts.offset().sectors_abs());
-		let tracks = opts.tracks()
+		let mut tracks = opts.tracks()
 			.map(|idx| RipEntry::new(toc, idx, padding).map(|e| (idx, e)))
 			.collect::<Result<BTreeMap<u8, RipEntry>, RipRipError>>()?;
+
+		// Drop any tracks that are too short to bother with.
+		let mut short = Vec::new();
+		tracks.retain(|&idx, e| {
+			if track_too_short(e.track, opts) {
+				short.push(idx);
+				false
+			}
+			else { true }
+		});
 		if tracks.is_empty() { return Err(RipRipError::Noop); }
 
 		// Last but not least, add up all the sectors to give us a total for
@@ -110,7 +189,7 @@ impl<'a> Ripper<'a> {
 		// title changes after the last read operation.)
 		let total = tracks.values()
 			.try_fold(0_u32, |acc, e| acc.checked_add(e.sectors))
-			.and_then(|n| n.checked_mul(u32::from(opts.passes())))
+			.and_then(|n| n.checked_mul(u32::from(opts.passes()) + u32::from(opts.paranoid())))
 			.and_then(|n| n.checked_add(1 + tracks.len() as u32))
 			.ok_or(RipRipError::RipOverflow)?;
 
@@ -118,7 +197,9 @@ impl<'a> Ripper<'a> {
 			now: Instant::now(),
 			disc,
 			opts: *opts,
+			offset: opts.offset(),
 			tracks,
+			short,
 			total,
 		})
 	}
@@ -151,18 +232,17 @@ impl<'a> Ripper<'a> {
 	/// and be returned.
 	pub(crate) fn rip(&mut self, progress: &Progless, killed: &KillSwitch)
 	-> Result<(), RipRipError> {
-		// We should definitely have a first track, but if for some reason we
-		// don't there's nothing more to do!
-		let Some(first_track) = self.tracks.values().map(|t| t.track).next() else {
-			return Ok(());
-		};
-
-		// Load a bunch of other stuff!
+		// Load a bunch of stuff! If there's no (loadable) first track,
+		// there's nothing more to do.
 		let toc = self.disc.toc();
 		let _res = progress.reset(self.total);
 		progress.set_title(Some(Msg::custom("Initializing", 199, standby_msg())));
-		let mut state = RipState::new(toc, first_track, &self.opts)?;
-		let mut share = RipShare::new(self.disc, progress, killed);
+		self.warn_short(progress);
+		let Some(mut state) = self.first_state(progress)? else {
+			progress.finish();
+			return Ok(());
+		};
+		let mut share = RipShare::new(self.disc, &self.opts, progress, killed);
 
 		// Before we run through the passes, let's set up the initial quality,
 		// etc. But only if we're resuming.
@@ -171,17 +251,19 @@ impl<'a> Ripper<'a> {
 			for entry in self.tracks.values_mut() {
 				if
 					! killed.killed() &&
-					state_path(toc, entry.track).is_ok_and(|s| s.is_file())
+					! entry.corrupt &&
+					self.opts.resume_track(entry.track.number()) &&
+					state_path(toc, entry.track).is_ok_and(|s| s.is_file()) &&
+					replace_entry_state(&mut state, entry, &self.opts, progress, 1)? &&
+					entry.preverify(&mut state, &self.opts)? &&
+					! self.opts.forced_pass(1)
 				{
-					state.replace(entry.track, &self.opts)?;
-					if entry.preverify(&state, &self.opts)? {
-						let _res = share.progress.push_msg(happy_track_msg(entry.track));
-						progress.increment_n(entry.sectors * u32::from(self.opts.passes()));
-					}
+					let _res = share.progress.push_msg(happy_track_msg(entry.track));
+					progress.increment_n(entry.sectors * u32::from(self.opts.passes()));
 				}
 
 				// Note the first non-skippable track so we can reset afterward.
-				if first_track.is_none() && ! entry.skippable() {
+				if first_track.is_none() && ! entry.skippable_on(1, &self.opts) {
 					first_track.replace(entry.track);
 				}
 
@@ -195,10 +277,10 @@ impl<'a> Ripper<'a> {
 			// Reset the loaded track to the first one we'll actually be
 			// ripping.
 			if let Some(first_track) = first_track {
-				state.replace(first_track, &self.opts)?;
+				replace_state(&mut state, first_track, &self.opts, progress)?;
 			}
-			// Nothing to do!
-			else {
+			// Nothing to do! (Unless we're being paranoid.)
+			else if ! self.opts.paranoid() {
 				progress.finish();
 				return Ok(());
 			}
@@ -206,55 +288,96 @@ impl<'a> Ripper<'a> {
 		// Otherwise we can skip this step.
 		else { progress.increment_n(self.tracks.len() as u32); }
 
+		// Tally the starting quality for the big-picture progress.
+		share.set_overall(self.tracks.values().map(|e| &e.quality.1));
+
 		// Loop each pass!
 		for pass in 1..=self.opts.passes() {
 			// Fire up the log if we're logging.
-			if self.opts.verbose() { share.log.bump_pass(); }
+			if self.opts.logging() { share.log.bump_pass(); }
 
 			// Bump the pass in our shared data. We can skip the initial cache
 			// bust if this entry is brand new, and we aren't no-resuming or
 			// anything like that.
 			share.bump_pass(&self.opts);
-			if pass == 1 && state.is_new() && self.opts.resume() {
+			if pass == 1 && state.is_new() && self.opts.resume_track(state.track().number()) {
 				share.force_bust = false;
 			}
 
+			// Alternate between drives, if we have two.
+			self.switch_drive(&mut share, &mut state, pass % 2 == 0)?;
+
 			// Loop each track!
 			for entry in self.tracks.values_mut() {
 				// Skip the work if we aborted or already confirmed the track
-				// is complete.
-				if entry.skippable() { continue; }
+				// is complete (and the minimum passes are behind us).
+				if entry.skippable_on(pass, &self.opts) { continue; }
 				if killed.killed() {
 					progress.increment_n(entry.sectors);
 					continue;
 				}
 
-				// Switch states if needed.
+				// Make sure the disc is still the disc, then switch states
+				// if needed.
+				self.disc.check_toc()?;
 				if state.track() != entry.track {
 					set_progress_title(progress, entry.track.number(), "Initializing…");
-					state.replace(entry.track, &self.opts)?;
+					if ! replace_entry_state(&mut state, entry, &self.opts, progress, pass)? { continue; }
 				}
 
 				// Rip it! If the result comes back confirmed and we were
 				// planning additional passes, we can increase the progress
 				// (remove them from the todo) accordingly.
-				if entry.rip(&mut share, &mut state, &self.opts)? {
+				let old = entry.quality.1;
+				let confirmed = entry.rip(&mut share, &mut state, &self.opts)?;
+				share.update_overall(old, entry.quality.1);
+				if confirmed && ! self.opts.forced_pass(pass + 1) {
 					let skip = u32::from(self.opts.passes() - pass) * entry.sectors;
 					if skip != 0 { progress.increment_n(skip); }
-					let _res = share.progress.push_msg(happy_track_msg(entry.track));
+					if ! entry.silent {
+						let _res = share.progress.push_msg(happy_track_msg(entry.track));
+					}
 				}
 			}
 
+			// Call out drives whose C2 seems to be all talk.
+			share.warn_fake_c2(&self.opts);
+
+			// If we're automating the passes and nothing changed this time
+			// around (or everything is confirmed), there's no point
+			// continuing.
+			if
+				self.opts.auto_passes() &&
+				pass < self.opts.passes() &&
+				! self.opts.forced_pass(pass + 1) &&
+				! killed.killed() &&
+				(! share.pass_changed || self.tracks.values().all(RipEntry::skippable))
+			{
+				self.stop_early(&mut share, &mut state, pass)?;
+				break;
+			}
+
 			// Flip the read order for next time?
 			if self.opts.flip_flop() {
 				self.opts = self.opts.with_backwards(! self.opts.backwards());
 			}
 			// After the first pass, always resume, never reset.
-			if pass == 1 {
-				self.opts = self.opts.with_resume(true);
-			}
+			if pass == 1 { self.opts = self.opts.with_resume(true); }
 		}
 
+		// Back to the first drive for anything else.
+		self.switch_drive(&mut share, &mut state, false)?;
+
+		// One last look for the paranoid.
+		if self.opts.paranoid() { self.paranoid(&mut share, &mut state)?; }
+
+		// Bring a merged HTOA up to date.
+		self.remerge_htoa(&mut share, &mut state)?;
+
+		// Point out any suspiciously stable errors.
+		self.warn_stable_c2(progress);
+		warn_sync(&share.buf, progress);
+
 		progress.finish();
 
 		// Add some line breaks if we printed any confirmation messages.
@@ -263,31 +386,196 @@ impl<'a> Ripper<'a> {
 		Ok(())
 	}
 
+	/// # First State.
+	///
+	/// Load the state for the first track, or with `RipOptions::skip_corrupt`,
+	/// the first track whose state _can_ be loaded. Returns `None` if there
+	/// isn't one.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered while loading the state.
+	fn first_state(&mut self, progress: &Progless) -> Result<Option<RipState>, RipRipError> {
+		let toc = self.disc.toc();
+		for entry in self.tracks.values_mut() {
+			match RipState::new(toc, entry.track, &self.opts) {
+				Ok(mut state) => {
+					warn_recovered(&mut state, progress);
+					return Ok(Some(state));
+				},
+				Err(RipRipError::StateCorrupt(_)) if self.opts.skip_corrupt() => {
+					entry.mark_corrupt(&self.opts, progress, 1);
+				},
+				Err(e) => return Err(e),
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// # Re-Merge HTOA.
+	///
+	/// With `HtoaMode::Merge`, the first track's export includes the HTOA as
+	/// it stood at the time, but the HTOA may well have been ripped some more
+	/// since, e.g. if the first track was confirmed early on. This re-exports
+	/// the first track — and its lossy copy, if any — one last time to catch
+	/// up.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered while loading the state or
+	/// saving the files.
+	fn remerge_htoa(&mut self, share: &mut RipShare, state: &mut RipState)
+	-> Result<(), RipRipError> {
+		if
+			share.killed.killed() ||
+			! matches!(self.opts.htoa_mode(), HtoaMode::Merge) ||
+			! self.tracks.contains_key(&0)
+		{
+			return Ok(());
+		}
+
+		let Some(entry) = self.tracks.get_mut(&1) else { return Ok(()); };
+		if entry.corrupt || entry.dst.is_none() { return Ok(()); }
+
+		set_progress_title(share.progress, entry.track.number(), "Merging the HTOA…");
+		if state.track() != entry.track {
+			replace_state(state, entry.track, &self.opts, share.progress)?;
+		}
+		entry.dst.replace(state.save_track(&self.opts, entry.quality.1, (entry.ar, entry.ctdb))?);
+		if self.opts.lossy() { entry.save_lossy(share, state, &self.opts)?; }
+
+		Ok(())
+	}
+
+	/// # Paranoid Re-Read.
+	///
+	/// Run one final (paranoid) pass over every non-corrupt track, confirmed
+	/// or not.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered while loading the states or
+	/// reading the disc.
+	fn paranoid(&mut self, share: &mut RipShare, state: &mut RipState)
+	-> Result<(), RipRipError> {
+		if self.opts.retry_shitlisted() { LibcdioInstance::clear_shitlist(); }
+		for entry in self.tracks.values_mut() {
+			if entry.corrupt { continue; }
+			if share.killed.killed() {
+				share.progress.increment_n(entry.sectors);
+				continue;
+			}
+
+			self.disc.check_toc()?;
+			if state.track() != entry.track {
+				set_progress_title(share.progress, entry.track.number(), "Initializing…");
+				let pass = self.opts.passes() + 1;
+				if ! replace_entry_state(state, entry, &self.opts, share.progress, pass)? { continue; }
+			}
+
+			let old = entry.quality.1;
+			entry.paranoid(share, state, &self.opts)?;
+			share.update_overall(old, entry.quality.1);
+		}
+
+		Ok(())
+	}
+
+	/// # Switch Drive.
+	///
+	/// When there's a second drive, point the reads — and read offset — at it
+	/// (or back at the first), rebuilding the loaded state's todos to match
+	/// the new offset. Samples are stored offset-corrected, so the data from
+	/// both drives simply piles up together, but the C2 and unchanged streaks
+	/// are tracked per drive.
+	///
+	/// ## Errors
+	///
+	/// This will return an error if there's a bug in the programming, but that
+	/// shouldn't happen. ;)
+	fn switch_drive<'b>(&mut self, share: &mut RipShare<'b>, state: &mut RipState, second: bool)
+	-> Result<(), RipRipError> where 'a: 'b {
+		let disc = self.disc;
+		let Some(other) = disc.second_cdio() else { return Ok(()); };
+		let (cdio, offset) =
+			if second { other }
+			else { (disc.cdio(), self.offset) };
+		if std::ptr::eq(share.cdio, cdio) { return Ok(()); }
+
+		// The new drive's cache may still be holding onto its last pass.
+		share.cdio = cdio;
+		share.drive = usize::from(second);
+		share.force_bust = self.opts.cache_sectors() != 0;
+		self.opts = self.opts.with_offset(offset);
+		state.init_todo(&self.opts)
+	}
+
+	/// # Stop Early.
+	///
+	/// When automatic passes come to an early end, this accounts for the
+	/// skipped passes in the progress bar, and takes care of the lossy copies
+	/// that would otherwise have been made on the final pass.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered while loading the states.
+	fn stop_early(&self, share: &mut RipShare, state: &mut RipState, pass: u8)
+	-> Result<(), RipRipError> {
+		let left = u32::from(self.opts.passes() - pass);
+		for entry in self.tracks.values() {
+			if ! entry.skippable() { share.progress.increment_n(entry.sectors * left); }
+		}
+
+		// This is the final pass now!
+		share.pass = self.opts.passes();
+		if self.opts.lossy() {
+			for entry in self.tracks.values() {
+				if entry.skippable() { continue; }
+				if state.track() != entry.track { replace_state(state, entry.track, &self.opts, share.progress)?; }
+				entry.save_lossy(share, state, &self.opts)?;
+			}
+		}
+
+		Ok(())
+	}
+
 	#[expect(clippy::cast_possible_truncation, reason = "False positive.")]
 	/// # Status.
 	///
-	/// Check the status of each track and nothing else.
+	/// Check the status of each track and nothing else. This is read-only;
+	/// nothing is exported or saved.
 	pub(crate) fn status(&mut self, progress: &Progless, killed: &KillSwitch)
 	-> Result<(), RipRipError> {
 		// We should definitely have a first track, but if for some reason we
 		// don't there's nothing more to do!
-		let Some(first_track) = self.tracks.values().map(|t| t.track).next() else {
-			return Err(RipRipError::FirstTrackNum);
-		};
+		if self.tracks.is_empty() { return Err(RipRipError::FirstTrackNum); }
 
 		// Load a bunch of other stuff!
 		let toc = self.disc.toc();
 		let _res = progress.reset(self.tracks.len() as u32);
 		progress.set_title(Some(Msg::custom("Analyzing", 199, standby_msg())));
-		let mut state = RipState::new(toc, first_track, &self.opts)?;
+		let mut state: Option<RipState> = None;
 
-		// Take a look!
+		// Take a look! The header summaries are good enough if they're
+		// current; otherwise the full state has to be loaded.
 		for entry in self.tracks.values_mut() {
 			if killed.killed() { return Err(RipRipError::Killed); }
 
-			if state_path(toc, entry.track).is_ok_and(|s| s.is_file()) {
-				state.replace(entry.track, &self.opts)?;
-				entry.preverify(&state, &self.opts)?;
+			if let Some(summary) = StateSummary::load(toc, entry.track)
+				.filter(|s| s.rereads() == self.opts.rereads())
+			{
+				entry.presummarize(summary, &self.opts);
+			}
+			else if state_path(toc, entry.track).is_ok_and(|s| s.is_file()) {
+				let state = match state.as_mut() {
+					Some(s) => {
+						replace_state(s, entry.track, &self.opts, progress)?;
+						s
+					},
+					None => state.insert(RipState::new(toc, entry.track, &self.opts)?),
+				};
+				entry.precheck(state, &self.opts);
 			}
 
 			progress.increment();
@@ -297,6 +585,47 @@ impl<'a> Ripper<'a> {
 
 		Ok(())
 	}
+
+	#[expect(clippy::cast_possible_truncation, reason = "False positive.")]
+	/// # Merge.
+	///
+	/// Fold the state data from another rip's scratch directory — e.g. one
+	/// made with a different drive — into each track's state, returning the
+	/// number of tracks that changed.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered while loading, merging, or
+	/// saving the states.
+	pub(crate) fn merge(&self, src: &Path, progress: &Progless, killed: &KillSwitch)
+	-> Result<usize, RipRipError> {
+		let Some(first_track) = self.tracks.values().map(|t| t.track).next() else {
+			return Err(RipRipError::Noop);
+		};
+
+		// Our own state needs to be loaded, not replaced.
+		let opts = self.opts.with_resume(true).with_reset(false);
+
+		let toc = self.disc.toc();
+		let _res = progress.reset(self.tracks.len() as u32);
+		progress.set_title(Some(Msg::custom("Merging", 199, standby_msg())));
+		let mut state = RipState::new(toc, first_track, &opts)?;
+		warn_recovered(&mut state, progress);
+
+		let mut merged = 0;
+		for entry in self.tracks.values() {
+			if killed.killed() { return Err(RipRipError::Killed); }
+
+			replace_state(&mut state, entry.track, &opts, progress)?;
+			if state.merge_state(src, &opts)? { merged += 1; }
+
+			progress.increment();
+		}
+
+		progress.finish();
+
+		Ok(merged)
+	}
 }
 
 impl Ripper<'_> {
@@ -331,11 +660,137 @@ impl Ripper<'_> {
 		if let Some(legend_a) = legend.start() { eprintln!("        {legend_a}"); }
 		eprintln!("        {legend} \x1b[2msamples\x1b[0m");
 
+		// Summarize the AccurateRip matches across tracks, if any.
+		if let Some(ar) = self.summarize_ar() {
+			eprintln!();
+			Msg::custom("AccurateRip", 199, &ar).with_newline(true).eprint();
+		}
+
+		// Explain the silence at the edge of the disc, if any.
+		if let Some(lost) = self.summarize_offset() {
+			eprintln!();
+			Msg::custom("Offset", 199, &lost).with_newline(true).eprint();
+		}
+
+		// Warn about excessive contention, if any.
+		if let Some(advice) = q2.contentious_advisory() {
+			eprintln!();
+			Msg::warning(advice).eprint();
+		}
+
+		// Note the sectors that stopped changing, if any.
+		let stuck: usize = self.tracks.values().map(RipEntry::stuck_sectors).sum();
+		if stuck != 0 {
+			eprintln!();
+			Msg::warning(format!(
+				"{} came back exactly the same {STUCK_PASSES} passes in a row, so {} skipped for the rest of the rip. The drive has likely given all it can for {}.",
+				stuck.nice_inflect("sector", "sectors"),
+				if stuck == 1 { "was" } else { "were" },
+				if stuck == 1 { "it" } else { "them" },
+			)).eprint();
+		}
+
+		// List the tracks skipped for corruption, if any.
+		let corrupt = self.tracks.values()
+			.filter_map(|t| if t.corrupt { Some(format!("#{:02}", t.track.number())) } else { None })
+			.collect::<Vec<String>>();
+		if ! corrupt.is_empty() {
+			eprintln!();
+			Msg::warning(format!(
+				"Skipped {} with corrupt state data: {}. Rerip {} with --no-resume to start over.",
+				corrupt.len().nice_inflect("track", "tracks"),
+				corrupt.join(", "),
+				if corrupt.len() == 1 { "it" } else { "them" },
+			)).eprint();
+		}
+
 		// An extra line to give some separation between this task and the
 		// next.
 		eprintln!();
 	}
 
+	/// # Summarize AccurateRip.
+	///
+	/// Count up the tracks that matched AccurateRip with sufficient
+	/// confidence, and check whether they all share a common database entry,
+	/// i.e. came from the same pressing.
+	///
+	/// Returns `None` if nothing matched.
+	fn summarize_ar(&self) -> Option<String> {
+		use std::fmt::Write;
+
+		let conf = self.opts.confidence();
+		let mut total = 0_usize;
+		let mut matched = 0_usize;
+		let mut common: Option<Vec<usize>> = None;
+		for entry in self.tracks.values() {
+			// HTOA isn't verifiable. Boo.
+			if entry.track.is_htoa() { continue; }
+			total += 1;
+
+			if entry.ar.is_some_and(|(v1, v2)| conf <= v1 || conf <= v2) {
+				matched += 1;
+				match &mut common {
+					Some(c) => { c.retain(|k| entry.ar_entries.contains(k)); },
+					None => { common = Some(entry.ar_entries.clone()); },
+				}
+			}
+		}
+
+		if matched == 0 { return None; }
+		let mut out = format!(
+			"{matched}/{} matched with confidence {conf}+",
+			total.nice_inflect("track", "tracks"),
+		);
+		if matched == 1 { out.push('.'); }
+		else if let Some(k) = common.as_deref().and_then(<[usize]>::first) {
+			write!(&mut out, ", all from the same pressing (entry #{}).", k + 1).unwrap();
+		}
+		else { out.push_str(", but not from the same pressing."); }
+
+		Some(out)
+	}
+
+	/// # Summarize Offset.
+	///
+	/// Count up the samples at the very start or end of the disc that the
+	/// drive can't reach with its read offset, for each track affected.
+	/// These are filled with silence and counted as confirmed, which can look
+	/// like a bug if you don't know why.
+	///
+	/// Returns `None` if nothing was lost.
+	fn summarize_offset(&self) -> Option<String> {
+		let offset = self.opts.offset();
+		if offset.samples_abs() == 0 { return None; }
+		let access = accessible_range(self.disc.toc(), offset).ok()?;
+
+		let mut lost = Vec::new();
+		for entry in self.tracks.values() {
+			let rng = entry.track.sector_range_normalized();
+			let start = i32::try_from(rng.start).ok()?.checked_mul(i32::from(SAMPLES_PER_SECTOR))?;
+			let end = i32::try_from(rng.end).ok()?.checked_mul(i32::from(SAMPLES_PER_SECTOR))?;
+			let missing =
+				if offset.is_negative() { access.start.min(end) - start }
+				else { end - access.end.max(start) };
+			if let Ok(missing @ 1..) = u32::try_from(missing) {
+				lost.push(format!(
+					"the {} {} of track #{:02}",
+					if offset.is_negative() { "first" } else { "last" },
+					missing.nice_inflect("sample", "samples"),
+					entry.track.number(),
+				));
+			}
+		}
+
+		if lost.is_empty() { return None; }
+		Some(format!(
+			"The drive can't reach {} with its {}-sample offset; {} written as silence and counted as confirmed.",
+			lost.join(" and "),
+			offset.samples(),
+			if lost.len() == 1 { "it is" } else { "they are" },
+		))
+	}
+
 	#[expect(clippy::option_if_let_else, reason = "Too messy.")]
 	#[expect(clippy::type_complexity, reason = "It is only used internally.")]
 	/// # Summarize Per-Track Status.
@@ -357,7 +812,7 @@ impl Ripper<'_> {
 				let idx = t.track.number();
 				let (_, q) = t.quality;
 				let ar = t.ar.filter(|&(v1, v2)| conf <= v1 || conf <= v2);
-				let ctdb = t.ctdb.filter(|&v1| u16::from(conf) <= v1);
+				let ctdb = t.ctdb.filter(|&v1| self.opts.ctdb_confidence() <= v1);
 				let likely = NiceU32::from(q.confirmed() + q.likely());
 				let maybe = NiceU32::from(q.maybe());
 				let bad =
@@ -402,7 +857,7 @@ impl Ripper<'_> {
 					"{idx:02}  \x1b[{COLOR_BAD}m{:>wbad$}  \x1b[0;{COLOR_MAYBE}m{:>wmaybe$}  \x1b[0;{}m{:>wlikely$}\x1b[0m{}{}",
 					bad.as_str(),
 					maybe.as_str(),
-					if ar.is_some() || ctdb.is_some() { COLOR_CONFIRMED } else { COLOR_LIKELY },
+					if ar.is_some() || ctdb.is_some() { COLOR_CONFIRMED.as_str() } else { COLOR_LIKELY.as_str() },
 					likely.as_str(),
 					if let Some((v1, v2)) = ar {
 						let nice_v1 = NiceU8::from(v1.min(99));
@@ -415,10 +870,14 @@ impl Ripper<'_> {
 					}
 					else if any_ar { Cow::Borrowed("             ") }
 					else { Cow::Borrowed("") },
+					// A lone CTDB match is only let through on request, and
+					// is flagged as such.
 					if let Some(v1) = ctdb {
 						Cow::Owned(format!(
-							"       \x1b[1;{COLOR_CONFIRMED}m{:03}\x1b[0m",
+							"       \x1b[1;{}m{:03}{}\x1b[0m",
+							if v1 == 1 { COLOR_LIKELY.as_str() } else { COLOR_CONFIRMED.as_str() },
 							v1.min(999),
+							if v1 == 1 { "!" } else { "" },
 						))
 					}
 					else if any_ctdb { Cow::Borrowed("          ") }
@@ -432,28 +891,86 @@ impl Ripper<'_> {
 		handle.flush().unwrap();
 	}
 
-	/// # Finish.
+	/// # Summarize Status (One Line).
 	///
-	/// Dissolve the instance and return the tracks we actually exported, along
-	/// with their confirmation details. Specifically, this returns the file
-	/// path and AccurateRip/CTDB match counts, indexed by track number.
-	pub(crate) fn finish(self) -> Option<SavedRips> {
+	/// Collapse the per-track status into a single line — disc ID, the
+	/// number of confirmed tracks, and the worst unconfirmed track — for
+	/// dashboards and the like.
+	pub(crate) fn summarize_status_oneline(&self) -> String {
 		let conf = self.opts.confidence();
-		let out: SavedRips = self.tracks.into_iter()
-			.filter_map(|(k, v)| {
-				let dst = v.dst?;
-				let ar =
-					if k == 0 && v.quality.1.is_likely() { Some((u8::MAX, u8::MAX))}
-					else { v.ar.filter(|&(v1, v2)| conf <= v1 || conf <= v2) };
-				let ctdb =
-					if k == 0 && v.quality.1.is_likely() { Some(u16::MAX) }
-					else { v.ctdb.filter(|&v1| u16::from(conf) <= v1) };
-				Some((k, (dst, ar, ctdb)))
+		let mut total = 0_usize;
+		let mut confirmed = 0_usize;
+		let mut worst: Option<(u8, f64)> = None;
+		for t in self.tracks.values() {
+			// HTOA isn't verifiable. Boo.
+			if t.track.is_htoa() { continue; }
+			total += 1;
+
+			if
+				t.ar.is_some_and(|(v1, v2)| conf <= v1 || conf <= v2) ||
+				t.ctdb.is_some_and(|v1| self.opts.ctdb_confidence() <= v1)
+			{
+				confirmed += 1;
+				continue;
+			}
+
+			let p = t.quality.1.percent_likely().unwrap_or(0.0);
+			if worst.is_none_or(|(_, w)| p < w) {
+				worst.replace((t.track.number(), p));
+			}
+		}
+
+		let toc = self.disc.toc();
+		let worst = worst.map_or_else(
+			|| "--".to_owned(),
+			|(idx, p)| format!("#{idx:02} {}%", NiceFloat::from(p).precise_str(2)),
+		);
+		format!("{}  {confirmed}/{total} confirmed  worst {worst}", toc.cddb_id())
+	}
+
+	/// # Results.
+	///
+	/// Return the final details for each track, in order.
+	pub(crate) fn results(&self) -> Vec<RipTrackResult> {
+		self.tracks.iter()
+			.map(|(&k, v)| RipTrackResult {
+				track: k,
+				dst: v.dst.clone(),
+				ar: v.ar,
+				ctdb: v.ctdb,
+				quality: v.quality.1.as_array(),
 			})
-			.collect();
+			.collect()
+	}
 
-		if out.is_empty() { None }
-		else { Some(out) }
+	/// # Warn Short.
+	///
+	/// Mention the tracks skipped for being too short, if any.
+	fn warn_short(&self, progress: &Progless) {
+		if self.short.is_empty() { return; }
+		let list: Vec<String> = self.short.iter().map(|idx| format!("#{idx:02}")).collect();
+		let _res = progress.push_msg(Msg::warning(format!(
+			"Skipping {} shorter than {} sectors: {}.",
+			self.short.len().nice_inflect("track", "tracks"),
+			self.opts.min_sectors(),
+			list.join(", "),
+		)).with_newline(true));
+	}
+
+	/// # Warn Stable C2.
+	///
+	/// Print a warning for each sustained run of sectors whose C2 errors
+	/// haven't budged in several passes, as that usually means copy protection
+	/// rather than damage.
+	fn warn_stable_c2(&self, progress: &Progless) {
+		for entry in self.tracks.values() {
+			for (a, b) in entry.stable_c2_runs() {
+				let _res = progress.push_msg(Msg::warning(format!(
+					"Track #{} sectors {a}–{b} returned identical C2 errors for {C2_STABLE_PASSES}+ passes in a row. This looks more like intentional corruption (copy protection) than damage, so further passes are unlikely to help; the best-guess data may be as good as it gets.",
+					entry.track.number(),
+				)).with_newline(true));
+			}
+		}
 	}
 }
 
@@ -481,8 +998,44 @@ struct RipEntry {
 	/// # AccurateRip Confidence.
 	ar: Option<(u8, u8)>,
 
+	/// # AccurateRip Entries.
+	///
+	/// The indices of the AccurateRip database entries (pressings) the track
+	/// matched, if any.
+	ar_entries: Vec<usize>,
+
 	/// # CTDB Confidence.
 	ctdb: Option<u16>,
+
+	/// # CTDB Shift.
+	///
+	/// The (non-zero) sample shift CTDB matched at, if any.
+	ctdb_shift: Option<i16>,
+
+	/// # C2 Error Streaks.
+	///
+	/// The C2 error hash for each (read) LSN that came back with errors,
+	/// along with the number of consecutive passes it has been identical,
+	/// kept separately for each drive.
+	c2: [BTreeMap<i32, (u32, u8)>; 2],
+
+	/// # Unchanged Streaks.
+	///
+	/// The number of consecutive passes each (read) LSN has been read
+	/// without changing any of its samples, kept separately for each drive.
+	stuck: [BTreeMap<i32, u8>; 2],
+
+	/// # Corrupt (Skipped)?
+	///
+	/// This is set when the state couldn't be loaded and
+	/// `RipOptions::skip_corrupt` is in effect.
+	corrupt: bool,
+
+	/// # Silent?
+	///
+	/// This is set when a pass turns up nothing but (uncontested) silence,
+	/// as there's nothing more to be gained from it.
+	silent: bool,
 }
 
 impl RipEntry {
@@ -522,7 +1075,13 @@ impl RipEntry {
 			sectors,
 			quality: (quality, quality),
 			ar: None,
+			ar_entries: Vec::new(),
 			ctdb: None,
+			ctdb_shift: None,
+			c2: [BTreeMap::new(), BTreeMap::new()],
+			stuck: [BTreeMap::new(), BTreeMap::new()],
+			corrupt: false,
+			silent: false,
 		})
 	}
 }
@@ -554,24 +1113,50 @@ impl RipEntry {
 	-> Result<bool, RipRipError> {
 		// Update the title.
 		let title = format!(
-			"{}{}{}…",
+			"{}{}{}…{}",
 			if share.pass == 1 && state.is_new() { "Ripping fresh" } else { "Re-ripping" },
 			if opts.passes() == 1 { String::new() } else { format!(", pass #{}", share.pass) },
 			if opts.backwards() { ", backwards, and in heels" } else { "" },
+			share.overall().map_or_else(String::new, |v| format!(" ({v} of the rip is likely good)")),
 		);
 		set_progress_title(share.progress, self.track.number(), &title);
+		let watch = ReadWatch::new(share.progress, share.killed.inner(), self.track.number(), &title);
 
 		let mut any_read = false;
 		let before = state.quick_hash();
 		let rip_rng = state.sector_rip_range();
 
-		for (read_lsn, sector) in state.offset_rip_iter(opts)? {
-			// We can skip this block if the user aborted or there's
-			// nothing to refine.
-			if
-				share.killed.killed() ||
-				sector.iter().all(|v| v.is_likely(opts.rereads()))
-			{
+		// Age the samples, if we're doing that. This has to happen after the
+		// "before" hash so any halved counts get saved. (The ages alone
+		// don't count as changes.)
+		if let Some(max) = opts.decay() { state.decay(max.get(), opts)?; }
+
+		// Everything gets read during the minimum passes.
+		if opts.forced_pass(share.pass) { state.mark_todo(opts)?; }
+
+		// Open the C2 dump, if requested.
+		let mut dump =
+			if opts.dump_c2() { Some(C2Dump::new(state.toc(), self.track)?) }
+			else { None };
+
+		// Sectors with nothing left to refine are skipped by the iterator, but
+		// we still need to account for them in the progress.
+		let iter = state.offset_rip_iter(opts)?;
+		let skipped = u32::try_from(iter.skipped()).map_err(|_| RipRipError::RipOverflow)?;
+		if skipped != 0 { share.progress.increment_n(skipped); }
+
+		for (read_lsn, sector, todo) in iter {
+			// Hold up if the user asked us to, then skip the block if they
+			// aborted.
+			share.pause(self.track.number(), &title);
+			if share.killed.killed() {
+				share.progress.increment();
+				continue;
+			}
+
+			// There's no point re-reading sectors that have stopped changing.
+			if self.is_stuck(share.drive, read_lsn) {
+				*todo = false;
 				share.progress.increment();
 				continue;
 			}
@@ -583,18 +1168,14 @@ impl RipEntry {
 			// back-to-back.
 			if ! any_read {
 				if let Some(cache_len) = share.should_bust_cache(self.track.number(), opts) {
-					set_progress_title(
-						share.progress,
-						self.track.number(),
-						"Busting the cache…",
-					);
+					set_progress_title(share.progress, self.track.number(), "Busting the cache…");
 					share.log.add_cache_bust();
 					share.buf.cache_bust(
 						share.cdio,
 						cache_len,
 						&rip_rng,
 						share.leadout,
-						opts.backwards(),
+						opts,
 						share.killed,
 					);
 					set_progress_title(share.progress, self.track.number(), &title);
@@ -605,20 +1186,34 @@ impl RipEntry {
 			// Read and patch!
 			any_read = true;
 			share.pass_reads += 1;
-			match share.buf.read_sector(share.cdio, read_lsn, opts) {
+			let now = Instant::now();
+			let res = watch.read(read_lsn, || share.buf.read_sector(share.cdio, read_lsn, opts, share.pass));
+			share.log.add_read(self.track, read_lsn, now.elapsed());
+			match res {
 				// Good is good!
 				Ok(all_good) => if ! share.killed.killed() {
+					if let Some(d) = dump.as_mut() { d.push(read_lsn, share.pass, &share.buf)?; }
+					self.note_c2(share.drive, read_lsn, share.buf.c2_hash());
+
 					// Patch the data, unless the user just aborted, as that
-					// will probably have messed up the data.
-					for (old, (new, c2_err)) in sector.iter_mut().zip(share.buf.samples()) {
-						old.update(new, c2_err, all_good);
+					// will probably have messed up the data. The changes are
+					// staged in a scratch copy and only committed once the
+					// whole sector is done so an abort can't leave it
+					// half-updated.
+					share.stage_sector(sector, all_good, opts);
+					if ! share.killed.killed() {
+						self.note_unchanged(share.drive, read_lsn, share.scratch.as_slice() == &*sector);
+						sector.clone_from_slice(&share.scratch);
+
+						// Keep the todo current so we know whether to come back.
+						*todo = sector_todo(sector, opts);
 					}
 				},
 				// Silently skip generic read errors.
-				Err(RipRipError::CdRead) => if opts.verbose() {
+				Err(RipRipError::CdRead) => if opts.logging() {
 					share.log.add_error(read_lsn, RipRipError::CdRead);
 				},
-				Err(RipRipError::SubchannelDesync) => if opts.verbose() {
+				Err(RipRipError::SubchannelDesync) => if opts.logging() {
 					share.log.add_error(read_lsn, RipRipError::SubchannelDesync);
 				},
 				// Abort for all other kinds of errors.
@@ -626,51 +1221,257 @@ impl RipEntry {
 			}
 
 			// Count up the issues for this sector.
-			if opts.verbose() {
-				let mut total_bad = 0;
-				let mut total_wishy = 0;
-				for v in sector {
-					if v.is_bad() { total_bad += 1; }
-					else if v.is_confused() { total_wishy += 1; }
-				}
-				if total_bad != 0 {
-					share.log.add_bad(self.track, read_lsn, total_bad);
-				}
-				if total_wishy != 0 {
-					share.log.add_confused(self.track, read_lsn, total_wishy);
-				}
-			}
+			if opts.logging() { self.log_sector(&mut share.log, read_lsn, sector); }
 
+			warn_c2(&mut share.buf, share.progress);
 			share.progress.increment();
 		}
 
-		// Reverify if we changed any data, or haven't verified yet.
+		if let Some(d) = dump { d.finish()?; }
+
+		// Reverify if we changed any data, or haven't verified yet. Silence
+		// isn't worth the trouble.
 		self.quality.1 = state.track_quality(opts);
-		if self.ar.is_none() || self.ctdb.is_none() || before != state.quick_hash() {
+		if self.quality.1.is_silent() { self.note_silent(share.progress); }
+		else if self.ar.is_none() || self.ctdb.is_none() || before != state.quick_hash() {
 			self.verify(state, opts, share.progress);
 		}
+		if opts.logging() {
+			share.log.add_heatmap(self.track, TrackHeatmap::new(state.track_slice(), opts.rereads(), self.quality.1.is_confirmed()));
+		}
 
-		// Save the state if we changed any data.
+		// Save the state if we changed any data (or the summary).
 		let changed = before != state.quick_hash();
-		if changed {
-			// Resave the state.
+		if changed { share.pass_changed = true; }
+		if state.set_summary(opts.rereads(), self.quality.1, self.ar, self.ctdb) {
 			set_progress_title(
 				share.progress,
 				self.track.number(),
 				"Saving the state…",
 			);
-			let _res = state.save_state();
+			state.save_state_soft()?;
 		}
 
 		// Don't forget to extract the track. Do this after every pass
 		// in case people want to fuck with CUETools immediately.
-		if self.dst.is_none() || changed {
-			self.dst.replace(state.save_track()?);
+		if (self.dst.is_none() || changed) && self.exportable(opts) {
+			self.dst.replace(state.save_track(opts, self.quality.1, (self.ar, self.ctdb))?);
 		}
 
+		// And maybe a lossy copy too.
+		if opts.lossy() { self.save_lossy(share, state, opts)?; }
+
 		Ok(self.skippable())
 	}
 
+	/// # Log Sector.
+	///
+	/// Record the number of bad and confused samples in the sector, if any.
+	fn log_sector(&self, log: &mut RipLog<'_>, lsn: i32, sector: &[RipSample]) {
+		let mut total_bad = 0;
+		let mut total_wishy = 0;
+		for v in sector {
+			if v.is_bad() { total_bad += 1; }
+			else if v.is_confused() { total_wishy += 1; }
+		}
+		if total_bad != 0 { log.add_bad(self.track, lsn, total_bad); }
+		if total_wishy != 0 { log.add_confused(self.track, lsn, total_wishy); }
+	}
+
+	/// # Note C2 Errors.
+	///
+	/// Keep track of how many passes in a row a sector has returned the exact
+	/// same C2 errors from the given drive. Error-free reads clear the streak.
+	fn note_c2(&mut self, drive: usize, lsn: i32, hash: Option<u32>) {
+		let c2 = &mut self.c2[drive];
+		if let Some(hash) = hash {
+			let streak = c2.entry(lsn).or_insert((hash, 0));
+			if streak.0 == hash { streak.1 = streak.1.saturating_add(1); }
+			else { *streak = (hash, 1); }
+		}
+		else { c2.remove(&lsn); }
+	}
+
+	/// # Note Unchanged.
+	///
+	/// Keep track of how many passes in a row a sector has been read by the
+	/// given drive without any of its samples changing. Any change clears the
+	/// streak.
+	fn note_unchanged(&mut self, drive: usize, lsn: i32, unchanged: bool) {
+		let stuck = &mut self.stuck[drive];
+		if unchanged {
+			let streak = stuck.entry(lsn).or_insert(0);
+			*streak = streak.saturating_add(1);
+		}
+		else { stuck.remove(&lsn); }
+	}
+
+	/// # Is Stuck?
+	///
+	/// Returns `true` if the sector has come back unchanged from the given
+	/// drive for at least `STUCK_PASSES` of its passes in a row.
+	fn is_stuck(&self, drive: usize, lsn: i32) -> bool {
+		self.stuck[drive].get(&lsn).is_some_and(|&n| STUCK_PASSES <= n)
+	}
+
+	/// # Stuck Sectors.
+	///
+	/// Return the number of sectors deemed stuck. With two drives, this is
+	/// whichever has given up on more of them.
+	fn stuck_sectors(&self) -> usize {
+		self.stuck.iter()
+			.map(|stuck| stuck.values().filter(|&&n| STUCK_PASSES <= n).count())
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// # Stable C2 Runs.
+	///
+	/// Return the (inclusive) LSN ranges of sustained runs of sectors whose
+	/// C2 errors have been identical for at least `C2_STABLE_PASSES` passes
+	/// of either drive.
+	///
+	/// Real damage tends to read a little differently each time; errors that
+	/// never budge across a long stretch are more likely intentional, i.e.
+	/// copy protection.
+	fn stable_c2_runs(&self) -> Vec<(i32, i32)> {
+		let mut out = Vec::new();
+		for c2 in &self.c2 {
+			let mut run: Option<(i32, i32)> = None;
+			for lsn in c2.iter().filter_map(|(&k, v)| (C2_STABLE_PASSES <= v.1).then_some(k)) {
+				match run {
+					Some((_, ref mut end)) if *end + 1 == lsn => { *end = lsn; },
+					_ => {
+						if let Some(r) = run.replace((lsn, lsn)) { out.push(r); }
+					},
+				}
+			}
+			if let Some(r) = run { out.push(r); }
+		}
+
+		out.retain(|&(a, b)| C2_STABLE_RUN <= (a..=b).count());
+		out.sort_unstable();
+		out.dedup();
+		out
+	}
+
+	/// # Paranoid Re-Read.
+	///
+	/// Re-read every sector in the track once — confirmed or not — and check
+	/// the fresh, error-free samples against the current values.
+	///
+	/// Nothing is changed unless a contradiction is found, in which case the
+	/// offending sectors are updated like any other read (so the contention
+	/// is on record), a warning is printed, and the track is re-verified,
+	/// re-saved, and re-exported.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any errors encountered, except run-of-the-mill
+	/// sector read or sync errors, which are simply skipped.
+	fn paranoid(&mut self, share: &mut RipShare, state: &mut RipState, opts: &RipOptions)
+	-> Result<(), RipRipError> {
+		set_progress_title(share.progress, self.track.number(), "Paranoid re-read…");
+
+		let mut contradictions = 0_u32;
+		state.mark_todo(opts)?;
+		let iter = state.offset_rip_iter(opts)?;
+		let skipped = u32::try_from(iter.skipped()).map_err(|_| RipRipError::RipOverflow)?;
+		if skipped != 0 { share.progress.increment_n(skipped); }
+		for (read_lsn, sector, todo) in iter {
+			if ! share.killed.killed() {
+				match share.buf.read_sector(share.cdio, read_lsn, opts, share.pass) {
+					Ok(all_good) => if
+						! share.killed.killed() &&
+						sector.iter().zip(share.buf.samples()).any(|(old, (new, c2_err))|
+							! c2_err &&
+							matches!(old, RipSample::Maybe(_, _)) &&
+							old.as_array() != new
+						)
+					{
+						contradictions += 1;
+						stage_sector(&mut share.scratch, sector, &share.buf, all_good, opts);
+						if ! share.killed.killed() { sector.clone_from_slice(&share.scratch); }
+					},
+					// Silently skip generic read errors.
+					Err(RipRipError::CdRead | RipRipError::SubchannelDesync) => {},
+					// Abort for all other kinds of errors.
+					Err(e) => return Err(e),
+				}
+			}
+
+			*todo = sector_todo(sector, opts);
+			share.progress.increment();
+		}
+		warn_c2(&mut share.buf, share.progress);
+
+		// All quiet on the western front?
+		if contradictions == 0 { return Ok(()); }
+
+		let _res = share.progress.push_msg(Msg::warning(format!(
+			"Track #{} had {} contradicted on a paranoid re-read.",
+			self.track.number(),
+			contradictions.nice_inflect("sector", "sectors"),
+		)).with_newline(true));
+
+		self.quality.1 = state.track_quality(opts);
+		self.verify(state, opts, share.progress);
+
+		set_progress_title(share.progress, self.track.number(), "Saving the state…");
+		state.set_summary(opts.rereads(), self.quality.1, self.ar, self.ctdb);
+		state.save_state_soft()?;
+		if self.exportable(opts) {
+			self.dst.replace(state.save_track(opts, self.quality.1, (self.ar, self.ctdb))?);
+		}
+
+		Ok(())
+	}
+
+	/// # Save Lossy Copy.
+	///
+	/// The lossy copy is only worth making once we're done with the track,
+	/// i.e. it was just confirmed or this is the final pass.
+	///
+	/// Encoding failures are printed as warnings rather than bubbled up;
+	/// they aren't worth dying over. Running out of disk space is, though.
+	///
+	/// ## Errors
+	///
+	/// This will return `RipRipError::DiskFull` if the disk is full.
+	fn save_lossy(&self, share: &RipShare, state: &RipState, opts: &RipOptions)
+	-> Result<(), RipRipError> {
+		if
+			! share.killed.killed() &&
+			self.exportable(opts) &&
+			(share.pass == opts.passes() || self.skippable_on(share.pass + 1, opts))
+		{
+			set_progress_title(
+				share.progress,
+				self.track.number(),
+				"Encoding a lossy copy…",
+			);
+			match state.save_lossy(opts) {
+				Ok(_) => {},
+				Err(e @ RipRipError::DiskFull(..)) => return Err(e),
+				Err(e) => {
+					let _res = share.progress.push_msg(Msg::warning(e.to_string()).with_newline(true));
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	/// # Exportable?
+	///
+	/// Returns `true` unless `RipOptions::verified_only` is set and the track
+	/// has yet to be verified, or the track is an HTOA destined to be merged
+	/// into the first track's export instead.
+	fn exportable(&self, opts: &RipOptions) -> bool {
+		! (self.track.is_htoa() && matches!(opts.htoa_mode(), HtoaMode::Merge)) &&
+		(! opts.verified_only() || is_verified(opts, self.ar, self.ctdb))
+	}
+
 	/// # Skippable?
 	///
 	/// Returns `true` if we have already loaded/exported this rip, and at last
@@ -681,7 +1482,51 @@ impl RipEntry {
 	/// unnecessary loop, this prevents us having to read/decompress/deserialize
 	/// the state data at all.
 	const fn skippable(&self) -> bool {
-		self.dst.is_some() && self.quality.1.is_confirmed()
+		self.corrupt || self.silent || (self.dst.is_some() && self.quality.1.is_confirmed())
+	}
+
+	/// # Skippable on Pass?
+	///
+	/// Same as `RipEntry::skippable`, except confirmed tracks still need
+	/// ripping during the (one-based) `pass` if it falls within
+	/// `RipOptions::min_passes`.
+	const fn skippable_on(&self, pass: u8, opts: &RipOptions) -> bool {
+		self.corrupt || (self.skippable() && ! opts.forced_pass(pass))
+	}
+
+	/// # Note Silence.
+	///
+	/// Flag the entry as silent so it gets skipped from here on out, letting
+	/// the user know why. (Silent tracks rarely match AccurateRip, so there's
+	/// no point verifying or re-ripping them.)
+	fn note_silent(&mut self, progress: &Progless) {
+		if self.silent { return; }
+		self.silent = true;
+		let _res = progress.push_msg(Msg::info(format!(
+			"Track #{} is pure silence; skipping verification and any further passes.",
+			self.track.number(),
+		)).with_newline(true));
+	}
+
+	/// # Mark Corrupt.
+	///
+	/// Flag the entry as corrupt so it gets skipped from here on out, warn
+	/// the user, and account for the work that won't be happening in the
+	/// progress bar, starting from (one-based) `pass`. (The paranoid re-read,
+	/// if any, counts as one more pass.)
+	///
+	/// Entries already marked are left alone, so the work isn't discounted
+	/// twice.
+	fn mark_corrupt(&mut self, opts: &RipOptions, progress: &Progless, pass: u8) {
+		if self.corrupt { return; }
+		self.corrupt = true;
+		let left = (u32::from(opts.passes()) + u32::from(opts.paranoid()) + 1)
+			.saturating_sub(u32::from(pass));
+		if left != 0 { progress.increment_n(self.sectors * left); }
+		let _res = progress.push_msg(Msg::warning(format!(
+			"The state data for track #{} (and its backup) is corrupt; skipping it per --skip-corrupt.",
+			self.track.number(),
+		)).with_newline(true));
 	}
 
 	/// # Verify Entry.
@@ -700,11 +1545,25 @@ impl RipEntry {
 		if self.track.is_htoa() { return false; }
 
 		// Check AccurateRip and CTDB in separate threads.
-		(self.ar, self.ctdb) = verify_track(self.track, state);
+		let shift;
+		(self.ar, self.ctdb, shift, self.ar_entries) = verify_track(self.track, state, opts);
+
+		// A CTDB match at a shifted position could just be a different
+		// pressing, but could also mean the offset is wrong. Mention it, but
+		// only once.
+		if let Some(shift) = shift {
+			if self.ctdb_shift != Some(shift) {
+				let _res = progress.push_msg(Msg::warning(format!(
+					"Track #{} matched CUETools at a shift of {shift:+} samples; the read offset may be off by that much.",
+					self.track.number(),
+				)).with_newline(true));
+			}
+		}
+		self.ctdb_shift = shift;
 
 		// If we're confirmed and the state isn't, update the state and our
 		// quality snapshot.
-		let verified = opts.confidence() <= max_confidence(self.ar, self.ctdb);
+		let verified = is_verified(opts, self.ar, self.ctdb);
 		if verified && ! self.quality.1.is_confirmed() {
 			self.quality.1 = TrackQuality::new_confirmed(self.quality.1.total());
 		}
@@ -713,6 +1572,22 @@ impl RipEntry {
 		verified
 	}
 
+	/// # Pre-Summarize Entry.
+	///
+	/// Same as `RipEntry::preverify`, but for status checks, using the
+	/// summary from the state header instead of the full data. (Nothing is
+	/// exported.)
+	fn presummarize(&mut self, summary: StateSummary, opts: &RipOptions) {
+		self.ar = summary.ar().filter(|_| ! opts.no_accuraterip());
+		self.ctdb = summary.ctdb().filter(|_| ! opts.no_ctdb());
+		let tmp =
+			if is_verified(opts, self.ar, self.ctdb) {
+				TrackQuality::new_confirmed(summary.quality().total())
+			}
+			else { summary.quality() };
+		self.quality = (tmp, tmp);
+	}
+
 	/// # Pre-Verify Entry.
 	///
 	/// Check out the initial state of the rip before doing any new work. If
@@ -725,22 +1600,32 @@ impl RipEntry {
 	///
 	/// If the track is confirmed it will be exported here and now; an error
 	/// will be returned in the unlikely event that fails.
-	fn preverify(&mut self, state: &RipState, opts: &RipOptions)
+	fn preverify(&mut self, state: &mut RipState, opts: &RipOptions)
 	-> Result<bool, RipRipError> {
-		if ! state.is_new() {
-			(self.ar, self.ctdb) = verify_track(self.track, state);
-			if opts.confidence() <= max_confidence(self.ar, self.ctdb) {
-				let tmp = TrackQuality::new_confirmed(self.quality.1.total());
-				self.quality = (tmp, tmp);
-				self.dst.replace(state.save_track()?);
-				return Ok(true);
-			}
-
-			let tmp = state.track_quality(opts);
-			self.quality = (tmp, tmp);
+		if self.precheck(state, opts) {
+			self.dst.replace(state.save_track(opts, self.quality.1, (self.ar, self.ctdb))?);
+			Ok(true)
 		}
+		else { Ok(false) }
+	}
 
-		Ok(false)
+	/// # Pre-Check Entry.
+	///
+	/// Same as `RipEntry::preverify`, but without the export, leaving the
+	/// state and files alone. This is used for status checks.
+	///
+	/// Returns `true` if the track is already confirmed w/ AccurateRip or
+	/// CUETools, `false` if not.
+	fn precheck(&mut self, state: &RipState, opts: &RipOptions) -> bool {
+		if state.is_new() { return false; }
+
+		(self.ar, self.ctdb, _, self.ar_entries) = verify_track(self.track, state, opts);
+		let verified = is_verified(opts, self.ar, self.ctdb);
+		let tmp =
+			if verified { TrackQuality::new_confirmed(self.quality.1.total()) }
+			else { state.track_quality(opts) };
+		self.quality = (tmp, tmp);
+		verified
 	}
 }
 
@@ -765,7 +1650,7 @@ struct RipShare<'a> {
 	buf: RipBuffer,
 
 	/// # Event Log.
-	log: RipLog,
+	log: RipLog<'a>,
 
 	/// # Leadout Sector.
 	leadout: i32,
@@ -776,6 +1661,9 @@ struct RipShare<'a> {
 	/// # Reads This Pass.
 	pass_reads: u32,
 
+	/// # Changes This Pass?
+	pass_changed: bool,
+
 	/// # Force Bust?
 	///
 	/// When true, a cache bust will be attempted on the next read.
@@ -784,9 +1672,34 @@ struct RipShare<'a> {
 	/// # Last Read Track Number.
 	last_read_track: u8,
 
+	/// # Sector Scratch.
+	///
+	/// Sector updates are staged here before being committed to the state.
+	scratch: Vec<RipSample>,
+
+	/// # Samples Contended Without C2.
+	///
+	/// The number of samples that turned contentious on reads C2 claimed
+	/// were fine, or `None` once C2 has reported an error (or we've warned
+	/// about it), as there's no longer any point counting.
+	c2_missed: Option<u32>,
+
+	/// # Overall Quality.
+	///
+	/// The total likely/confirmed and overall sample counts across all
+	/// tracks, used to show the big-picture progress in the title. This is
+	/// adjusted incrementally as track qualities change so we never have to
+	/// rescan anything.
+	overall: (u32, u32),
+
 	/// # CDIO Instance.
 	cdio: &'a LibcdioInstance,
 
+	/// # Drive Index.
+	///
+	/// Zero for the first drive, one for the second.
+	drive: usize,
+
 	/// # Progress Instance.
 	progress: &'a Progless,
 
@@ -797,16 +1710,29 @@ struct RipShare<'a> {
 impl<'a> RipShare<'a> {
 	#[expect(clippy::cast_possible_wrap, reason = "False positive.")]
 	/// # New Instance.
-	const fn new(disc: &'a Disc, progress: &'a Progless, killed: &'a KillSwitch) -> Self {
+	const fn new(
+		disc: &'a Disc,
+		opts: &RipOptions,
+		progress: &'a Progless,
+		killed: &'a KillSwitch,
+	) -> Self {
 		Self {
 			buf: RipBuffer::new(),
-			log: RipLog::new(),
+			log: RipLog::new(
+				opts.verbose(),
+				if opts.log_files() { Some(disc.toc()) } else { None },
+			),
 			leadout: disc.toc().audio_leadout_normalized() as i32,
 			pass: 0,
 			pass_reads: 0,
+			pass_changed: false,
 			force_bust: false,
 			last_read_track: u8::MAX,
+			scratch: Vec::new(),
+			c2_missed: Some(0),
+			overall: (0, 0),
 			cdio: disc.cdio(),
+			drive: 0,
 			progress,
 			killed,
 		}
@@ -824,11 +1750,94 @@ impl<'a> RipShare<'a> {
 		let len = opts.cache_sectors();
 		self.force_bust = len != 0 && self.pass_reads < len;
 		self.pass_reads = 0;
+		self.pass_changed = false;
+
+		// Give previously-bad sectors another chance?
+		if self.pass != 0 && opts.retry_shitlisted() {
+			LibcdioInstance::clear_shitlist();
+		}
 
 		// Bump the pass.
 		self.pass += 1;
 	}
 
+	/// # Stage Sector.
+	///
+	/// Copy `sector` to the scratch buffer and patch it with the freshly-read
+	/// samples, keeping count of any that turn contentious without C2
+	/// raising an eyebrow.
+	fn stage_sector(&mut self, sector: &[RipSample], all_good: bool, opts: &RipOptions) {
+		if ! all_good { self.c2_missed = None; }
+		let missed = stage_sector(&mut self.scratch, sector, &self.buf, all_good, opts);
+		if let Some(n) = self.c2_missed.as_mut() { *n += missed; }
+	}
+
+	/// # Warn Fake C2.
+	///
+	/// Some drives accept C2 requests but never actually flag anything. If
+	/// C2 hasn't reported a single error all run, yet re-reads have
+	/// disagreed on at least `C2_MISSED_MIN` samples, let the user know —
+	/// once — that the drive's C2 probably can't be trusted.
+	fn warn_fake_c2(&mut self, opts: &RipOptions) {
+		let Some(missed) = self.c2_missed else { return; };
+		if C2_MISSED_MIN <= missed && opts.c2() && ! self.buf.c2_unsupported() {
+			self.c2_missed = None;
+			let _res = self.progress.push_msg(Msg::warning(format!(
+				"C2 hasn't flagged a single error, yet re-reads have disagreed on {} samples. The drive's C2 error pointers may be unreliable, so consider adding --sync and/or more passes.",
+				NiceU32::from(missed),
+			)).with_newline(true));
+		}
+	}
+
+	/// # Overall Percentage.
+	///
+	/// Return the likely/confirmed percentage across all tracks, formatted
+	/// for display, or `None` if nothing has been confirmed yet.
+	fn overall(&self) -> Option<String> {
+		let (good, total) = self.overall;
+		if good == 0 || total == 0 { None }
+		else if good == total { Some("100%".to_owned()) }
+		else {
+			let v = f64::from(good) * 100.0 / f64::from(total);
+			Some(format!("{}%", NiceFloat::from(v).precise_str(2)))
+		}
+	}
+
+	/// # Set Overall Quality.
+	///
+	/// Tally up the overall quality from scratch. This is only needed once
+	/// at the start; `RipShare::update_overall` handles subsequent changes.
+	fn set_overall<'b, I>(&mut self, iter: I)
+	where I: Iterator<Item=&'b TrackQuality> {
+		self.overall = iter.fold((0, 0), |(good, total), q| (
+			good.saturating_add(q.good()),
+			total.saturating_add(q.total().get()),
+		));
+	}
+
+	/// # Update Overall Quality.
+	///
+	/// Swap a track's old quality for its new quality.
+	fn update_overall(&mut self, old: TrackQuality, new: TrackQuality) {
+		if old != new {
+			self.overall.0 = self.overall.0
+				.saturating_sub(old.good())
+				.saturating_add(new.good());
+		}
+	}
+
+	/// # Pause.
+	///
+	/// If the user has paused the rip, update the title and wait until they
+	/// resume (or abort), then restore the original title.
+	fn pause(&self, idx: u8, title: &str) {
+		if self.killed.paused() {
+			set_progress_title(self.progress, idx, "Paused; send SIGUSR1 again to resume…");
+			self.killed.wait_while_paused();
+			set_progress_title(self.progress, idx, title);
+		}
+	}
+
 	/// # Should Bust Cache?
 	///
 	/// This method is only called at most once per track per pass, just before
@@ -866,25 +1875,116 @@ fn happy_track_msg(track: Track) -> Msg {
 		.with_newline(true)
 }
 
-#[expect(clippy::cast_possible_truncation, reason = "False positive.")]
-/// # Max Confidence.
+/// # Track Confirmed?
+///
+/// Load the saved state for a track, if any, and check whether or not it
+/// meets the AccurateRip/CUETools confidence threshold.
 ///
-/// Return the largest confidence value.
-const fn max_confidence(ar: Option<(u8, u8)>, ctdb: Option<u16>) -> u8 {
-	let mut max = 0;
+/// If the state header has a current summary, that is used instead, saving
+/// the trouble of loading and reverifying all the data.
+///
+/// Tracks without states, and the HTOA (which can't be verified), always
+/// return `false`.
+///
+/// ## Errors
+///
+/// This will return an error if the state exists but cannot be loaded.
+pub(crate) fn track_confirmed(toc: &Toc, track: Track, opts: &RipOptions)
+-> Result<bool, RipRipError> {
+	if track.is_htoa() || ! state_path(toc, track).is_ok_and(|s| s.is_file()) {
+		return Ok(false);
+	}
 
-	if let Some(v1) = ctdb {
-		// AccurateRip tops out at 99, so we can leave early.
-		if 99 <= v1 { return 99; }
-		max = v1 as u8;
+	// The summary is good enough if we have one.
+	if let Some(summary) = StateSummary::load(toc, track) {
+		return Ok(is_verified(
+			opts,
+			summary.ar().filter(|_| ! opts.no_accuraterip()),
+			summary.ctdb().filter(|_| ! opts.no_ctdb()),
+		));
 	}
 
-	if let Some((v1, v2)) = ar {
-		if max < v1 { max = v1; }
-		if max < v2 { max = v2; }
+	// Make sure we don't accidentally alter anything.
+	let opts = opts.with_resume(true).with_reset(false);
+	let state = RipState::new(toc, track, &opts)?;
+	if state.is_new() { return Ok(false); }
+
+	let (ar, ctdb, _, _) = verify_track(track, &state, &opts);
+	Ok(is_verified(&opts, ar, ctdb))
+}
+
+/// # HTOA Tracks.
+///
+/// Adjust the track list according to `RipOptions::htoa_mode`. An ignored
+/// HTOA is dropped, while a merged one is added whenever the first track is
+/// included, since the latter can't be exported without it.
+pub(crate) fn htoa_tracks(toc: &Toc, opts: RipOptions) -> RipOptions {
+	if toc.htoa().is_none() { return opts; }
+	match opts.htoa_mode() {
+		HtoaMode::Separate => opts,
+		HtoaMode::Merge =>
+			if opts.tracks().any(|idx| idx == 1) { opts.with_track(0) }
+			else { opts },
+		HtoaMode::Ignore => opts.without_track(0),
 	}
+}
 
-	max
+/// # HTOA Silence.
+///
+/// Load the saved state for the HTOA, if any, and return the number of
+/// leading and trailing silent samples, along with the total.
+///
+/// ## Errors
+///
+/// This will return an error if the state exists but cannot be loaded.
+pub(crate) fn htoa_silence(toc: &Toc, opts: &RipOptions)
+-> Result<Option<(usize, usize, usize)>, RipRipError> {
+	let Some(track) = toc.htoa() else { return Ok(None); };
+	if ! state_path(toc, track).is_ok_and(|s| s.is_file()) { return Ok(None); }
+
+	// Make sure we don't accidentally alter anything.
+	let opts = opts.with_resume(true).with_reset(false);
+	let state = RipState::new(toc, track, &opts)?;
+	if state.is_new() { return Ok(None); }
+
+	Ok(state.htoa_silence().map(|(a, b)| (a, b, state.track_slice().len())))
+}
+
+/// # Track Too Short?
+///
+/// Returns `true` if the track is shorter than `RipOptions::min_sectors`, and
+/// so should be skipped.
+pub(super) fn track_too_short(track: Track, opts: &RipOptions) -> bool {
+	track.duration().sectors() < u64::from(opts.min_sectors())
+}
+
+/// # Is Verified?
+///
+/// Returns `true` if either the AccurateRip or CTDB confidence meets the
+/// relevant threshold from `opts`.
+fn is_verified(opts: &RipOptions, ar: Option<(u8, u8)>, ctdb: Option<u16>) -> bool {
+	let conf = opts.confidence();
+	ar.is_some_and(|(v1, v2)| conf <= v1 || conf <= v2) ||
+	ctdb.is_some_and(|v1| opts.ctdb_confidence() <= v1)
+}
+
+/// # Replace State.
+///
+/// Switch the state over to a different track, pointing out if it had to be
+/// recovered from a backup.
+///
+/// ## Errors
+///
+/// This will bubble up any state-related errors encountered.
+fn replace_state(
+	state: &mut RipState,
+	track: Track,
+	opts: &RipOptions,
+	progress: &Progless,
+) -> Result<(), RipRipError> {
+	state.replace(track, opts)?;
+	warn_recovered(state, progress);
+	Ok(())
 }
 
 /// # Set Progress Title.
@@ -907,6 +2007,105 @@ fn standby_msg() -> &'static str {
 	STANDBY[idx]
 }
 
+/// # Replace State (Entry).
+///
+/// Same as `replace_state`, except with `RipOptions::skip_corrupt`, a corrupt
+/// state marks the entry as such instead of returning an error. Returns
+/// `false` in that case.
+///
+/// ## Errors
+///
+/// This will bubble up any other errors encountered.
+fn replace_entry_state(
+	state: &mut RipState,
+	entry: &mut RipEntry,
+	opts: &RipOptions,
+	progress: &Progless,
+	pass: u8,
+) -> Result<bool, RipRipError> {
+	match replace_state(state, entry.track, opts, progress) {
+		Ok(()) => Ok(true),
+		Err(RipRipError::StateCorrupt(_)) if opts.skip_corrupt() => {
+			entry.mark_corrupt(opts, progress, pass);
+			Ok(false)
+		},
+		Err(e) => Err(e),
+	}
+}
+
+/// # Warn Recovered.
+///
+/// Let the user know if the state data was corrupt and the previous save was
+/// used instead.
+fn warn_recovered(state: &mut RipState, progress: &Progless) {
+	if state.take_recovered() {
+		let _res = progress.push_msg(Msg::warning(format!(
+			"The state data for track #{} was corrupt; the previous save was recovered instead.",
+			state.track().number(),
+		)).with_newline(true));
+	}
+}
+
+/// # Warn C2 Fallback.
+///
+/// Let the user know — once — if C2 had to be disabled because the drive
+/// doesn't support it.
+pub(super) fn warn_c2(buf: &mut RipBuffer, progress: &Progless) {
+	if buf.take_c2_fallback() {
+		let _res = progress.push_msg(Msg::warning(
+			"The drive does not support C2 error pointers; they have been disabled for the rest of the run, so all reads will be taken at face value."
+		).with_newline(true));
+	}
+}
+
+/// # Warn Unverified Sync.
+///
+/// Subchannel sync can only verify reads whose subchannel carries a
+/// timecode. If most didn't, `--sync` wasn't really doing anything, and the
+/// user ought to know.
+pub(super) fn warn_sync(buf: &RipBuffer, progress: &Progless) {
+	let Some((bad, total)) = buf.sync_unverified() else { return; };
+	let msg =
+		if bad == total {
+			"Subchannel sync was requested, but the drive never returned any timecodes, so none of the reads were actually verified.".to_owned()
+		}
+		else if total < bad.saturating_mul(2) {
+			format!("Subchannel sync could only verify {} of {total} reads; the rest had no timecode to check against.", total - bad)
+		}
+		else { return; };
+
+	let _res = progress.push_msg(Msg::warning(msg).with_newline(true));
+}
+
+/// # Stage Sector.
+///
+/// Copy `sector` to `scratch` and patch it with the samples freshly read into
+/// `buf`, returning the number that turned contentious without C2 raising an
+/// eyebrow.
+///
+/// The changes are only staged so an abort can't leave the sector
+/// half-updated; it is up to the caller to commit them.
+pub(super) fn stage_sector(
+	scratch: &mut Vec<RipSample>,
+	sector: &[RipSample],
+	buf: &RipBuffer,
+	all_good: bool,
+	opts: &RipOptions,
+) -> u32 {
+	scratch.truncate(0);
+	scratch.extend_from_slice(sector);
+
+	let tentative = opts.c2_tentative(all_good);
+	let mut missed = 0;
+	for (old, (new, c2_err)) in scratch.iter_mut().zip(buf.samples()) {
+		let was = old.is_contentious();
+		if tentative { old.update_tentative(new, c2_err); }
+		else { old.update(new, c2_err, all_good); }
+		if ! c2_err && ! was && old.is_contentious() { missed += 1; }
+	}
+	missed
+}
+
 /// # Track Number to Bitflag.
 ///
 /// Redbook audio CDs can only have a maximum of 99 tracks — or 100 if we count
@@ -923,23 +2122,47 @@ const fn track_idx_to_bits(idx: u8) -> u128 {
 
 /// # Verify Track.
 ///
-/// Check the track rip against both the AccurateRip and CUETools databases.
-/// To improve performance, this performs each check in a separate thread.
-fn verify_track(track: Track, state: &RipState) -> (Option<(u8, u8)>, Option<u16>) {
+/// Check the track rip against both the AccurateRip and CUETools databases,
+/// returning their confidences, along with the CUETools match shift, if
+/// non-zero, and the AccurateRip entries matched, if any. To improve
+/// performance, this performs each check in a separate thread.
+///
+/// Databases disabled via `RipOptions` are skipped, and their results
+/// returned as `None`.
+fn verify_track(track: Track, state: &RipState, opts: &RipOptions)
+-> Verification {
+	verify_samples(state.toc(), track, state.track_slice(), state.rip_slice(), opts)
+}
+
+/// # Verify Samples.
+///
+/// This does the actual work for `verify_track`, but accepts the track and
+/// (padded) rip samples directly, so works for tracks sliced out of larger
+/// rips too.
+fn verify_samples(
+	toc: &Toc,
+	track: Track,
+	data: &[RipSample],
+	padded: &[RipSample],
+	opts: &RipOptions,
+) -> Verification {
 	std::thread::scope(|s| {
-		let ar = s.spawn(|| chk_accuraterip(
-			state.toc(),
-			track,
-			state.track_slice(),
-		));
-		let ctdb = s.spawn(|| chk_ctdb(
-			state.toc(),
-			track,
-			state.rip_slice(),
-		));
+		let ar = s.spawn(||
+			if opts.no_accuraterip() { None }
+			else { chk_accuraterip(toc, track, data) }
+		);
+		let ctdb = s.spawn(||
+			if opts.no_ctdb() { None }
+			else { chk_ctdb(toc, track, padded, opts.ctdb_single(), opts.ctdb_shift()) }
+		);
+		let ctdb = ctdb.join().ok().flatten();
+		let (ar, entries) = ar.join().ok().flatten()
+			.map_or((None, Vec::new()), |((v1, v2), entries)| (Some((v1.min(99), v2.min(99))), entries));
 		(
-			ar.join().ok().flatten().map(|(v1, v2)| (v1.min(99), v2.min(99))),
-			ctdb.join().ok().flatten().map(|v1| v1.min(999)),
+			ar,
+			ctdb.map(|(v1, _)| v1.min(999)),
+			ctdb.and_then(|(_, shift)| if shift == 0 { None } else { Some(shift) }),
+			entries,
 		)
 	})
 }
@@ -4,12 +4,17 @@
 
 use crate::{
 	CD_DATA_SIZE,
+	DriveVendorModel,
 	ReadOffset,
+	SAMPLE_OVERREAD,
 };
 use oxford_join::JoinFmt;
 use std::{
 	fmt,
-	num::NonZeroU16,
+	num::{
+		NonZeroU8,
+		NonZeroU16,
+	},
 	ops::RangeInclusive,
 };
 use super::track_idx_to_bits;
@@ -17,28 +22,112 @@ use super::track_idx_to_bits;
 
 
 /// # FLAG: Read Backwards.
-const FLAG_BACKWARDS: u8 =  0b0000_0001;
+const FLAG_BACKWARDS: u64 =   0b0000_0000_0000_0000_0000_0000_0000_0001;
 
 /// # FLAG: Flip Flop.
-const FLAG_FLIP_FLOP: u8 =  0b0000_0010;
+const FLAG_FLIP_FLOP: u64 =   0b0000_0000_0000_0000_0000_0000_0000_0010;
 
 /// # FLAG: Reset counts.
-const FLAG_RESET: u8 =      0b0000_0100;
+const FLAG_RESET: u64 =       0b0000_0000_0000_0000_0000_0000_0000_0100;
 
 /// # FLAG: Resume previous rip (when applicable).
-const FLAG_RESUME: u8 =     0b0000_1000;
+const FLAG_RESUME: u64 =      0b0000_0000_0000_0000_0000_0000_0000_1000;
 
 /// # FLAG: Strict C2 Mode.
-const FLAG_STRICT: u8 =     0b0001_0000;
+const FLAG_STRICT: u64 =      0b0000_0000_0000_0000_0000_0000_0001_0000;
 
 /// # FLAG: Subchannel Sync.
-const FLAG_SYNC: u8 =       0b0010_0000;
+const FLAG_SYNC: u64 =        0b0000_0000_0000_0000_0000_0000_0010_0000;
 
 /// # FLAG: Verbose.
-const FLAG_VERBOSE: u8 =    0b0100_0000;
+const FLAG_VERBOSE: u64 =     0b0000_0000_0000_0000_0000_0000_0100_0000;
+
+/// # FLAG: Lossy Copy.
+const FLAG_LOSSY: u64 =       0b0000_0000_0000_0000_0000_0000_1000_0000;
+
+/// # FLAG: Retry Lock.
+const FLAG_UNLOCK: u64 =      0b0000_0000_0000_0000_0000_0001_0000_0000;
+
+/// # FLAG: Paranoid Re-Read.
+const FLAG_PARANOID: u64 =    0b0000_0000_0000_0000_0000_0010_0000_0000;
+
+/// # FLAG: Automatic Passes.
+const FLAG_AUTO_PASSES: u64 = 0b0000_0000_0000_0000_0000_0100_0000_0000;
+
+/// # FLAG: Whole Disc.
+const FLAG_WHOLE_DISC: u64 =  0b0000_0000_0000_0000_0000_1000_0000_0000;
+
+/// # FLAG: Trim HTOA.
+const FLAG_TRIM_HTOA: u64 =   0b0000_0000_0000_0000_0001_0000_0000_0000;
+
+/// # FLAG: cdrdao TOC.
+const FLAG_CDRDAO_TOC: u64 =  0b0000_0000_0000_0000_0010_0000_0000_0000;
+
+/// # FLAG: Dump C2.
+const FLAG_DUMP_C2: u64 =     0b0000_0000_0000_0000_0100_0000_0000_0000;
+
+/// # FLAG: Single CTDB Match.
+const FLAG_CTDB_SINGLE: u64 = 0b0000_0000_0000_0000_1000_0000_0000_0000;
+
+/// # FLAG: No Overread.
+const FLAG_NO_OVERREAD: u64 = 0b0000_0000_0000_0001_0000_0000_0000_0000;
+
+/// # FLAG: Single File.
+const FLAG_SINGLE_FILE: u64 = 0b0000_0000_0000_0010_0000_0000_0000_0000;
+
+/// # FLAG: Durable Saves.
+const FLAG_DURABLE: u64 =     0b0000_0000_0000_0100_0000_0000_0000_0000;
+
+/// # FLAG: Confused Only.
+const FLAG_CONFUSED: u64 =    0b0000_0000_0000_1000_0000_0000_0000_0000;
+
+/// # FLAG: No AccurateRip.
+const FLAG_NO_AR: u64 =       0b0000_0000_0001_0000_0000_0000_0000_0000;
+
+/// # FLAG: No CTDB.
+const FLAG_NO_CTDB: u64 =     0b0000_0000_0010_0000_0000_0000_0000_0000;
+
+/// # FLAG: Skip Corrupt.
+const FLAG_SKIP_CORRUPT: u64 = 0b0000_0000_0100_0000_0000_0000_0000_0000;
+
+/// # FLAG: Broadcast Wave.
+const FLAG_BWF: u64 =         0b0000_0000_1000_0000_0000_0000_0000_0000;
+
+/// # FLAG: Retry Shitlisted.
+const FLAG_RETRY_SHIT: u64 =  0b0000_0001_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: C2 Error Pointers.
+const FLAG_C2: u64 =          0b0000_0010_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: WAV INFO.
+const FLAG_WAV_INFO: u64 =    0b0000_0100_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: Combined Sync Reads.
+const FLAG_SYNC_ONE: u64 =    0b0000_1000_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: Per-Track Log Files.
+const FLAG_LOG_FILES: u64 =   0b0001_0000_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: Interpolate Bad Samples.
+const FLAG_INTERPOLATE: u64 = 0b0010_0000_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: Export Verified Tracks Only.
+const FLAG_VERIFIED: u64 =    0b0100_0000_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: AccurateRip Submission File.
+const FLAG_SUBMIT: u64 =      0b1000_0000_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: SHA-256 Sidecars.
+const FLAG_SHA256: u64 =      0b0001_0000_0000_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: Append Tracks.
+const FLAG_APPEND: u64 =      0b0010_0000_0000_0000_0000_0000_0000_0000_0000;
+
+/// # FLAG: Manual Offset.
+const FLAG_MANUAL_OFFSET: u64 = 0b0100_0000_0000_0000_0000_0000_0000_0000_0000;
 
 /// # FLAG: Default.
-const FLAG_DEFAULT: u8 = FLAG_RESUME;
+const FLAG_DEFAULT: u64 = FLAG_RESUME | FLAG_C2;
 
 /// # Minimum Confidence.
 const CONFIDENCE_MIN: u8 = 1;
@@ -46,6 +135,9 @@ const CONFIDENCE_MIN: u8 = 1;
 /// # Maximum Confidence.
 const CONFIDENCE_MAX: u8 = 10;
 
+/// # Maximum Decay Age.
+const DECAY_MAX: u8 = 32;
+
 /// # Maximum Refine Passes.
 const PASSES_MAX: u8 = 16;
 
@@ -57,6 +149,148 @@ const REREADS_REL_MAX: u8 = 10;
 
 
 
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+/// # Cache Bust Mode.
+///
+/// This determines how the drive's read buffer gets cleared between passes.
+pub enum CacheBustMode {
+	#[default]
+	/// # Fill.
+	///
+	/// Read enough sectors outside the track range to cycle out the whole
+	/// cache. Slow, but reliable.
+	Fill,
+
+	/// # Seek.
+	///
+	/// Read a single sector from the far end of the disc. Much faster, but
+	/// only effective if the drive discards its buffer on a long seek.
+	Seek,
+}
+
+impl CacheBustMode {
+	#[must_use]
+	/// # As Str.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Fill => "fill",
+			Self::Seek => "seek",
+		}
+	}
+}
+
+
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+/// # C2 Granularity.
+///
+/// This determines how much of a sector a C2 error casts doubt upon.
+pub enum C2Granularity {
+	#[default]
+	/// # Sample.
+	///
+	/// Only the samples flagged by C2 are bad; the rest are taken at face
+	/// value.
+	Sample,
+
+	/// # Sector.
+	///
+	/// Samples flagged by C2 are bad, and the rest of the sector is suspect:
+	/// its values can fill in blanks, but won't count toward making existing
+	/// samples likely. (Unlike `RipOptions::strict`, nothing is thrown out.)
+	Sector,
+}
+
+impl C2Granularity {
+	#[must_use]
+	/// # As Str.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Sample => "sample",
+			Self::Sector => "sector",
+		}
+	}
+}
+
+
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+/// # Existing File Mode.
+///
+/// This determines what happens when an exported track's destination is
+/// already occupied by a file the rip didn't put there, e.g. one left by some
+/// other tool. (Riprip's own earlier exports are always replaced.)
+pub enum ExistingMode {
+	#[default]
+	/// # Overwrite.
+	///
+	/// Replace the file.
+	Overwrite,
+
+	/// # Skip.
+	///
+	/// Leave the file alone, using it as-is.
+	Skip,
+
+	/// # Suffix.
+	///
+	/// Leave the file alone, saving the track with a numeric suffix instead,
+	/// e.g. `{CDDB ID}__01-1.wav`.
+	Suffix,
+}
+
+impl ExistingMode {
+	#[must_use]
+	/// # As Str.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Overwrite => "overwrite",
+			Self::Skip => "skip",
+			Self::Suffix => "suffix",
+		}
+	}
+}
+
+
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+/// # HTOA Mode.
+///
+/// This determines what becomes of the hidden track one audio, if any.
+pub enum HtoaMode {
+	#[default]
+	/// # Separate.
+	///
+	/// Rip and export the HTOA as its own track, `{CDDB ID}__00.wav`, with
+	/// the cue sheet treating it as the first track's pregap.
+	Separate,
+
+	/// # Merge.
+	///
+	/// Rip the HTOA along with the first track, and export the two as one
+	/// continuous file, the HTOA becoming the file's `INDEX 00`.
+	Merge,
+
+	/// # Ignore.
+	///
+	/// Leave the HTOA alone entirely.
+	Ignore,
+}
+
+impl HtoaMode {
+	#[must_use]
+	/// # As Str.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Separate => "separate",
+			Self::Merge => "merge",
+			Self::Ignore => "ignore",
+		}
+	}
+}
+
+
+
 #[derive(Debug, Clone, Copy)]
 /// # Rip Options.
 ///
@@ -82,20 +316,62 @@ pub struct RipOptions {
 	/// # Read Offset.
 	offset: ReadOffset,
 
+	/// # Drive(s).
+	///
+	/// The drive — and second drive, if any — doing the ripping, for the
+	/// `bext` credits.
+	drives: [Option<DriveVendorModel>; 2],
+
 	/// # Cache Size.
 	cache: Option<NonZeroU16>,
 
+	/// # Cache Bust Mode.
+	cache_bust_mode: CacheBustMode,
+
+	/// # C2 Granularity.
+	c2_granularity: C2Granularity,
+
 	/// # Minimum Checksum Confidence.
 	confidence: u8,
 
+	/// # Maximum CTDB Shift (Samples).
+	ctdb_shift: u16,
+
+	/// # Decay Age.
+	decay: Option<NonZeroU8>,
+
+	/// # Minimum Track Length (Sectors).
+	min_sectors: u16,
+
+	/// # Read Speed.
+	speed: u16,
+
 	/// # Re/Read Attempts.
 	rereads: (u8, u8),
 
+	/// # Strict After Pass.
+	strict_after: Option<NonZeroU8>,
+
 	/// # Passes.
 	passes: u8,
 
+	/// # Minimum (Forced) Passes.
+	min_passes: u8,
+
+	/// # Start Fresh From Track.
+	from_track: Option<NonZeroU8>,
+
+	/// # Export Bit Depth.
+	bits: u8,
+
+	/// # Existing File Mode.
+	existing: ExistingMode,
+
+	/// # HTOA Mode.
+	htoa: HtoaMode,
+
 	/// # Flags.
-	flags: u8,
+	flags: u64,
 
 	/// # Tracks.
 	tracks: u128,
@@ -105,10 +381,23 @@ impl Default for RipOptions {
 	fn default() -> Self {
 		Self {
 			offset: ReadOffset::default(),
+			drives: [None, None],
 			cache: None,
+			cache_bust_mode: CacheBustMode::Fill,
+			c2_granularity: C2Granularity::Sample,
 			confidence: 3,
+			ctdb_shift: SAMPLE_OVERREAD,
+			decay: None,
+			min_sectors: 0,
+			speed: 0,
+			strict_after: None,
 			rereads: (2, 2),
 			passes: 1,
+			min_passes: 0,
+			from_track: None,
+			bits: 16,
+			existing: ExistingMode::Overwrite,
+			htoa: HtoaMode::Separate,
 			flags: FLAG_DEFAULT,
 			tracks: 0,
 		}
@@ -137,6 +426,18 @@ macro_rules! with_flag {
 
 /// ## Setters.
 impl RipOptions {
+	with_flag!(
+		with_append_tracks,
+		FLAG_APPEND,
+		"# Append Tracks.",
+		"",
+		"When `true`, a previously-exported track will only be overwritten if",
+		"the new rip is at least as good, per the quality recorded in the state",
+		"at the time of the last export. This doesn't apply to whole-disc rips.",
+		"",
+		"The default is `false`.",
+	);
+
 	with_flag!(
 		with_backwards,
 		FLAG_BACKWARDS,
@@ -148,6 +449,33 @@ impl RipOptions {
 		"The default is `false`.",
 	);
 
+	with_flag!(
+		with_bwf,
+		FLAG_BWF,
+		"# Broadcast Wave.",
+		"",
+		"When `true`, exported tracks will include a Broadcast Wave `bext`",
+		"chunk — noting the rip date, drive, and AccurateRip/CTDB",
+		"confidences — for archival provenance.",
+		"",
+		"The default is `false`, i.e. a bare WAV header, for compatibility.",
+	);
+
+	with_flag!(
+		with_c2,
+		FLAG_C2,
+		"# C2 Error Pointers.",
+		"",
+		"When `true`, sectors are read along with their C2 error pointers so",
+		"that damaged samples can be identified and re-read. When `false`,",
+		"all reads are assumed to be error-free.",
+		"",
+		"If the drive turns out not to support C2, it will be disabled",
+		"automatically — with a warning — for the rest of the run.",
+		"",
+		"The default is `true`.",
+	);
+
 	#[must_use]
 	/// # With Cache Size.
 	///
@@ -162,6 +490,66 @@ impl RipOptions {
 		}
 	}
 
+	#[must_use]
+	/// # Cache Bust Mode.
+	///
+	/// Set the strategy used to clear the drive's read buffer. See
+	/// `CacheBustMode` for details.
+	///
+	/// This has no effect unless a cache size has been set. The default is
+	/// `CacheBustMode::Fill`.
+	pub const fn with_cache_bust_mode(self, cache_bust_mode: CacheBustMode) -> Self {
+		Self {
+			cache_bust_mode,
+			..self
+		}
+	}
+
+	#[must_use]
+	/// # C2 Granularity.
+	///
+	/// Set how much of a sector a C2 error casts doubt upon. See
+	/// `C2Granularity` for details.
+	///
+	/// This has no effect on strict passes, which mark the whole sector bad.
+	/// The default is `C2Granularity::Sample`.
+	pub const fn with_c2_granularity(self, c2_granularity: C2Granularity) -> Self {
+		Self {
+			c2_granularity,
+			..self
+		}
+	}
+
+	#[must_use]
+	/// # Existing File Mode.
+	///
+	/// Set what to do when an exported track's destination is already taken
+	/// by a file from somewhere else. See `ExistingMode` for details.
+	///
+	/// The default is `ExistingMode::Overwrite`.
+	pub const fn with_existing(self, existing: ExistingMode) -> Self {
+		Self {
+			existing,
+			..self
+		}
+	}
+
+	#[must_use]
+	/// # HTOA Mode.
+	///
+	/// Set what to do with the HTOA, if any. See `HtoaMode` for details.
+	///
+	/// Note that `RipOptions::trim_htoa` has no effect on a merged HTOA, as
+	/// that would break the continuity with the first track.
+	///
+	/// The default is `HtoaMode::Separate`.
+	pub const fn with_htoa_mode(self, htoa: HtoaMode) -> Self {
+		Self {
+			htoa,
+			..self
+		}
+	}
+
 	#[must_use]
 	/// # Confirmation Confidence.
 	///
@@ -187,6 +575,150 @@ impl RipOptions {
 		}
 	}
 
+	with_flag!(
+		with_confused_only,
+		FLAG_CONFUSED,
+		"# Confused Sectors Only.",
+		"",
+		"When `true`, only sectors containing confused samples — those whose",
+		"reads have been so contradictory as to trigger strict handling — will",
+		"be re-read, and only until those samples become likely. Everything",
+		"else is left alone.",
+		"",
+		"This is a focused recovery tool for breaking stubborn ties, best",
+		"paired with `RipOptions::with_sync`.",
+		"",
+		"The default is `false`.",
+	);
+
+	#[must_use]
+	/// # Count Decay.
+	///
+	/// Drives can drift over time, so a value that was seen a bunch of times
+	/// long ago and never again might not be as trustworthy as its count
+	/// suggests.
+	///
+	/// When set, each maybe sample keeps track of the number of passes since
+	/// any of its values were last seen. Once that reaches this number, its
+	/// counts are halved — no lower than one — giving fresher reads a chance
+	/// to catch up.
+	///
+	/// Like `with_reset`, this has no effect on tracks that have already been
+	/// confirmed by AccurateRip and/or CUETools.
+	///
+	/// Set to zero to disable. Also the default.
+	///
+	/// Values are capped to `0..=32`.
+	pub const fn with_decay(self, mut decay: u8) -> Self {
+		if DECAY_MAX < decay { decay = DECAY_MAX; }
+		Self {
+			decay: NonZeroU8::new(decay),
+			..self
+		}
+	}
+
+	with_flag!(
+		with_cdrdao_toc,
+		FLAG_CDRDAO_TOC,
+		"# cdrdao TOC.",
+		"",
+		"When `true`, a cdrdao-style `.toc` file — with barcode, ISRCs, and",
+		"CD-Text, where available — will be saved alongside the `.cue` sheet",
+		"once all tracks have been ripped.",
+		"",
+		"The default is `false`.",
+	);
+
+	#[must_use]
+	/// # Maximum CTDB Shift.
+	///
+	/// When verifying a rip against CUETools, the checksums are also tested
+	/// at every sample shift within `±N` of the read offset, to account for
+	/// pressing differences. Lowering this can speed up verification for
+	/// well-aligned rips; zero checks the exact offset only.
+	///
+	/// Values are capped by the available padding — `0..=5880` — which is
+	/// also the default.
+	pub const fn with_ctdb_shift(self, mut ctdb_shift: u16) -> Self {
+		if SAMPLE_OVERREAD < ctdb_shift { ctdb_shift = SAMPLE_OVERREAD; }
+		Self {
+			ctdb_shift,
+			..self
+		}
+	}
+
+	#[must_use]
+	/// # Minimum Track Length.
+	///
+	/// Tracks shorter than this many sectors — e.g. stray index markers
+	/// rather than real audio — will be skipped entirely, with a warning.
+	///
+	/// The default is zero, i.e. keep everything.
+	pub const fn with_min_sectors(self, min_sectors: u16) -> Self {
+		Self {
+			min_sectors,
+			..self
+		}
+	}
+
+	#[must_use]
+	/// # Read Speed.
+	///
+	/// Ask the drive(s) to read at `N` times the standard CD speed for the
+	/// duration of the rip. Slower reads — e.g. 1x — often recover more from
+	/// damaged discs. Drives are free to round or ignore the value.
+	///
+	/// The default is zero, i.e. leave the drive as it is.
+	pub const fn with_speed(self, speed: u16) -> Self {
+		Self {
+			speed,
+			..self
+		}
+	}
+
+	with_flag!(
+		with_ctdb_single,
+		FLAG_CTDB_SINGLE,
+		"# Accept Single CTDB Match.",
+		"",
+		"CUETools submissions are unvetted, so by default a CTDB confidence",
+		"of `1` is treated as no match at all. When `true`, that floor is",
+		"lowered so a lone match counts — regardless of the general",
+		"confidence threshold — which can be useful for obscure discs where",
+		"that's all there is.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_dump_c2,
+		FLAG_DUMP_C2,
+		"# Dump C2 Error Maps.",
+		"",
+		"When `true`, the raw C2 error bits for every sector read will be",
+		"appended to a per-track `.c2` file in the scratch directory, for",
+		"drive diagnostics. Each record is the (little-endian `i32`) read LSN,",
+		"the (`u8`) pass number, and the 294-byte C2 bitmap.",
+		"",
+		"This generates a lot of data, and is not supported in whole-disc or",
+		"sector range modes.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_durable_saves,
+		FLAG_DURABLE,
+		"# Durable Saves.",
+		"",
+		"When `true`, state and track files are fsynced before and after being",
+		"swapped into place, so a \"saved\" message actually means the data",
+		"will survive a power loss. This is slower, particularly with lots of",
+		"small state saves.",
+		"",
+		"The default is `false`.",
+	);
+
 	with_flag!(
 		with_flip_flop,
 		FLAG_FLIP_FLOP,
@@ -198,6 +730,104 @@ impl RipOptions {
 		"The default is `false`.",
 	);
 
+	with_flag!(
+		with_interpolate,
+		FLAG_INTERPOLATE,
+		"# Interpolate Bad Samples.",
+		"",
+		"When `true`, any bad or unread samples remaining in a track will be",
+		"papered over with a straight line between the nearest allegedly-good",
+		"neighbors when the WAV is saved. This is a last resort for otherwise",
+		"unrecoverable discs; the result is _not_ an accurate copy, and the",
+		"number of samples so treated is noted in the WAV's `LIST`/`INFO`",
+		"comment.",
+		"",
+		"The rip state itself is not altered; this only affects the export.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_log_files,
+		FLAG_LOG_FILES,
+		"# Per-Track Log Files.",
+		"",
+		"When `true`, the problematic sectors and heatmap for each track will",
+		"be appended to a `{cddb_id}__{track}.log` sidecar in the scratch",
+		"directory after each pass, independently of (and in addition to)",
+		"`RipOptions::with_verbose`.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_lossy,
+		FLAG_LOSSY,
+		"# Lossy Copy.",
+		"",
+		"When `true`, an Ogg Vorbis copy of each track will be saved alongside",
+		"the WAV, for casual listening. This happens after the final pass, or",
+		"as soon as the track is confirmed, whichever comes first.",
+		"",
+		"Encoding failures are reported, but won't abort the rip.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_no_accuraterip,
+		FLAG_NO_AR,
+		"# Skip AccurateRip.",
+		"",
+		"When `true`, rips will not be checked against the AccurateRip",
+		"database, saving a pointless lookup when offline or when the service",
+		"is down. Tracks can still be confirmed by CUETools, unless that has",
+		"been disabled too.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_no_ctdb,
+		FLAG_NO_CTDB,
+		"# Skip CUETools.",
+		"",
+		"When `true`, rips will not be checked against the CUETools database.",
+		"Tracks can still be confirmed by AccurateRip, unless that has been",
+		"disabled too.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_no_overread,
+		FLAG_NO_OVERREAD,
+		"# No Overread.",
+		"",
+		"Each track is normally padded with ten sectors on either side to",
+		"support offset-shifted CUETools matching. When `true`, those padding",
+		"sectors are never read, saving time and keeping drives that choke on",
+		"out-of-range requests happy, at the expense of less reliable shifted",
+		"CUETools matches.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_paranoid,
+		FLAG_PARANOID,
+		"# Paranoid Re-Read.",
+		"",
+		"When `true`, every sector of every track — even confirmed ones — will",
+		"be re-read one final time after the last pass, and any sector whose",
+		"fresh (error-free) read contradicts the current value will be",
+		"flagged.",
+		"",
+		"The data is left alone unless a contradiction is found.",
+		"",
+		"The default is `false`.",
+	);
+
 	#[must_use]
 	/// # Read Offset.
 	///
@@ -214,6 +844,29 @@ impl RipOptions {
 		}
 	}
 
+	with_flag!(
+		with_manual_offset,
+		FLAG_MANUAL_OFFSET,
+		"# Manual Offset.",
+		"",
+		"Set to `true` when the read offset came from the user rather than the",
+		"drive database, so the cue sheet (and reports) can say as much.",
+		"",
+		"The default is `false`.",
+	);
+
+	#[must_use]
+	/// # Drive(s).
+	///
+	/// Set the drive — and second drive, if any — doing the ripping, to be
+	/// credited in `bext` chunks. `Disc` takes care of this.
+	pub(crate) const fn with_drives(self, drives: [Option<DriveVendorModel>; 2]) -> Self {
+		Self {
+			drives,
+			..self
+		}
+	}
+
 	#[must_use]
 	/// # Number of Passes.
 	///
@@ -240,6 +893,61 @@ impl RipOptions {
 		}
 	}
 
+	#[must_use]
+	/// # Minimum Passes.
+	///
+	/// Force at least this many passes over each track, re-reading _every_
+	/// sector — confirmed or not — each time, to build up some local
+	/// confidence on top of AccurateRip/CUETools. Tracks are only skipped
+	/// for confirmation once these passes are done.
+	///
+	/// If this exceeds `RipOptions::with_passes`, it takes its place.
+	///
+	/// The default is `0`, i.e. disabled.
+	///
+	/// Values are capped to `0..=16`.
+	pub const fn with_min_passes(self, mut min_passes: u8) -> Self {
+		if PASSES_MAX < min_passes { min_passes = PASSES_MAX; }
+		Self {
+			min_passes,
+			..self
+		}
+	}
+
+	#[must_use]
+	/// # Export Bit Depth.
+	///
+	/// Save the WAVs with `24` or `32` bits per sample instead of the usual
+	/// `16`, for DAWs and the like that would rather not deal with the
+	/// latter. The CD samples are simply left-shifted into the bigger
+	/// containers, so nothing is lost (or gained).
+	///
+	/// Anything other than `24` or `32` is treated as `16`.
+	pub const fn with_bits(self, bits: u8) -> Self {
+		let bits = match bits {
+			24 | 32 => bits,
+			_ => 16,
+		};
+		Self {
+			bits,
+			..self
+		}
+	}
+
+	with_flag!(
+		with_auto_passes,
+		FLAG_AUTO_PASSES,
+		"# Automatic Passes.",
+		"",
+		"When `true`, the number of passes is treated as open-ended — up to the",
+		"usual maximum of `16` — with ripping stopping early as soon as a full",
+		"pass fails to change anything.",
+		"",
+		"This overrides `RipOptions::with_passes`.",
+		"",
+		"The default is `false`.",
+	);
+
 	#[must_use]
 	/// # Likeliness Re-Read Cutoff.
 	///
@@ -302,6 +1010,66 @@ impl RipOptions {
 		"The default is `true`.",
 	);
 
+	#[must_use]
+	/// # Start Fresh From Track.
+	///
+	/// Resume the previous states for tracks before this one as usual, but
+	/// start this track and everything after it over from scratch, as if
+	/// `RipOptions::with_resume` were `false` for them alone.
+	///
+	/// This has no effect on whole-disc rips, which only have the one state,
+	/// or if resuming is disabled altogether.
+	///
+	/// Set to zero to disable. Also the default.
+	pub const fn with_from_track(self, idx: u8) -> Self {
+		Self {
+			from_track: NonZeroU8::new(idx),
+			..self
+		}
+	}
+
+	with_flag!(
+		with_retry_shitlisted,
+		FLAG_RETRY_SHIT,
+		"# Retry Shitlisted Sectors.",
+		"",
+		"Sectors that trigger hard read errors are remembered and avoided for",
+		"the rest of the run. When `true`, that list is cleared at the start",
+		"of each pass so they get another chance, e.g. after the disc has",
+		"been reseated or cleaned.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_single_file,
+		FLAG_SINGLE_FILE,
+		"# Single-File Album.",
+		"",
+		"When `true`, once all tracks have been ripped, they will also be",
+		"joined into a single gapless `.wav` — HTOA included — with a matching",
+		"cue sheet marking where each track begins.",
+		"",
+		"Note that this cannot be combined with `RipOptions::trim_htoa` when",
+		"the HTOA is exported separately, as the shortened HTOA would throw",
+		"off every track position; no album will be saved in that case.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_skip_corrupt,
+		FLAG_SKIP_CORRUPT,
+		"# Skip Corrupt States.",
+		"",
+		"A track whose state data (and backup) is corrupt normally brings the",
+		"whole rip to a halt. When `true`, such tracks are skipped instead —",
+		"with a warning — so the rest can carry on. They are listed again in",
+		"the final summary.",
+		"",
+		"The default is `false`.",
+	);
+
 	with_flag!(
 		with_strict,
 		FLAG_STRICT,
@@ -313,6 +1081,48 @@ impl RipOptions {
 		"The default is `false`.",
 	);
 
+	#[must_use]
+	/// # Strict C2 (Sector) After Pass.
+	///
+	/// Same as `RipOptions::with_strict`, but only from pass `N` onward. This
+	/// allows the first few passes to be lenient — accepting partial sector
+	/// data — and later ones strict, to help shake out inconsistencies.
+	///
+	/// This has no effect if `RipOptions::with_strict` is enabled.
+	///
+	/// Set to zero to disable. Also the default.
+	pub const fn with_strict_after(self, pass: u8) -> Self {
+		Self {
+			strict_after: NonZeroU8::new(pass),
+			..self
+		}
+	}
+
+	with_flag!(
+		with_sha256,
+		FLAG_SHA256,
+		"# SHA-256 Sidecars.",
+		"",
+		"When `true`, each exported track will be re-read from disk after saving",
+		"and its SHA-256 hash written to a `sha256sum`-compatible sidecar",
+		"sitting right next to it, e.g. `01.wav.sha256`.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_submit_file,
+		FLAG_SUBMIT,
+		"# AccurateRip Submission File.",
+		"",
+		"When `true` and every track on the disc has been verified, the v1 and",
+		"v2 AccurateRip checksums will be written to a dBAR-style binary",
+		"alongside the cue sheet, ready for contributing back via a supported",
+		"tool. (Nothing is uploaded.)",
+		"",
+		"The default is `false`.",
+	);
+
 	with_flag!(
 		with_sync,
 		FLAG_SYNC,
@@ -326,6 +1136,25 @@ impl RipOptions {
 		"useful in cases where disc rot, rather than wear-and-tear, is the sole",
 		"cause of readability issues.",
 		"",
+		"Only subchannel frames carrying a timecode can be verified; if most",
+		"reads lack one, a warning will be printed at the end of the rip.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_sync_combined,
+		FLAG_SYNC_ONE,
+		"# Combined Sync Reads.",
+		"",
+		"Subchannel-synced reads normally take two trips to the drive: one for",
+		"the data and subchannel, and another for the data and C2. When `true`,",
+		"the data, C2, and subchannel are requested together in a single",
+		"block instead, halving the reads per sector for drives that support",
+		"it. (Drives that don't will quietly fall back to the usual method.)",
+		"",
+		"This has no effect unless paired with `RipOptions::with_sync`.",
+		"",
 		"The default is `false`.",
 	);
 
@@ -341,6 +1170,23 @@ impl RipOptions {
 		}
 	}
 
+	#[must_use]
+	/// # Set Tracks.
+	///
+	/// Replace the to-rip list with the given track numbers in one go. Any
+	/// previously-added tracks are cleared first.
+	///
+	/// As with `RipOptions::with_track`, numbers above `99` are ignored.
+	pub fn with_tracks<I>(self, tracks: I) -> Self
+	where I: IntoIterator<Item=u8> {
+		let tracks = tracks.into_iter()
+			.fold(0, |acc, track| acc | track_idx_to_bits(track));
+		Self {
+			tracks,
+			..self
+		}
+	}
+
 	#[must_use]
 	/// # Exclude Track.
 	///
@@ -357,6 +1203,32 @@ impl RipOptions {
 		}
 	}
 
+	with_flag!(
+		with_trim_htoa,
+		FLAG_TRIM_HTOA,
+		"# Trim HTOA.",
+		"",
+		"When `true`, leading and trailing silence will be trimmed from the",
+		"exported HTOA WAV, if any. The rip state itself is left alone, as are",
+		"all of the regular tracks.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_unlock,
+		FLAG_UNLOCK,
+		"# Retry Lock.",
+		"",
+		"Rips are locked per-disc to keep concurrent processes from clobbering",
+		"each other's state. The lock is released automatically when the",
+		"process holding it exits, so it is never forcibly taken; set this to",
+		"`true` to try once more after a short pause — in case the holder was",
+		"just finishing up — and report who has it if it's still held.",
+		"",
+		"The default is `false`.",
+	);
+
 	with_flag!(
 		with_verbose,
 		FLAG_VERBOSE,
@@ -367,6 +1239,57 @@ impl RipOptions {
 		"",
 		"The default is `false`.",
 	);
+
+	with_flag!(
+		with_verified_only,
+		FLAG_VERIFIED,
+		"# Export Verified Tracks Only.",
+		"",
+		"When `true`, tracks will only be exported — WAV, lossy copy, etc. —",
+		"once they have been verified by AccurateRip and/or CUETools. The",
+		"rest are left in the cache for more passes later, and listed as",
+		"pending in the summary.",
+		"",
+		"Because the HTOA can't be verified, it is never exported in this",
+		"mode.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_wav_info,
+		FLAG_WAV_INFO,
+		"# WAV INFO.",
+		"",
+		"When `true`, exported tracks will include a RIFF `LIST`/`INFO` chunk",
+		"noting the AccurateRip and CTDB confidences and pass count (`ICMT`),",
+		"and the Rip Rip version (`ISFT`), so their provenance can be audited",
+		"from the files alone.",
+		"",
+		"The default is `false`.",
+	);
+
+	with_flag!(
+		with_whole_disc,
+		FLAG_WHOLE_DISC,
+		"# Whole Disc.",
+		"",
+		"When `true`, all of the audio on the disc will be ripped as a single",
+		"continuous stream, then sliced into individual tracks at export.",
+		"",
+		"This avoids re-reading the overlap between adjacent tracks and keeps",
+		"the offset handling consistent across boundaries, which can help",
+		"with gapless albums.",
+		"",
+		"The (per-track) logging, lossy copies, and paranoid re-reads are not",
+		"supported in this mode. Any remaining passes are skipped once every",
+		"track has been verified.",
+		"",
+		"Note that the whole disc's state is held in memory at once, which",
+		"can run to several gigabytes for a full-length CD.",
+		"",
+		"The default is `false`.",
+	);
 }
 
 
@@ -381,18 +1304,72 @@ macro_rules! get_flag {
 
 /// # Getters.
 impl RipOptions {
+	get_flag!(append_tracks, FLAG_APPEND, "Append Tracks");
+	get_flag!(auto_passes, FLAG_AUTO_PASSES, "Automatic Passes");
 	get_flag!(backwards, FLAG_BACKWARDS, "Rip Backwards");
+	get_flag!(bwf, FLAG_BWF, "Broadcast Wave");
+	get_flag!(c2, FLAG_C2, "C2 Error Pointers");
+	get_flag!(cdrdao_toc, FLAG_CDRDAO_TOC, "cdrdao TOC");
+	get_flag!(confused_only, FLAG_CONFUSED, "Confused Sectors Only");
+	get_flag!(ctdb_single, FLAG_CTDB_SINGLE, "Accept Single CTDB Match");
+	get_flag!(dump_c2, FLAG_DUMP_C2, "Dump C2 Error Maps");
+	get_flag!(durable_saves, FLAG_DURABLE, "Durable Saves");
 	get_flag!(strict, FLAG_STRICT, "Strict C2 Error Pointers");
 	get_flag!(flip_flop, FLAG_FLIP_FLOP, "Alternate Rip Read Order");
+	get_flag!(interpolate, FLAG_INTERPOLATE, "Interpolate Bad Samples");
+	get_flag!(log_files, FLAG_LOG_FILES, "Per-Track Log Files");
+	get_flag!(lossy, FLAG_LOSSY, "Lossy Copy");
+	get_flag!(manual_offset, FLAG_MANUAL_OFFSET, "Manual Offset");
+	get_flag!(no_accuraterip, FLAG_NO_AR, "Skip AccurateRip");
+	get_flag!(no_ctdb, FLAG_NO_CTDB, "Skip CUETools");
+	get_flag!(no_overread, FLAG_NO_OVERREAD, "No Overread");
+	get_flag!(paranoid, FLAG_PARANOID, "Paranoid Re-Read");
 	get_flag!(reset, FLAG_RESET, "Reset Counts");
+	get_flag!(retry_shitlisted, FLAG_RETRY_SHIT, "Retry Shitlisted Sectors");
 	get_flag!(resume, FLAG_RESUME, "Resume Previous Rip");
+	get_flag!(single_file, FLAG_SINGLE_FILE, "Single-File Album");
+	get_flag!(skip_corrupt, FLAG_SKIP_CORRUPT, "Skip Corrupt States");
+	get_flag!(sha256, FLAG_SHA256, "SHA-256 Sidecars");
+	get_flag!(submit_file, FLAG_SUBMIT, "AccurateRip Submission File");
 	get_flag!(sync, FLAG_SYNC, "Subchannel Sync");
+	get_flag!(sync_combined, FLAG_SYNC_ONE, "Combined Sync Reads");
+	get_flag!(trim_htoa, FLAG_TRIM_HTOA, "Trim HTOA");
+	get_flag!(unlock, FLAG_UNLOCK, "Retry Lock");
 	get_flag!(verbose, FLAG_VERBOSE, "Verbose (Log) Mode");
+	get_flag!(verified_only, FLAG_VERIFIED, "Export Verified Tracks Only");
+	get_flag!(wav_info, FLAG_WAV_INFO, "WAV INFO");
+	get_flag!(whole_disc, FLAG_WHOLE_DISC, "Whole Disc");
 
 	#[must_use]
 	/// # Cache Size.
 	pub const fn cache(&self) -> Option<NonZeroU16> { self.cache }
 
+	#[must_use]
+	/// # Cache Bust Mode.
+	pub const fn cache_bust_mode(&self) -> CacheBustMode { self.cache_bust_mode }
+
+	#[must_use]
+	/// # C2 Granularity.
+	pub const fn c2_granularity(&self) -> C2Granularity { self.c2_granularity }
+
+	#[must_use]
+	/// # Tentative Read?
+	///
+	/// Returns `true` if the good samples from a read should only be taken
+	/// tentatively, i.e. it had C2 errors and `C2Granularity::Sector` is in
+	/// effect.
+	pub(crate) const fn c2_tentative(&self, all_good: bool) -> bool {
+		! all_good && matches!(self.c2_granularity, C2Granularity::Sector)
+	}
+
+	#[must_use]
+	/// # Existing File Mode.
+	pub const fn existing(&self) -> ExistingMode { self.existing }
+
+	#[must_use]
+	/// # HTOA Mode.
+	pub const fn htoa_mode(&self) -> HtoaMode { self.htoa }
+
 	#[must_use]
 	/// # Cache Sectors.
 	///
@@ -404,21 +1381,114 @@ impl RipOptions {
 		else { 0 }
 	}
 
+	#[must_use]
+	/// # Logging?
+	///
+	/// Returns `true` if the problematic sectors should be logged anywhere,
+	/// i.e. `RipOptions::verbose` and/or `RipOptions::log_files`.
+	pub const fn logging(&self) -> bool { self.verbose() || self.log_files() }
+
 	#[must_use]
 	/// # Minimum AccurateRip/CTDB Confidence.
 	pub const fn confidence(&self) -> u8 { self.confidence }
 
+	#[must_use]
+	/// # Minimum CTDB Confidence.
+	///
+	/// This is the same as `RipOptions::confidence`, except when
+	/// `RipOptions::ctdb_single` is enabled, in which case it is `1`.
+	pub const fn ctdb_confidence(&self) -> u16 {
+		if self.ctdb_single() { 1 }
+		else { self.confidence as u16 }
+	}
+
+	#[must_use]
+	/// # Maximum CTDB Shift.
+	pub const fn ctdb_shift(&self) -> u16 { self.ctdb_shift }
+
+	#[must_use]
+	/// # Minimum Track Length.
+	pub const fn min_sectors(&self) -> u16 { self.min_sectors }
+
+	#[must_use]
+	/// # Read Speed.
+	pub const fn speed(&self) -> u16 { self.speed }
+
+	#[must_use]
+	/// # Count Decay.
+	pub const fn decay(&self) -> Option<NonZeroU8> { self.decay }
+
+	#[must_use]
+	/// # Start Fresh From Track.
+	pub const fn from_track(&self) -> Option<NonZeroU8> { self.from_track }
+
+	#[must_use]
+	/// # Resume Track?
+	///
+	/// Returns `true` if the previous state for track `idx` should be
+	/// resumed, i.e. resuming is enabled and the track comes before
+	/// `RipOptions::from_track`, if any.
+	pub const fn resume_track(&self, idx: u8) -> bool {
+		self.resume() &&
+		match self.from_track {
+			Some(from) => idx < from.get(),
+			None => true,
+		}
+	}
+
+	#[must_use]
+	/// # Strict C2 After Pass.
+	pub const fn strict_after(&self) -> Option<NonZeroU8> { self.strict_after }
+
+	#[must_use]
+	/// # Strict C2 For Pass?
+	///
+	/// Returns `true` if strict C2 handling applies to the given pass, either
+	/// because `RipOptions::strict` is enabled, or the pass is at or beyond
+	/// `RipOptions::strict_after`.
+	pub const fn strict_pass(&self, pass: u8) -> bool {
+		if self.strict() { true }
+		else if let Some(n) = self.strict_after { n.get() <= pass }
+		else { false }
+	}
+
 	#[must_use]
 	/// # Has Any Tracks?
 	pub const fn has_tracks(&self) -> bool { self.tracks != 0 }
 
+	#[must_use]
+	/// # Export Bit Depth.
+	pub const fn bits(&self) -> u8 { self.bits }
+
 	#[must_use]
 	/// # Read Offset.
 	pub const fn offset(&self) -> ReadOffset { self.offset }
 
+	#[must_use]
+	/// # Drive(s).
+	pub(crate) const fn drives(&self) -> [Option<DriveVendorModel>; 2] { self.drives }
+
 	#[must_use]
 	/// # Number of Passes.
-	pub const fn passes(&self) -> u8 { self.passes }
+	///
+	/// If automatic passes are enabled, this will return the maximum. It
+	/// will never be less than `RipOptions::min_passes`.
+	pub const fn passes(&self) -> u8 {
+		if self.auto_passes() { PASSES_MAX }
+		else if self.passes < self.min_passes { self.min_passes }
+		else { self.passes }
+	}
+
+	#[must_use]
+	/// # Minimum Passes.
+	pub const fn min_passes(&self) -> u8 { self.min_passes }
+
+	#[must_use]
+	/// # Forced Pass?
+	///
+	/// Returns `true` if (one-based) `pass` is within the minimum, i.e. every
+	/// sector needs to be read regardless of quality or confirmation.
+	pub const fn forced_pass(&self, pass: u8) -> bool { pass <= self.min_passes }
 
 	#[must_use]
 	/// # Likeliness Reread Cutoffs.
@@ -452,6 +1522,7 @@ impl RipOptions {
 /// # Misc.
 impl RipOptions {
 	#[must_use]
+	#[expect(clippy::too_many_lines, reason = "There are a lot of options.")]
 	/// # CLI String.
 	///
 	/// Convert the options back into a list of arguments in CLI format. This
@@ -483,25 +1554,86 @@ impl RipOptions {
 		let mut opts = String::with_capacity(256);
 
 		// All the easy stuff.
+		if self.lossy() { opts.push_str("--also-lossy "); }
+		if self.append_tracks() { opts.push_str("--append-tracks "); }
 		if self.backwards() { opts.push_str("--backwards "); }
+		if self.bits != 16 { write!(&mut opts, "--bits={} ", self.bits).unwrap(); }
+		if self.bwf() { opts.push_str("--bwf "); }
 		if let Some(cache) = self.cache {
 			write!(&mut opts, "-c{cache} ").unwrap();
+			if matches!(self.cache_bust_mode, CacheBustMode::Seek) {
+				opts.push_str("--cache-bust-mode=seek ");
+			}
+		}
+		if matches!(self.c2_granularity, C2Granularity::Sector) {
+			opts.push_str("--c2-granularity=sector ");
 		}
 		write!(&mut opts, "--confidence={} ", self.confidence()).unwrap();
+		if self.confused_only() { opts.push_str("--confused-only "); }
+		if self.ctdb_shift != SAMPLE_OVERREAD {
+			write!(&mut opts, "--ctdb-shift={} ", self.ctdb_shift).unwrap();
+		}
+		if self.ctdb_single() { opts.push_str("--ctdb-single "); }
+		if let Some(decay) = self.decay {
+			write!(&mut opts, "--decay={decay} ").unwrap();
+		}
+		if self.dump_c2() { opts.push_str("--dump-c2 "); }
+		if self.durable_saves() { opts.push_str("--durable-saves "); }
 		if self.flip_flop() { opts.push_str("--flip-flop "); }
+		if let Some(from) = self.from_track {
+			write!(&mut opts, "--from-track={from} ").unwrap();
+		}
+		if ! matches!(self.htoa, HtoaMode::Separate) {
+			write!(&mut opts, "--htoa={} ", self.htoa.as_str()).unwrap();
+		}
+		if self.interpolate() { opts.push_str("--interpolate "); }
+		if self.log_files() { opts.push_str("--log-files "); }
+		if self.min_passes != 0 {
+			write!(&mut opts, "--min-passes={} ", self.min_passes).unwrap();
+		}
+		if self.min_sectors != 0 {
+			write!(&mut opts, "--min-track-sectors={} ", self.min_sectors).unwrap();
+		}
+		if self.no_accuraterip() { opts.push_str("--no-accuraterip "); }
+		if self.no_ctdb() { opts.push_str("--no-ctdb "); }
+		if self.no_overread() { opts.push_str("--no-overread "); }
 		if ! self.resume() { opts.push_str("--no-resume "); }
+		if ! matches!(self.existing, ExistingMode::Overwrite) {
+			write!(&mut opts, "--on-existing={} ", self.existing.as_str()).unwrap();
+		}
 
+		// An explicit zero still counts, since it overrides the drive's
+		// detected offset.
 		let offset = self.offset().samples();
-		if offset != 0 { write!(&mut opts, "-o{offset} ").unwrap(); }
+		if offset != 0 || self.manual_offset() {
+			write!(&mut opts, "-o{offset} ").unwrap();
+		}
 
-		write!(&mut opts, "-p{} ", self.passes()).unwrap();
+		if self.auto_passes() { opts.push_str("-pauto "); }
+		else { write!(&mut opts, "-p{} ", self.passes()).unwrap(); }
+		if self.paranoid() { opts.push_str("--paranoid "); }
 
 		let rr = self.rereads();
 		write!(&mut opts, "-r{},{} ", rr.0, rr.1).unwrap();
 
 		if self.reset() { opts.push_str("--reset-counts "); }
+		if self.retry_shitlisted() { opts.push_str("--retry-shitlisted "); }
+		if self.sha256() { opts.push_str("--sha256 "); }
+		if self.single_file() { opts.push_str("--single-file "); }
+		if self.skip_corrupt() { opts.push_str("--skip-corrupt "); }
+		if self.speed != 0 { write!(&mut opts, "--speed={} ", self.speed).unwrap(); }
 		if self.strict() { opts.push_str("--strict-c2 "); }
+		else if let Some(n) = self.strict_after {
+			write!(&mut opts, "--strict-after={n} ").unwrap();
+		}
+		if self.submit_file() { opts.push_str("--submit-file "); }
 		if self.sync() { opts.push_str("--sync "); }
+		if self.sync_combined() { opts.push_str("--sync-combined "); }
+		if self.cdrdao_toc() { opts.push_str("--toc "); }
+		if self.trim_htoa() { opts.push_str("--trim-htoa "); }
+		if self.verified_only() { opts.push_str("--verified-only "); }
+		if self.wav_info() { opts.push_str("--wav-info "); }
+		if self.whole_disc() { opts.push_str("--whole-disc "); }
 
 		// The tracks should be condensed.
 		write!(
@@ -619,17 +1751,45 @@ mod test {
 	fn t_rip_flags() {
 		// Make sure our flags are unique.
 		let mut all = vec![
+			FLAG_APPEND,
+			FLAG_AUTO_PASSES,
 			FLAG_BACKWARDS,
+			FLAG_BWF,
+			FLAG_C2,
+			FLAG_CDRDAO_TOC,
+			FLAG_CONFUSED,
+			FLAG_CTDB_SINGLE,
+			FLAG_DUMP_C2,
+			FLAG_DURABLE,
 			FLAG_FLIP_FLOP,
+			FLAG_INTERPOLATE,
+			FLAG_LOG_FILES,
+			FLAG_LOSSY,
+			FLAG_MANUAL_OFFSET,
+			FLAG_NO_AR,
+			FLAG_NO_CTDB,
+			FLAG_NO_OVERREAD,
+			FLAG_PARANOID,
 			FLAG_RESET,
 			FLAG_RESUME,
+			FLAG_RETRY_SHIT,
+			FLAG_SHA256,
+			FLAG_SINGLE_FILE,
+			FLAG_SKIP_CORRUPT,
 			FLAG_STRICT,
+			FLAG_SUBMIT,
 			FLAG_SYNC,
+			FLAG_SYNC_ONE,
+			FLAG_TRIM_HTOA,
+			FLAG_UNLOCK,
 			FLAG_VERBOSE,
+			FLAG_VERIFIED,
+			FLAG_WAV_INFO,
+			FLAG_WHOLE_DISC,
 		];
 		all.sort_unstable();
 		all.dedup();
-		assert_eq!(all.len(), 7);
+		assert_eq!(all.len(), 35);
 
 		// Also make sure each is only one bit.
 		assert!(all.iter().all(|&v| v.count_ones() == 1));
@@ -643,6 +1803,27 @@ mod test {
 		assert_eq!(opts.cache(), NonZeroU16::new(16));
 		opts = opts.with_cache(0);
 		assert_eq!(opts.cache(), None);
+
+		assert_eq!(opts.cache_bust_mode(), CacheBustMode::Fill);
+		opts = opts.with_cache_bust_mode(CacheBustMode::Seek);
+		assert_eq!(opts.cache_bust_mode(), CacheBustMode::Seek);
+
+		assert_eq!(opts.c2_granularity(), C2Granularity::Sample);
+		assert!(! opts.c2_tentative(false));
+		opts = opts.with_c2_granularity(C2Granularity::Sector);
+		assert_eq!(opts.c2_granularity(), C2Granularity::Sector);
+		assert!(opts.c2_tentative(false));
+		assert!(! opts.c2_tentative(true));
+
+		assert_eq!(opts.existing(), ExistingMode::Overwrite);
+		opts = opts.with_existing(ExistingMode::Suffix);
+		assert_eq!(opts.existing(), ExistingMode::Suffix);
+
+		assert_eq!(opts.htoa_mode(), HtoaMode::Separate);
+		assert!(! opts.cli().contains("--htoa"));
+		opts = opts.with_htoa_mode(HtoaMode::Merge);
+		assert_eq!(opts.htoa_mode(), HtoaMode::Merge);
+		assert!(opts.cli().contains("--htoa=merge "));
 	}
 
 	#[test]
@@ -661,6 +1842,50 @@ mod test {
 		assert_eq!(opts.confidence(), CONFIDENCE_MAX);
 	}
 
+	#[test]
+	fn t_rip_options_ctdb_shift() {
+		let mut opts = RipOptions::default();
+		assert_eq!(opts.ctdb_shift(), SAMPLE_OVERREAD);
+		opts = opts.with_ctdb_shift(0);
+		assert_eq!(opts.ctdb_shift(), 0);
+		opts = opts.with_ctdb_shift(100);
+		assert_eq!(opts.ctdb_shift(), 100);
+		opts = opts.with_ctdb_shift(u16::MAX);
+		assert_eq!(opts.ctdb_shift(), SAMPLE_OVERREAD);
+	}
+
+	#[test]
+	fn t_rip_options_speed() {
+		let mut opts = RipOptions::default();
+		assert_eq!(opts.speed(), 0);
+		assert!(! opts.cli().contains("--speed"));
+		opts = opts.with_speed(4);
+		assert_eq!(opts.speed(), 4);
+		assert!(opts.cli().contains("--speed=4 "));
+	}
+
+	#[test]
+	fn t_rip_options_min_sectors() {
+		let mut opts = RipOptions::default();
+		assert_eq!(opts.min_sectors(), 0);
+		opts = opts.with_min_sectors(75);
+		assert_eq!(opts.min_sectors(), 75);
+		opts = opts.with_min_sectors(0);
+		assert_eq!(opts.min_sectors(), 0);
+	}
+
+	#[test]
+	fn t_rip_options_decay() {
+		let mut opts = RipOptions::default();
+		assert_eq!(opts.decay(), None);
+		opts = opts.with_decay(4);
+		assert_eq!(opts.decay(), NonZeroU8::new(4));
+		opts = opts.with_decay(64);
+		assert_eq!(opts.decay(), NonZeroU8::new(DECAY_MAX));
+		opts = opts.with_decay(0);
+		assert_eq!(opts.decay(), None);
+	}
+
 	#[test]
 	fn t_rip_options_flags() {
 		macro_rules! t_flags {
@@ -678,13 +1903,62 @@ mod test {
 			);
 		}
 
+		t_flags!("append_tracks", with_append_tracks, append_tracks);
+		t_flags!("auto_passes", with_auto_passes, auto_passes);
 		t_flags!("backwards", with_backwards, backwards);
+		t_flags!("bwf", with_bwf, bwf);
+		t_flags!("c2", with_c2, c2);
+		t_flags!("cdrdao_toc", with_cdrdao_toc, cdrdao_toc);
+		t_flags!("confused_only", with_confused_only, confused_only);
+		t_flags!("ctdb_single", with_ctdb_single, ctdb_single);
+		t_flags!("dump_c2", with_dump_c2, dump_c2);
+		t_flags!("durable_saves", with_durable_saves, durable_saves);
 		t_flags!("flip_flop", with_flip_flop, flip_flop);
+		t_flags!("interpolate", with_interpolate, interpolate);
+		t_flags!("log_files", with_log_files, log_files);
+		t_flags!("lossy", with_lossy, lossy);
+		t_flags!("manual_offset", with_manual_offset, manual_offset);
+		t_flags!("no_accuraterip", with_no_accuraterip, no_accuraterip);
+		t_flags!("no_ctdb", with_no_ctdb, no_ctdb);
+		t_flags!("no_overread", with_no_overread, no_overread);
+		t_flags!("paranoid", with_paranoid, paranoid);
 		t_flags!("reset", with_reset, reset);
 		t_flags!("resume", with_resume, resume);
+		t_flags!("retry_shitlisted", with_retry_shitlisted, retry_shitlisted);
+		t_flags!("sha256", with_sha256, sha256);
+		t_flags!("single_file", with_single_file, single_file);
+		t_flags!("skip_corrupt", with_skip_corrupt, skip_corrupt);
 		t_flags!("strict", with_strict, strict);
+		t_flags!("submit_file", with_submit_file, submit_file);
 		t_flags!("sync", with_sync, sync);
+		t_flags!("sync_combined", with_sync_combined, sync_combined);
+		t_flags!("trim_htoa", with_trim_htoa, trim_htoa);
+		t_flags!("unlock", with_unlock, unlock);
 		t_flags!("verbose", with_verbose, verbose);
+		t_flags!("verified_only", with_verified_only, verified_only);
+		t_flags!("wav_info", with_wav_info, wav_info);
+		t_flags!("whole_disc", with_whole_disc, whole_disc);
+	}
+
+	#[test]
+	fn t_rip_options_strict_after() {
+		let mut opts = RipOptions::default();
+		assert_eq!(opts.strict_after(), None);
+		assert!(! opts.strict_pass(1));
+
+		opts = opts.with_strict_after(3);
+		assert_eq!(opts.strict_after(), NonZeroU8::new(3));
+		assert!(! opts.strict_pass(2));
+		assert!(opts.strict_pass(3));
+		assert!(opts.strict_pass(4));
+
+		// Regular strict always wins.
+		opts = opts.with_strict(true);
+		assert!(opts.strict_pass(1));
+
+		opts = opts.with_strict(false).with_strict_after(0);
+		assert_eq!(opts.strict_after(), None);
+		assert!(! opts.strict_pass(16));
 	}
 
 	#[test]
@@ -695,6 +1969,14 @@ mod test {
 			let opts = RipOptions::default().with_offset(v);
 			assert_eq!(opts.offset(), v);
 		}
+
+		// A zero offset is only worth mentioning if it was set manually.
+		let offset0 = ReadOffset::try_from(b"0".as_slice()).expect("Read offset 0 failed.");
+		let opts = RipOptions::default().with_offset(offset0);
+		assert!(! opts.cli().contains("-o0"));
+		let opts = opts.with_manual_offset(true);
+		assert!(opts.cli().contains("-o0 "));
+		assert!(RipOptions::default().with_offset(offset5).cli().contains("-o5 "));
 	}
 
 	#[test]
@@ -711,6 +1993,55 @@ mod test {
 		// Max.
 		let opts = RipOptions::default().with_passes(64);
 		assert_eq!(opts.passes(), PASSES_MAX);
+
+		// Auto.
+		let opts = RipOptions::default().with_passes(3).with_auto_passes(true);
+		assert_eq!(opts.passes(), PASSES_MAX);
+		let opts = opts.with_auto_passes(false);
+		assert_eq!(opts.passes(), 3);
+
+		// Minimum passes can raise the count, but not lower it.
+		let opts = opts.with_min_passes(5);
+		assert_eq!(opts.min_passes(), 5);
+		assert_eq!(opts.passes(), 5);
+		assert!(opts.forced_pass(5));
+		assert!(! opts.forced_pass(6));
+		let opts = opts.with_min_passes(2);
+		assert_eq!(opts.passes(), 3);
+		let opts = opts.with_min_passes(64);
+		assert_eq!(opts.min_passes(), PASSES_MAX);
+		let opts = opts.with_min_passes(0);
+		assert_eq!(opts.passes(), 3);
+		assert!(! opts.forced_pass(1));
+	}
+
+	#[test]
+	fn t_rip_options_from_track() {
+		let opts = RipOptions::default();
+		assert!(opts.resume_track(5));
+
+		let opts = opts.with_from_track(5);
+		assert!(opts.resume_track(0));
+		assert!(opts.resume_track(4));
+		assert!(! opts.resume_track(5));
+		assert!(! opts.resume_track(9));
+
+		// No resume means no resume.
+		assert!(! opts.with_resume(false).resume_track(1));
+
+		// Zero disables it.
+		let opts = opts.with_from_track(0);
+		assert!(opts.from_track().is_none());
+		assert!(opts.resume_track(9));
+	}
+
+	#[test]
+	fn t_rip_options_bits() {
+		let opts = RipOptions::default();
+		assert_eq!(opts.bits(), 16);
+		assert_eq!(opts.with_bits(24).bits(), 24);
+		assert_eq!(opts.with_bits(32).bits(), 32);
+		assert_eq!(opts.with_bits(24).with_bits(20).bits(), 16);
 	}
 
 	#[test]
@@ -775,6 +2106,12 @@ mod test {
 		// Remove the rest.
 		opts = opts.without_track(5).without_track(15);
 		assert!(! opts.has_tracks(), "Options tracks should be empty!");
+
+		// Set them all at once, replacing whatever was there before.
+		opts = opts.with_track(1).with_tracks([3, 2, 100, 7]);
+		assert_eq!(opts.tracks().collect::<Vec<u8>>(), [2, 3, 7]);
+		opts = opts.with_tracks([]);
+		assert!(! opts.has_tracks(), "Options tracks should be empty!");
 	}
 
 	#[test]
@@ -0,0 +1,80 @@
+/*!
+# Rip Rip Hooray: Offset Probing
+*/
+
+use crate::{
+	chk_ctdb,
+	Disc,
+	KillSwitch,
+	RipOptions,
+	RipRipError,
+	SAMPLE_OVERREAD,
+};
+use fyi_msg::Progless;
+use super::sectors::{
+	init_range,
+	RangeSave,
+	rip_range,
+};
+
+
+
+/// # Probe Read Offset.
+///
+/// Rip the first selected (regular) track from scratch — one pass, overread
+/// included, no state saved — then run the CUETools shift search over the
+/// full `±5880` sample range, regardless of `RipOptions::ctdb_shift`.
+///
+/// That range is the ten sectors of overread on either side of the track,
+/// the most that can be shifted without reading past what was ripped. Drives
+/// whose true offset lies further out than that won't be found.
+///
+/// Because the samples are stored offset-corrected, a match at a shift of `N`
+/// samples means the drive's true offset is the current one plus `N`.
+///
+/// The track number, best shift, and (total) confidence are returned if
+/// there was any match at all.
+///
+/// ## Errors
+///
+/// This will bubble up any errors encountered, except run-of-the-mill sector
+/// read or sync errors, which are simply skipped. If no regular track was
+/// selected, `RipRipError::Noop` is returned.
+pub(crate) fn probe_offset(
+	disc: &Disc,
+	opts: &RipOptions,
+	progress: &Progless,
+	killed: &KillSwitch,
+) -> Result<Option<(u8, i16, u16)>, RipRipError> {
+	let toc = disc.toc();
+	let track = opts.tracks()
+		.filter(|&idx| idx != 0)
+		.find_map(|idx| toc.audio_track(usize::from(idx)))
+		.ok_or(RipRipError::Noop)?;
+	let rng = track.sector_range_normalized();
+	let rng =
+		i32::try_from(rng.start).map_err(|_| RipRipError::RipOverflow)?..
+		i32::try_from(rng.end).map_err(|_| RipRipError::RipOverflow)?;
+
+	// Start fresh with one forward pass, and make sure the padding is
+	// actually read since that's where the shifted data lives.
+	let opts = opts.with_resume(false)
+		.with_reset(false)
+		.with_passes(1)
+		.with_min_passes(0)
+		.with_auto_passes(false)
+		.with_backwards(false)
+		.with_no_overread(false);
+
+	let label = format!("Track #{:02}", track.number());
+	let mut state = init_range(disc, rng, &label, &opts, progress)?;
+	rip_range(disc, &mut state, &label, &opts, progress, killed, &RangeSave::No)?;
+	progress.finish();
+	if killed.killed() { return Err(RipRipError::Killed); }
+
+	Ok(
+		chk_ctdb(toc, track, state.rip_slice(), true, SAMPLE_OVERREAD)
+			.filter(|(conf, _)| *conf != 0)
+			.map(|(conf, shift)| (track.number(), shift, conf))
+	)
+}
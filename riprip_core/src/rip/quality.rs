@@ -2,7 +2,9 @@
 # Rip Rip Hooray: Quality Counts
 */
 
+use cdtoc::TrackPosition;
 use crate::{
+	accuraterip_ignored,
 	COLOR_BAD,
 	COLOR_CONFIRMED,
 	COLOR_LIKELY,
@@ -29,6 +31,15 @@ use std::{
 /// # Quality Bar.
 const QUALITY_BAR: &str = "########################################################################";
 
+/// # Heatmap Columns.
+const HEATMAP_COLS: usize = 60;
+
+/// # Contentious Advisory Ratio.
+///
+/// An otherwise likely rip with at least one contentious sample per this many
+/// total samples is worth a warning.
+const CONTENTIOUS_RATIO: u64 = 10_000;
+
 
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -58,6 +69,12 @@ pub(super) struct TrackQuality {
 	/// This is true when the drive returns different values from read-to-read
 	/// without admitting any errors have occurred.
 	confused: bool,
+
+	/// # Silent?
+	///
+	/// This is true when every sample has been read, uncontested, as
+	/// silence (or is leadin/out).
+	silent: bool,
 }
 
 impl Add for TrackQuality {
@@ -70,6 +87,7 @@ impl Add for TrackQuality {
 			confirmed: self.confirmed + other.confirmed,
 			contentious: self.contentious + other.contentious,
 			confused: self.confused || other.confused,
+			silent: self.silent && other.silent,
 		}
 	}
 }
@@ -78,7 +96,11 @@ impl TrackQuality {
 	/// # From Slice.
 	///
 	/// Count up all the different statuses in a given track slice.
-	pub(super) fn new(src: &[RipSample], rereads: (u8, u8)) -> Self {
+	///
+	/// The edges of the first and last tracks — per `pos` — can never be
+	/// verified, so the likeliness criteria are relaxed for the samples there:
+	/// a single read is enough so long as the usual contention ratio holds.
+	pub(super) fn new(src: &[RipSample], rereads: (u8, u8), pos: TrackPosition) -> Self {
 		// This should never happen, but will ensure there's never any
 		// division-by-zero weirdness later on.
 		if src.is_empty() {
@@ -89,6 +111,7 @@ impl TrackQuality {
 				confirmed: 0,
 				contentious: 0,
 				confused: false,
+				silent: false,
 			};
 		}
 
@@ -98,12 +121,21 @@ impl TrackQuality {
 		let mut confirmed = 0;
 		let mut contentious = 0;
 		let mut confused = false;
+		let mut silent = true;
+
+		let (head, tail) = accuraterip_ignored(pos);
+		let tail = src.len().saturating_sub(tail);
+		let relaxed = (1, rereads.1);
 
-		for v in src {
+		for (idx, v) in src.iter().enumerate() {
+			silent &= v.is_silent();
 			match v {
 				RipSample::Tbd | RipSample::Bad(_) => { bad += 1; },
 				RipSample::Lead => { confirmed += 1; },
-				RipSample::Maybe(_) => {
+				RipSample::Maybe(_, _) => {
+					let rereads =
+						if idx < head || tail < idx { relaxed }
+						else { rereads };
 					if v.is_likely(rereads) { likely += 1; }
 					else { maybe += 1; }
 
@@ -115,7 +147,23 @@ impl TrackQuality {
 			}
 		}
 
-		Self { bad, maybe, likely, confirmed, contentious, confused }
+		Self { bad, maybe, likely, confirmed, contentious, confused, silent }
+	}
+
+	/// # From Array.
+	///
+	/// The inverse of `TrackQuality::as_array`. Contention details aren't
+	/// included, so are assumed to be nil.
+	pub(super) const fn from_array(src: [u32; 4]) -> Self {
+		Self {
+			bad: src[0],
+			maybe: src[1],
+			likely: src[2],
+			confirmed: src[3],
+			contentious: 0,
+			confused: false,
+			silent: false,
+		}
 	}
 
 	/// # New Bad.
@@ -129,6 +177,7 @@ impl TrackQuality {
 			confirmed: 0,
 			contentious: 0,
 			confused: false,
+			silent: false,
 		}
 	}
 
@@ -143,6 +192,7 @@ impl TrackQuality {
 			confirmed: num.get(),
 			contentious: 0,
 			confused: false,
+			silent: false,
 		}
 	}
 }
@@ -165,11 +215,27 @@ impl TrackQuality {
 		self.likely() + self.confirmed() == self.total().get()
 	}
 
+	/// # Is Silent?
+	///
+	/// Returns `true` if every sample has been read, uncontested, as silence.
+	pub(super) const fn is_silent(&self) -> bool { self.silent }
+
 	/// # Is Confirmed?
 	pub(super) const fn is_confirmed(&self) -> bool {
 		self.confirmed() == self.total().get()
 	}
 
+	/// # Is Worse Than?
+	///
+	/// Returns `true` if this has more bad samples than `other`, or the same
+	/// number of bad samples but fewer good ones. (Confirmations trump mere
+	/// likeliness when the good counts are tied.)
+	pub(super) const fn is_worse_than(&self, other: &Self) -> bool {
+		if self.bad != other.bad { other.bad < self.bad }
+		else if self.good() != other.good() { self.good() < other.good() }
+		else { self.confirmed < other.confirmed }
+	}
+
 	/// # Percent Maybe.
 	pub(super) fn percent_maybe(&self) -> Option<f64> {
 		let v = self.maybe + self.likely + self.confirmed;
@@ -185,6 +251,11 @@ impl TrackQuality {
 		}
 	}
 
+	/// # Good Samples.
+	///
+	/// Return the number of likely and confirmed samples.
+	pub(super) const fn good(&self) -> u32 { self.likely + self.confirmed }
+
 	/// # Percent Likely.
 	pub(super) fn percent_likely(&self) -> Option<f64> {
 		let v = self.likely + self.confirmed;
@@ -288,6 +359,32 @@ impl TrackQuality {
 		TrackQualityLegend { start, end }
 	}
 
+	/// # Contentious Advisory.
+	///
+	/// If the rip is likely — but not confirmed — and the share of contentious
+	/// samples is high enough to be worrying, return a warning suggesting
+	/// more passes.
+	pub(super) fn contentious_advisory(&self) -> Option<String> {
+		let total = self.total().get();
+		if
+			! self.is_likely() ||
+			self.is_confirmed() ||
+			u64::from(self.contentious) * CONTENTIOUS_RATIO < u64::from(total)
+		{
+			return None;
+		}
+
+		let percent = NiceFloat::from(f64::from(self.contentious) * 100.0 / f64::from(total));
+		Some(format!(
+			"{} {} ({}%) {} contentious{}. Everything rates likely, but additional passes — or a different drive — are recommended to settle the disagreements.",
+			NiceU32::from(self.contentious),
+			self.contentious.inflect("sample", "samples"),
+			percent.precise_str(3),
+			if self.contentious == 1 { "is" } else { "are" },
+			if self.confused { ", some confusingly so" } else { "" },
+		))
+	}
+
 	/// # Summary.
 	///
 	/// Summarize the state of the track rip in one line.
@@ -373,6 +470,86 @@ impl fmt::Display for TrackQualityBar {
 
 
 
+/// # Track Heatmap.
+///
+/// This divides a track's samples into `HEATMAP_COLS` equal(ish) regions,
+/// recording the worst status found in each — `0` for bad, `1` for maybe,
+/// `2` for likely — so the distribution of problems can be seen at a glance.
+/// A scratch, for example, tends to show up as a tight cluster, while general
+/// wear is spread out all over.
+///
+/// Confirmation applies to the track as a whole, so if it has been verified,
+/// every region is `3` (confirmed) instead.
+///
+/// The regular `Display` output is colored for the terminal; use the
+/// alternate form — `{:#}` — for plain text.
+pub(super) struct TrackHeatmap([u8; HEATMAP_COLS]);
+
+impl fmt::Display for TrackHeatmap {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let color = ! f.alternate();
+
+		// Only change colors when we have to.
+		let mut last = u8::MAX;
+		for &v in &self.0 {
+			if color && v != last {
+				match v {
+					0 => write!(f, "\x1b[0;{COLOR_BAD}m")?,
+					1 => write!(f, "\x1b[0;{COLOR_MAYBE}m")?,
+					2 => write!(f, "\x1b[0;{COLOR_LIKELY}m")?,
+					_ => write!(f, "\x1b[0;{COLOR_CONFIRMED}m")?,
+				}
+				last = v;
+			}
+			f.write_str(match v {
+				0 => "#",
+				1 => "+",
+				2 => "-",
+				_ => ".",
+			})?;
+		}
+
+		if color { f.write_str("\x1b[0m") }
+		else { Ok(()) }
+	}
+}
+
+impl TrackHeatmap {
+	/// # Legend.
+	///
+	/// A key for the heatmap characters.
+	pub(super) const LEGEND: &str = "# bad, + maybe, - likely, . confirmed";
+
+	/// # From Slice.
+	///
+	/// Bucket the samples of a given track slice, keeping the worst status
+	/// from each, unless the track has been `confirmed`, in which case it's
+	/// all good.
+	pub(super) fn new(src: &[RipSample], rereads: (u8, u8), confirmed: bool) -> Self {
+		let len = src.len();
+		if len == 0 { return Self([0; HEATMAP_COLS]); }
+		if confirmed { return Self([3; HEATMAP_COLS]); }
+
+		let mut out = [2_u8; HEATMAP_COLS];
+		for (k, v) in src.iter().enumerate() {
+			let status = match v {
+				RipSample::Tbd | RipSample::Bad(_) => 0,
+				RipSample::Lead => 2,
+				RipSample::Maybe(_, _) =>
+					if v.is_likely(rereads) { 2 }
+					else { 1 },
+			};
+
+			let col = k * HEATMAP_COLS / len;
+			if status < out[col] { out[col] = status; }
+		}
+
+		Self(out)
+	}
+}
+
+
+
 /// # Track Quality Legend.
 ///
 /// This is used to format the legend for the initial and/or final rip states.
@@ -386,7 +563,17 @@ pub(super) struct TrackQualityLegend {
 
 impl TrackQualityLegend {
 	/// # Colors.
-	const COLORS: [&str; 4] = [COLOR_BAD, COLOR_MAYBE, COLOR_LIKELY, COLOR_CONFIRMED];
+	///
+	/// Return the color corresponding to the bad/maybe/likely/confirmed
+	/// index.
+	fn color(idx: usize) -> &'static str {
+		match idx {
+			0 => COLOR_BAD.as_str(),
+			1 => COLOR_MAYBE.as_str(),
+			2 => COLOR_LIKELY.as_str(),
+			_ => COLOR_CONFIRMED.as_str(),
+		}
+	}
 
 	/// # Padding.
 	///
@@ -476,7 +663,7 @@ impl<'a> Iterator for TrackQualityLegendIter<'a> {
 				return Some((
 					start.map_or("0", NiceU32::as_str),
 					end.map_or("0", NiceU32::as_str),
-					TrackQualityLegend::COLORS[idx],
+					TrackQualityLegend::color(idx),
 				));
 			}
 		}
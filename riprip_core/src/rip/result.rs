@@ -0,0 +1,123 @@
+/*!
+# Rip Rip Hooray: Rip Results
+*/
+
+use crate::{
+	RipOptions,
+	SavedRips,
+};
+use std::path::{
+	Path,
+	PathBuf,
+};
+
+
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// # Track Rip Result.
+///
+/// This holds the final state of a track after a call to `Disc::rip`: where
+/// it was exported to (if anywhere), its AccurateRip and CUETools
+/// confidences (if checked), and the breakdown of its sample qualities.
+///
+/// It exists for library consumers who'd rather render their own summaries
+/// or decide what to do next programmatically.
+pub struct RipTrackResult {
+	/// # Track Number.
+	pub(super) track: u8,
+
+	/// # Destination Path.
+	pub(super) dst: Option<PathBuf>,
+
+	/// # AccurateRip Confidence.
+	pub(super) ar: Option<(u8, u8)>,
+
+	/// # CTDB Confidence.
+	pub(super) ctdb: Option<u16>,
+
+	/// # Sample Counts (Bad, Maybe, Likely, Confirmed).
+	pub(super) quality: [u32; 4],
+}
+
+impl RipTrackResult {
+	#[must_use]
+	/// # Track Number.
+	///
+	/// Note: the HTOA, if ripped, is track zero.
+	pub const fn track(&self) -> u8 { self.track }
+
+	#[must_use]
+	/// # Destination Path.
+	///
+	/// Return the path the track was exported to, if it was.
+	pub fn dst(&self) -> Option<&Path> { self.dst.as_deref() }
+
+	#[must_use]
+	/// # AccurateRip Confidence.
+	///
+	/// Return the v1 and v2 AccurateRip confidences, if checked.
+	pub const fn accuraterip(&self) -> Option<(u8, u8)> { self.ar }
+
+	#[must_use]
+	/// # CUETools Confidence.
+	///
+	/// Return the CUETools confidence, if checked.
+	pub const fn ctdb(&self) -> Option<u16> { self.ctdb }
+
+	#[must_use]
+	/// # Bad Samples.
+	pub const fn bad(&self) -> u32 { self.quality[0] }
+
+	#[must_use]
+	/// # Maybe Samples.
+	pub const fn maybe(&self) -> u32 { self.quality[1] }
+
+	#[must_use]
+	/// # Likely Samples.
+	pub const fn likely(&self) -> u32 { self.quality[2] }
+
+	#[must_use]
+	/// # Confirmed Samples.
+	pub const fn confirmed(&self) -> u32 { self.quality[3] }
+
+	#[must_use]
+	/// # Is Confirmed?
+	///
+	/// Returns `true` if the whole track was confirmed by AccurateRip and/or
+	/// CUETools.
+	pub const fn is_confirmed(&self) -> bool {
+		self.quality[0] == 0 && self.quality[1] == 0 && self.quality[2] == 0
+	}
+}
+
+
+
+/// # Saved Rips.
+///
+/// Reduce the results to the tracks we actually exported, along with their
+/// confirmation details. Specifically, this returns the file path and
+/// AccurateRip/CTDB match counts — omitted if below the `opts` thresholds —
+/// indexed by track number.
+///
+/// The HTOA can't be verified, but if it rates likely, it gets maxed-out
+/// counts to reflect that.
+pub(crate) fn saved_rips(results: &[RipTrackResult], opts: &RipOptions)
+-> Option<SavedRips> {
+	let conf = opts.confidence();
+	let out: SavedRips = results.iter()
+		.filter_map(|v| {
+			let dst = v.dst.clone()?;
+			let htoa_likely = v.track == 0 && v.bad() == 0 && v.maybe() == 0;
+			let ar =
+				if htoa_likely { Some((u8::MAX, u8::MAX))}
+				else { v.ar.filter(|&(v1, v2)| conf <= v1 || conf <= v2) };
+			let ctdb =
+				if htoa_likely { Some(u16::MAX) }
+				else { v.ctdb.filter(|&v1| opts.ctdb_confidence() <= v1) };
+			Some((v.track, (dst, ar, ctdb)))
+		})
+		.collect();
+
+	if out.is_empty() { None }
+	else { Some(out) }
+}
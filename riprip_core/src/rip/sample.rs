@@ -9,6 +9,10 @@ use crate::{
 };
 use std::{
 	cmp::Ordering,
+	hash::{
+		Hash,
+		Hasher,
+	},
 	io::{
 		Read,
 		Write,
@@ -19,14 +23,24 @@ use std::{
 
 
 /// # `RipSample` Variant ID range.
-const DATA_KIND_RNG: Range<u8> = 1..9;
+const DATA_KIND_RNG: Range<u8> = 1..14;
+
+/// # Aged Variant ID Offset.
+///
+/// Maybe samples with a non-zero age are stored under their own variant IDs —
+/// the regular ones plus this — followed by an extra byte for the age. This
+/// keeps the unaged serialization the same as it ever was.
+const DATA_KIND_AGED: u8 = 5;
 
 
 
-#[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
+#[derive(Debug, Clone, Default, Eq)]
 /// # Rip Sample.
 ///
 /// This enum combines sample value(s) and status(es).
+///
+/// Note that the age of a maybe is ignored for equality and hashing, so
+/// passes that merely tick it up don't count as changes.
 pub(crate) enum RipSample {
 	/// # Leadin/out.
 	Lead,
@@ -38,8 +52,46 @@ pub(crate) enum RipSample {
 	/// Samples that came down with C2 or read errors.
 	Bad(Sample),
 
-	/// Allegedly good sample(s).
-	Maybe(ContentiousSample),
+	/// Allegedly good sample(s), and the number of passes since any of them
+	/// were last seen. (The latter only ticks up when decay is enabled.)
+	Maybe(ContentiousSample, u8),
+}
+
+impl From<Sample> for RipSample {
+	#[inline]
+	/// # From Sample.
+	///
+	/// This is used for data from outside the ripping process, e.g. an old
+	/// WAV file, so is treated as an allegedly good sample with a single
+	/// count.
+	fn from(src: Sample) -> Self { Self::Maybe(ContentiousSample::new(src), 0) }
+}
+
+impl AsRef<[u8]> for RipSample {
+	#[inline]
+	fn as_ref(&self) -> &[u8] { self.as_slice() }
+}
+
+impl Hash for RipSample {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		std::mem::discriminant(self).hash(state);
+		match self {
+			Self::Lead | Self::Tbd => {},
+			Self::Bad(s) => { s.hash(state); },
+			Self::Maybe(s, _) => { s.hash(state); },
+		}
+	}
+}
+
+impl PartialEq for RipSample {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Lead, Self::Lead) | (Self::Tbd, Self::Tbd) => true,
+			(Self::Bad(a), Self::Bad(b)) => a == b,
+			(Self::Maybe(a, _), Self::Maybe(b, _)) => a == b,
+			_ => false,
+		}
+	}
 }
 
 impl RipSample {
@@ -50,7 +102,7 @@ impl RipSample {
 		match self {
 			Self::Tbd | Self::Lead => NULL_SAMPLE,
 			Self::Bad(s) => *s,
-			Self::Maybe(s) => s.as_array(),
+			Self::Maybe(s, _) => s.as_array(),
 		}
 	}
 
@@ -61,7 +113,7 @@ impl RipSample {
 		match self {
 			Self::Tbd | Self::Lead => NULL_SAMPLE.as_slice(),
 			Self::Bad(s) => s.as_slice(),
-			Self::Maybe(s) => s.as_slice(),
+			Self::Maybe(s, _) => s.as_slice(),
 		}
 	}
 
@@ -73,12 +125,18 @@ impl RipSample {
 			Self::Lead => 1,
 			Self::Tbd => 2,
 			Self::Bad(_) => 3,
-			Self::Maybe(ContentiousSample::Maybe1((_, count))) =>
-				if 1 == *count { 4 } // Implicit count of one.
-				else { 5 },          // Explicit other count.
-			Self::Maybe(ContentiousSample::Maybe2(_)) => 6,
-			Self::Maybe(ContentiousSample::Maybe3(_)) => 7,
-			Self::Maybe(ContentiousSample::Strict(_)) => 8,
+			Self::Maybe(s, age) => {
+				let kind = match s {
+					ContentiousSample::Maybe1((_, count)) =>
+						if 1 == *count { 4 } // Implicit count of one.
+						else { 5 },          // Explicit other count.
+					ContentiousSample::Maybe2(_) => 6,
+					ContentiousSample::Maybe3(_) => 7,
+					ContentiousSample::Strict(_) => 8,
+				};
+				if 0 == *age { kind }
+				else { kind + DATA_KIND_AGED }
+			},
 		}
 	}
 }
@@ -92,7 +150,7 @@ impl RipSample {
 	/// Returns true if the data has been so inconsistent as to warrant strict
 	/// handling.
 	pub(crate) const fn is_confused(&self) -> bool {
-		matches!(self, Self::Maybe(ContentiousSample::Strict(_)))
+		matches!(self, Self::Maybe(ContentiousSample::Strict(_), _))
 	}
 
 	/// # Is Contentious?
@@ -104,11 +162,27 @@ impl RipSample {
 			Self::Maybe(
 				ContentiousSample::Maybe2(_) |
 				ContentiousSample::Maybe3(_) |
-				ContentiousSample::Strict(_)
+				ContentiousSample::Strict(_),
+				_,
 			)
 		)
 	}
 
+	/// # Is Silent?
+	///
+	/// Returns true if the sample is an allegedly good, uncontested null, or
+	/// leadin/out, which is silence by definition. (Unread and bad samples
+	/// are never considered silent.)
+	pub(crate) const fn is_silent(&self) -> bool {
+		match self {
+			Self::Lead => true,
+			Self::Maybe(s, _) =>
+				! self.is_contentious() &&
+				u32::from_le_bytes(s.as_array()) == 0,
+			Self::Tbd | Self::Bad(_) => false,
+		}
+	}
+
 	/// # Likeliness.
 	///
 	/// Return the minimum reread abs/mul values to make the sample likely.
@@ -116,7 +190,7 @@ impl RipSample {
 		match self {
 			// Leadin/out is always likely.
 			Self::Lead => true,
-			Self::Maybe(s) => {
+			Self::Maybe(s, _) => {
 				let (a, mut b) = s.contention();
 				b = b.saturating_mul(rereads.1);
 				if b == u8::MAX { b -= 1; }
@@ -129,6 +203,36 @@ impl RipSample {
 }
 
 impl RipSample {
+	/// # Decay.
+	///
+	/// Bump the age of a maybe sample by one pass. If it reaches `max` — i.e.
+	/// none of its values have been seen for that many passes — its counts
+	/// are halved (to no less than one) and the clock starts over.
+	///
+	/// Returns `true` if the counts were halved.
+	pub(crate) fn decay(&mut self, max: u8) -> bool {
+		if let Self::Maybe(s, age) = self {
+			*age = age.saturating_add(1);
+			if max <= *age {
+				*age = 0;
+				s.halve();
+				return true;
+			}
+		}
+
+		false
+	}
+
+	/// # Reset Counts.
+	///
+	/// Drop all maybe counts back to one, and forget their age.
+	pub(crate) fn reset(&mut self) {
+		if let Self::Maybe(s, age) = self {
+			*age = 0;
+			s.reset();
+		}
+	}
+
 	/// # Update Sample.
 	///
 	/// See `update_bad` for what happens if there's a C2 error. Otherwise,
@@ -143,6 +247,8 @@ impl RipSample {
 	/// contention was a sync error and that is fixed by the new read, it is
 	/// changed to Maybe.)
 	///
+	/// Either way, a maybe's age is reset when a good value is added.
+	///
 	/// Leadin/out stays the same.
 	pub(crate) fn update(&mut self, new: Sample, err_c2: bool, all_good: bool) {
 		// Send bad samples to a different method to halve the onslaught of
@@ -152,14 +258,15 @@ impl RipSample {
 		match self {
 			// Always update a TBD.
 			Self::Tbd | Self::Bad(_) => {
-				*self = Self::Maybe(ContentiousSample::new(new));
+				*self = Self::Maybe(ContentiousSample::new(new), 0);
 			},
 
 			// Maybes
-			Self::Maybe(s) =>
+			Self::Maybe(s, age) =>
 				// Strict samples can only be updated if all good.
 				if all_good || ! matches!(s, ContentiousSample::Strict(_)) {
 					s.add_good(new);
+					*age = 0;
 				},
 
 			// Leave leadin/out samples alone.
@@ -167,6 +274,37 @@ impl RipSample {
 		}
 	}
 
+	/// # Update Sample (Tentatively).
+	///
+	/// Same as `update`, except good values only replace TBD and Bad samples;
+	/// existing maybes are left alone. This is used for reads with C2 errors
+	/// elsewhere in the sector under `C2Granularity::Sector`, so the data can
+	/// fill in blanks without vouching for anything.
+	pub(crate) fn update_tentative(&mut self, new: Sample, err_c2: bool) {
+		if err_c2 { self.update_bad(new); }
+		else if matches!(self, Self::Tbd | Self::Bad(_)) {
+			*self = Self::Maybe(ContentiousSample::new(new), 0);
+		}
+	}
+
+	/// # Merge Sample.
+	///
+	/// Fold in the equivalent sample from another rip — e.g. a different
+	/// drive — as if its reads had been made here. Good values are added once
+	/// per count, while bad ones go through the usual C2 handling.
+	///
+	/// Leadin/out and TBD samples have nothing to contribute.
+	pub(crate) fn merge(&mut self, other: &Self) {
+		match other {
+			Self::Lead | Self::Tbd => {},
+			Self::Bad(s) => { self.update(*s, true, false); },
+			Self::Maybe(s, _) =>
+				for (v, count) in s.counts() {
+					for _ in 0..count { self.update(v, false, true); }
+				},
+		}
+	}
+
 	/// # Update New Bad Sample.
 	///
 	/// TBD and Bad samples are simply replaced.
@@ -181,7 +319,7 @@ impl RipSample {
 			Self::Tbd | Self::Bad(_) => { *self = Self::Bad(new); },
 
 			// Simple Maybes.
-			Self::Maybe(s) => if let Some(boo) = s.add_bad(new) {
+			Self::Maybe(s, _) => if let Some(boo) = s.add_bad(new) {
 				*self = boo;
 			},
 
@@ -214,7 +352,7 @@ pub(super) struct RipSector {
 	kind: [u8; (SAMPLES_PER_SECTOR as usize).wrapping_div(2)],
 
 	/// # Data.
-	data: [u8; SAMPLES_PER_SECTOR as usize * 15],
+	data: [u8; SAMPLES_PER_SECTOR as usize * 16],
 }
 
 impl RipSector {
@@ -253,10 +391,17 @@ impl RipSector {
 	/// The type information is written first in a packed format — two `u4`
 	/// per byte — followed by the data, whose length will be variable
 	/// depending on the sample variants used.
+	///
+	/// ## Errors
+	///
+	/// This will bubble up any I/O errors, or return an `InvalidInput` error
+	/// if `src` isn't exactly one sector's worth of samples.
 	pub(super) fn serialize_into<W: Write>(&mut self, src: &[RipSample], w: &mut W)
-	-> Option<()> {
+	-> std::io::Result<()> {
 		// Sanity check: this should never fail, but just in case.
-		if src.len() != usize::from(SAMPLES_PER_SECTOR) { return None; }
+		if src.len() != usize::from(SAMPLES_PER_SECTOR) {
+			return Err(std::io::ErrorKind::InvalidInput.into());
+		}
 
 		// Set the type headers.
 		for (k, v) in self.kind.iter_mut().zip(src.chunks_exact(2)) {
@@ -276,7 +421,7 @@ impl RipSector {
 						(current, data) = data.split_at_mut(4);
 						current.copy_from_slice(s.as_slice());
 					},
-					RipSample::Maybe(ContentiousSample::Maybe1(pair)) =>
+					RipSample::Maybe(ContentiousSample::Maybe1(pair), _) =>
 						if 1 == pair.1 {
 							(current, data) = data.split_at_mut(4);
 							current.copy_from_slice(pair.0.as_slice());
@@ -286,14 +431,14 @@ impl RipSector {
 							current[..4].copy_from_slice(pair.0.as_slice());
 							current[4] = pair.1;
 						},
-					RipSample::Maybe(ContentiousSample::Maybe2(set)) => {
+					RipSample::Maybe(ContentiousSample::Maybe2(set), _) => {
 						(current, data) = data.split_at_mut(10);
 						current[..4].copy_from_slice(set[0].0.as_slice());
 						current[4] = set[0].1;
 						current[5..9].copy_from_slice(set[1].0.as_slice());
 						current[9] = set[1].1;
 					},
-					RipSample::Maybe(ContentiousSample::Maybe3(set) | ContentiousSample::Strict(set)) => {
+					RipSample::Maybe(ContentiousSample::Maybe3(set) | ContentiousSample::Strict(set), _) => {
 						(current, data) = data.split_at_mut(15);
 						current[..4].copy_from_slice(set[0].0.as_slice());
 						current[4] = set[0].1;
@@ -304,6 +449,12 @@ impl RipSector {
 					},
 					_ => {},
 				}
+
+				// Aged samples have one more byte to go.
+				if let RipSample::Maybe(_, age @ 1..) = v {
+					(current, data) = data.split_at_mut(1);
+					current[0] = *age;
+				}
 			}
 
 			// Return the leftover data so we can figure out how much was
@@ -312,8 +463,8 @@ impl RipSector {
 		};
 
 		// Write the types and data!
-		w.write_all(self.kind.as_slice()).ok()?;
-		w.write_all(&self.data[..len]).ok()
+		w.write_all(self.kind.as_slice())?;
+		w.write_all(&self.data[..len])
 	}
 }
 
@@ -322,7 +473,7 @@ impl RipSector {
 	pub(super) const fn new() -> Self {
 		Self {
 			kind: [0_u8; (SAMPLES_PER_SECTOR as usize).wrapping_div(2)],
-			data: [0_u8; SAMPLES_PER_SECTOR as usize * 15],
+			data: [0_u8; SAMPLES_PER_SECTOR as usize * 16],
 		}
 	}
 }
@@ -353,72 +504,24 @@ impl Iterator for RipSectorSamples<'_> {
 		if self.kind.len() <= idx { None }
 		else {
 			// Tease out the kind.
-			let kind =
+			let mut kind =
 				if 0 == self.pos & 1 { u4_unpack_lhs(self.kind[idx]) }
 				else { u4_unpack_rhs(self.kind[idx]) };
 			self.pos += 1;
 
-			// Parse it!
-			match kind {
-				1 => Some(RipSample::Lead),
-				2 => Some(RipSample::Tbd),
-				3 =>
-					if 4 <= self.data.len() {
-						let (data, rest) = self.data.split_at(4);
-						self.data = rest;
-						Some(RipSample::Bad([data[0], data[1], data[2], data[3]]))
-					}
-					else { None },
-				4 =>
-					if 4 <= self.data.len() {
-						let (data, rest) = self.data.split_at(4);
-						self.data = rest;
-						Some(RipSample::Maybe(ContentiousSample::Maybe1((
-							[data[0], data[1], data[2], data[3]],
-							1,
-						))))
-					}
-					else { None },
-				5 =>
-					if 5 <= self.data.len() {
-						let (data, rest) = self.data.split_at(5);
-						self.data = rest;
-						Some(RipSample::Maybe(ContentiousSample::Maybe1((
-							[data[0], data[1], data[2], data[3]],
-							data[4],
-						))))
-					}
-					else { None },
-				6 =>
-					if 10 <= self.data.len() {
-						let (data, rest) = self.data.split_at(10);
-						self.data = rest;
-						Some(RipSample::Maybe(ContentiousSample::Maybe2([
-							([data[0], data[1], data[2], data[3]], data[4]),
-							([data[5], data[6], data[7], data[8]], data[9]),
-						])))
-					}
-					else { None },
-				7 | 8 =>
-					if 15 <= self.data.len() {
-						let (data, rest) = self.data.split_at(15);
-						self.data = rest;
-						let set = [
-							([data[0],  data[1],  data[2],  data[3]],  data[4]),
-							([data[5],  data[6],  data[7],  data[8]],  data[9]),
-							([data[10], data[11], data[12], data[13]], data[14]),
-						];
-						if kind == 7 {
-							Some(RipSample::Maybe(ContentiousSample::Maybe3(set)))
-						}
-						else {
-							Some(RipSample::Maybe(ContentiousSample::Strict(set)))
-						}
-					}
-					else { None },
-				// This shouldn't be reachable.
-				_ => None,
+			// Aged samples are parsed like their regular counterparts, with
+			// the age tacked on at the end.
+			let aged = DATA_KIND_AGED + 4 <= kind;
+			if aged { kind -= DATA_KIND_AGED; }
+			let mut out = self.parse(kind)?;
+			if aged {
+				let RipSample::Maybe(_, age) = &mut out else { return None; };
+				let (&v, rest) = self.data.split_first()?;
+				self.data = rest;
+				*age = v;
 			}
+
+			Some(out)
 		}
 	}
 
@@ -428,6 +531,75 @@ impl Iterator for RipSectorSamples<'_> {
 	}
 }
 
+impl RipSectorSamples<'_> {
+	/// # Parse Sample.
+	///
+	/// Parse and return the next (unaged) sample of the given kind from the
+	/// data buffer.
+	fn parse(&mut self, kind: u8) -> Option<RipSample> {
+		match kind {
+			1 => Some(RipSample::Lead),
+			2 => Some(RipSample::Tbd),
+			3 =>
+				if 4 <= self.data.len() {
+					let (data, rest) = self.data.split_at(4);
+					self.data = rest;
+					Some(RipSample::Bad([data[0], data[1], data[2], data[3]]))
+				}
+				else { None },
+			4 =>
+				if 4 <= self.data.len() {
+					let (data, rest) = self.data.split_at(4);
+					self.data = rest;
+					Some(RipSample::Maybe(ContentiousSample::Maybe1((
+						[data[0], data[1], data[2], data[3]],
+						1,
+					)), 0))
+				}
+				else { None },
+			5 =>
+				if 5 <= self.data.len() {
+					let (data, rest) = self.data.split_at(5);
+					self.data = rest;
+					Some(RipSample::Maybe(ContentiousSample::Maybe1((
+						[data[0], data[1], data[2], data[3]],
+						data[4],
+					)), 0))
+				}
+				else { None },
+			6 =>
+				if 10 <= self.data.len() {
+					let (data, rest) = self.data.split_at(10);
+					self.data = rest;
+					Some(RipSample::Maybe(ContentiousSample::Maybe2([
+						([data[0], data[1], data[2], data[3]], data[4]),
+						([data[5], data[6], data[7], data[8]], data[9]),
+					]), 0))
+				}
+				else { None },
+			7 | 8 =>
+				if 15 <= self.data.len() {
+					let (data, rest) = self.data.split_at(15);
+					self.data = rest;
+					let set = [
+						([data[0],  data[1],  data[2],  data[3]],  data[4]),
+						([data[5],  data[6],  data[7],  data[8]],  data[9]),
+						([data[10], data[11], data[12], data[13]], data[14]),
+					];
+					if kind == 7 {
+						Some(RipSample::Maybe(ContentiousSample::Maybe3(set), 0))
+					}
+					else {
+						Some(RipSample::Maybe(ContentiousSample::Strict(set), 0))
+					}
+				}
+				else { None },
+			// This shouldn't be reachable.
+			_ => None,
+		}
+	}
+}
+
 impl ExactSizeIterator for RipSectorSamples<'_> {
 	fn len(&self) -> usize {
 		usize::from(SAMPLES_PER_SECTOR.saturating_sub(self.pos))
@@ -479,6 +651,18 @@ impl ContentiousSample {
 		}
 	}
 
+	/// # Values and Counts.
+	///
+	/// Return each value with its count, padding the set with zero counts
+	/// as needed.
+	const fn counts(&self) -> [(Sample, u8); 3] {
+		match self {
+			Self::Maybe1(a) => [*a, (NULL_SAMPLE, 0), (NULL_SAMPLE, 0)],
+			Self::Maybe2([a, b]) => [*a, *b, (NULL_SAMPLE, 0)],
+			Self::Maybe3(set) | Self::Strict(set) => *set,
+		}
+	}
+
 	/// # Contention.
 	///
 	/// Return the first (best) total, and the total of all the rest.
@@ -640,10 +824,29 @@ impl ContentiousSample {
 		}
 	}
 
+	/// # Halve Counts.
+	///
+	/// Cut all counts in half, but no lower than one. Relative order is
+	/// unaffected, so no resorting is necessary.
+	fn halve(&mut self) {
+		match self {
+			Self::Maybe1((_, count1)) => { *count1 = (*count1 / 2).max(1); },
+			Self::Maybe2(set) => {
+				set[0].1 = (set[0].1 / 2).max(1);
+				set[1].1 = (set[1].1 / 2).max(1);
+			},
+			Self::Maybe3(set) | Self::Strict(set) => {
+				set[0].1 = (set[0].1 / 2).max(1);
+				set[1].1 = (set[1].1 / 2).max(1);
+				set[2].1 = (set[2].1 / 2).max(1);
+			},
+		}
+	}
+
 	/// # Reset Counts.
 	///
 	/// Drop all counts back to one.
-	pub(crate) fn reset(&mut self) {
+	fn reset(&mut self) {
 		match self {
 			Self::Maybe1((_, count1)) => { *count1 = 1; },
 			Self::Maybe2(set) => {
@@ -680,9 +883,12 @@ impl ContentiousSample {
 const fn data_len_by_kind(kind: u8) -> u8 {
 	match kind {
 		3 | 4 => 4,
-		5 => 5,
+		5 | 9 => 5,
+		10 => 6,
 		6 => 10,
+		11 => 11,
 		7 | 8 => 15,
+		12 | 13 => 16,
 		_ => 0,
 	}
 }
@@ -702,7 +908,7 @@ fn sort_sample_count(a: &(Sample, u8), b: &(Sample, u8)) -> Ordering { b.1.cmp(&
 /// Pack two (small) integers into a single byte.
 ///
 /// This is used for our sample type de/serialization, which codes the variant
-/// identifiers as a number between `1..=8`.
+/// identifiers as a number between `1..=13`.
 const fn u4_pack(a: u8, b: u8) -> u8 { a | b << 4 }
 
 /// # Unpack `u8` into Two `u4`.
@@ -720,6 +926,21 @@ const fn u4_unpack_rhs(c: u8) -> u8 { (c >> 4) & 0b0000_1111 }
 mod test {
 	use super::*;
 
+	/// # Age.
+	///
+	/// Return the age of a maybe, or zero for anything else, since equality
+	/// doesn't look at it.
+	const fn age(src: &RipSample) -> u8 {
+		if let RipSample::Maybe(_, age) = src { *age } else { 0 }
+	}
+
+	/// # Hash.
+	fn hash(src: &RipSample) -> u64 {
+		let mut hasher = std::collections::hash_map::DefaultHasher::new();
+		src.hash(&mut hasher);
+		hasher.finish()
+	}
+
 	/// # Random Sample.
 	fn random_sample() -> Sample {
 		let a = fastrand::u8(..);
@@ -760,8 +981,8 @@ mod test {
 			data.push(RipSample::Tbd);
 
 			data.push(RipSample::Bad(random_sample()));
-			data.push(RipSample::Maybe(ContentiousSample::Maybe1((random_sample(), 1))));
-			data.push(RipSample::Maybe(ContentiousSample::Maybe1((random_sample(), fastrand::u8(1..)))));
+			data.push(RipSample::Maybe(ContentiousSample::Maybe1((random_sample(), 1)), 0));
+			data.push(RipSample::Maybe(ContentiousSample::Maybe1((random_sample(), fastrand::u8(1..))), 0));
 
 
 			let mut set = [
@@ -769,7 +990,7 @@ mod test {
 				(random_sample(), fastrand::u8(1..)),
 			];
 			set.sort_unstable_by(sort_sample_count);
-			data.push(RipSample::Maybe(ContentiousSample::Maybe2(set)));
+			data.push(RipSample::Maybe(ContentiousSample::Maybe2(set), 0));
 
 			let mut set = [
 				(random_sample(), fastrand::u8(1..)),
@@ -777,7 +998,7 @@ mod test {
 				(random_sample(), fastrand::u8(1..)),
 			];
 			set.sort_unstable_by(sort_sample_count);
-			data.push(RipSample::Maybe(ContentiousSample::Maybe3(set)));
+			data.push(RipSample::Maybe(ContentiousSample::Maybe3(set), 0));
 
 			let mut set = [
 				(random_sample(), fastrand::u8(1..)),
@@ -785,10 +1006,17 @@ mod test {
 				(random_sample(), fastrand::u8(1..)),
 			];
 			set.sort_unstable_by(sort_sample_count);
-			data.push(RipSample::Maybe(ContentiousSample::Strict(set)));
+			data.push(RipSample::Maybe(ContentiousSample::Strict(set), 0));
 		}
 		assert_eq!(data.len(), usize::from(SAMPLES_PER_SECTOR * 4), "You fucked up the sector length.");
 
+		// Age about half of the maybes so the aged variants get tested too.
+		for v in &mut data {
+			if let RipSample::Maybe(_, age) = v {
+				if fastrand::bool() { *age = fastrand::u8(1..); }
+			}
+		}
+
 		// Test the sector de/serialization.
 		let mut sector = RipSector::new();
 		for v in data.chunks_exact_mut(usize::from(SAMPLES_PER_SECTOR)) {
@@ -806,9 +1034,58 @@ mod test {
 
 			// The output should have us back where we started.
 			assert_eq!(v, de, "Deserialized samples do not match the original.");
+			assert!(
+				v.iter().zip(&de).all(|(a, b)| age(a) == age(b)),
+				"Deserialized ages do not match the original.",
+			);
 		}
 	}
 
+	#[test]
+	fn t_decay() {
+		let mut sample = RipSample::Maybe(ContentiousSample::Maybe2([
+			(NULL_SAMPLE, 5),
+			([1, 1, 1, 1], 1),
+		]), 0);
+
+		// Nothing happens until the age catches up, and the age alone isn't
+		// a change.
+		let before = (sample.clone(), hash(&sample));
+		assert!(! sample.decay(2));
+		assert_eq!(age(&sample), 1);
+		assert_eq!(sample, before.0);
+		assert_eq!(hash(&sample), before.1);
+
+		// Now the counts should be halved, but no lower than one.
+		assert!(sample.decay(2));
+		assert_eq!(age(&sample), 0);
+		assert_ne!(hash(&sample), before.1);
+		assert_eq!(
+			sample,
+			RipSample::Maybe(ContentiousSample::Maybe2([
+				(NULL_SAMPLE, 2),
+				([1, 1, 1, 1], 1),
+			]), 0)
+		);
+
+		// A good read should reset the age.
+		assert!(! sample.decay(2));
+		sample.update([1, 1, 1, 1], false, true);
+		assert_eq!(age(&sample), 0);
+		assert_eq!(
+			sample,
+			RipSample::Maybe(ContentiousSample::Maybe2([
+				(NULL_SAMPLE, 2),
+				([1, 1, 1, 1], 2),
+			]), 0)
+		);
+
+		// Non-maybes don't age.
+		let mut sample = RipSample::Bad(NULL_SAMPLE);
+		assert!(! sample.decay(1));
+		assert_eq!(sample, RipSample::Bad(NULL_SAMPLE));
+	}
+
 	#[test]
 	fn t_update() {
 		// Start with TBD.
@@ -820,21 +1097,21 @@ mod test {
 		sample.update(NULL_SAMPLE, false, true);
 		assert_eq!(
 			sample,
-			RipSample::Maybe(ContentiousSample::Maybe1((NULL_SAMPLE, 1)))
+			RipSample::Maybe(ContentiousSample::Maybe1((NULL_SAMPLE, 1)), 0)
 		);
 
 		// Maybe + Bad = no change.
 		sample.update([1, 1, 1, 1], true, false);
 		assert_eq!(
 			sample,
-			RipSample::Maybe(ContentiousSample::Maybe1((NULL_SAMPLE, 1)))
+			RipSample::Maybe(ContentiousSample::Maybe1((NULL_SAMPLE, 1)), 0)
 		);
 
 		// Maybe + Good = ++
 		sample.update(NULL_SAMPLE, false, true);
 		assert_eq!(
 			sample,
-			RipSample::Maybe(ContentiousSample::Maybe1((NULL_SAMPLE, 2)))
+			RipSample::Maybe(ContentiousSample::Maybe1((NULL_SAMPLE, 2)), 0)
 		);
 
 		// Maybe + Good (different) = Contentious
@@ -844,7 +1121,7 @@ mod test {
 			RipSample::Maybe(ContentiousSample::Maybe2([
 				(NULL_SAMPLE, 2),
 				([1, 1, 1, 1], 1),
-			]))
+			]), 0)
 		);
 
 		// Contentious + Bad (different) = no change
@@ -854,7 +1131,7 @@ mod test {
 			RipSample::Maybe(ContentiousSample::Maybe2([
 				(NULL_SAMPLE, 2),
 				([1, 1, 1, 1], 1),
-			]))
+			]), 0)
 		);
 
 		// Bump the second.
@@ -864,7 +1141,7 @@ mod test {
 			RipSample::Maybe(ContentiousSample::Maybe2([
 				(NULL_SAMPLE, 2),
 				([1, 1, 1, 1], 2),
-			]))
+			]), 0)
 		);
 
 		// Second takes the lead!
@@ -874,7 +1151,7 @@ mod test {
 			RipSample::Maybe(ContentiousSample::Maybe2([
 				([1, 1, 1, 1], 3),
 				(NULL_SAMPLE, 2),
-			]))
+			]), 0)
 		);
 
 		// Contentious + Bad (existing) = --
@@ -884,7 +1161,7 @@ mod test {
 			RipSample::Maybe(ContentiousSample::Maybe2([
 				([1, 1, 1, 1], 3),
 				(NULL_SAMPLE, 1),
-			]))
+			]), 0)
 		);
 
 		// Contentious + Bad (existing) = -- = Maybe
@@ -893,7 +1170,7 @@ mod test {
 			sample,
 			RipSample::Maybe(ContentiousSample::Maybe1(
 				([1, 1, 1, 1], 3),
-			))
+			), 0)
 		);
 
 		// Maybe + Bad (existing) = -- = empty = Bad.
@@ -902,16 +1179,95 @@ mod test {
 			sample,
 			RipSample::Maybe(ContentiousSample::Maybe1(
 				([1, 1, 1, 1], 2),
-			))
+			), 0)
 		);
 		sample.update([1, 1, 1, 1], true, false);
 		assert_eq!(
 			sample,
 			RipSample::Maybe(ContentiousSample::Maybe1(
 				([1, 1, 1, 1], 1),
-			))
+			), 0)
 		);
 		sample.update([1, 1, 1, 1], true, false);
 		assert_eq!(sample, RipSample::Bad([1, 1, 1, 1]));
 	}
+
+	#[test]
+	fn t_update_tentative() {
+		// Blanks get filled in.
+		let mut sample = RipSample::Tbd;
+		sample.update_tentative([1, 1, 1, 1], false);
+		assert_eq!(
+			sample,
+			RipSample::Maybe(ContentiousSample::Maybe1(([1, 1, 1, 1], 1)), 0)
+		);
+
+		// But maybes don't get any more likely.
+		sample.update_tentative([1, 1, 1, 1], false);
+		sample.update_tentative([2, 2, 2, 2], false);
+		assert_eq!(
+			sample,
+			RipSample::Maybe(ContentiousSample::Maybe1(([1, 1, 1, 1], 1)), 0)
+		);
+
+		// Errors are still errors.
+		sample.update_tentative([1, 1, 1, 1], true);
+		let mut expected = RipSample::Maybe(ContentiousSample::Maybe1(([1, 1, 1, 1], 1)), 0);
+		expected.update([1, 1, 1, 1], true, false);
+		assert_eq!(sample, expected);
+	}
+
+	#[test]
+	fn t_silent() {
+		assert!(RipSample::Lead.is_silent());
+		assert!(! RipSample::Tbd.is_silent());
+		assert!(! RipSample::Bad(NULL_SAMPLE).is_silent());
+		assert!(RipSample::from(NULL_SAMPLE).is_silent());
+		assert!(! RipSample::from([1, 0, 0, 0]).is_silent());
+
+		// Contested nulls don't count.
+		let mut sample = RipSample::from(NULL_SAMPLE);
+		sample.update([1, 0, 0, 0], false, true);
+		assert!(! sample.is_silent());
+	}
+
+	#[test]
+	fn t_merge() {
+		// Nothing from nothing.
+		let mut sample = RipSample::Tbd;
+		sample.merge(&RipSample::Tbd);
+		assert_eq!(sample, RipSample::Tbd);
+
+		// Bad replaces TBD.
+		sample.merge(&RipSample::Bad([1, 1, 1, 1]));
+		assert_eq!(sample, RipSample::Bad([1, 1, 1, 1]));
+
+		// Counts carry over.
+		sample.merge(&RipSample::Maybe(ContentiousSample::Maybe2([
+			(NULL_SAMPLE, 3),
+			([1, 1, 1, 1], 1),
+		]), 5));
+		assert_eq!(
+			sample,
+			RipSample::Maybe(ContentiousSample::Maybe2([
+				(NULL_SAMPLE, 3),
+				([1, 1, 1, 1], 1),
+			]), 0)
+		);
+
+		// And bad values knock them down.
+		sample.merge(&RipSample::Bad(NULL_SAMPLE));
+		assert_eq!(
+			sample,
+			RipSample::Maybe(ContentiousSample::Maybe2([
+				(NULL_SAMPLE, 2),
+				([1, 1, 1, 1], 1),
+			]), 0)
+		);
+
+		// Leadin/out is left alone.
+		sample = RipSample::Lead;
+		sample.merge(&RipSample::Bad(NULL_SAMPLE));
+		assert_eq!(sample, RipSample::Lead);
+	}
 }
@@ -0,0 +1,204 @@
+/*!
+# Rip Rip Hooray: Sector Range Rips
+*/
+
+use crate::{
+	Disc,
+	KillSwitch,
+	RipBuffer,
+	RipOptions,
+	RipRipError,
+	RipState,
+};
+use fyi_msg::{
+	Msg,
+	Progless,
+};
+use std::{
+	ops::Range,
+	path::PathBuf,
+};
+use super::{
+	data::sector_todo,
+	stage_sector,
+	warn_c2,
+	warn_sync,
+};
+
+
+
+/// # Range Save Mode.
+///
+/// What `rip_range` should do with the state after each pass.
+pub(super) enum RangeSave<'a> {
+	/// # Don't Save.
+	///
+	/// The data is thrown away after use.
+	No,
+
+	/// # Save Changes.
+	Yes,
+
+	/// # Save Changes, Stop When Done.
+	///
+	/// Same as `RangeSave::Yes`, but any remaining passes are skipped once the
+	/// callback returns `true`.
+	Until(&'a dyn Fn(&RipState) -> bool),
+}
+
+
+
+/// # Rip Sector Range.
+///
+/// This is a stripped-down version of `Ripper::rip` for arbitrary
+/// (normalized) sector ranges. The samples are read, refined, and saved the
+/// same way, but because the range needn't line up with any track, there is
+/// no cache busting, verification, or logging.
+///
+/// The path to the extracted WAV file is returned.
+///
+/// ## Errors
+///
+/// This will bubble up any errors encountered, except run-of-the-mill sector
+/// read or sync errors, which are simply skipped.
+pub(crate) fn rip_sectors(
+	disc: &Disc,
+	rng: Range<i32>,
+	opts: &RipOptions,
+	progress: &Progless,
+	killed: &KillSwitch,
+) -> Result<PathBuf, RipRipError> {
+	let label = format!("Sectors {}–{}", rng.start, rng.end - 1);
+	let mut state = init_range(disc, rng, &label, opts, progress)?;
+	rip_range(disc, &mut state, &label, opts, progress, killed, &RangeSave::Yes)?;
+	progress.finish();
+	let quality = state.track_quality(opts);
+	state.save_track(opts, quality, (None, None))
+}
+
+/// # Initialize Range.
+///
+/// Reset the progress bar and load (or create) the state for the range.
+///
+/// ## Errors
+///
+/// This will bubble up any state-related errors encountered.
+pub(super) fn init_range(
+	disc: &Disc,
+	rng: Range<i32>,
+	label: &str,
+	opts: &RipOptions,
+	progress: &Progless,
+) -> Result<RipState, RipRipError> {
+	let total = u32::try_from(rng.len())
+		.ok()
+		.and_then(|n| n.checked_mul(u32::from(opts.passes())))
+		.ok_or(RipRipError::RipOverflow)?;
+
+	let _res = progress.reset(total);
+	progress.set_title(Some(Msg::custom(label, 199, "Initializing…")));
+	let mut state = RipState::new_sectors(disc.toc(), rng, opts)?;
+	if state.take_recovered() {
+		let _res = progress.push_msg(Msg::warning(format!(
+			"The state data for {label} was corrupt; the previous save was recovered instead.",
+		)).with_newline(true));
+	}
+	Ok(state)
+}
+
+/// # Rip Range.
+///
+/// Run through all of the passes for the range, saving the state after each
+/// one that changes anything (according to `save`).
+///
+/// ## Errors
+///
+/// This will bubble up any errors encountered, except run-of-the-mill sector
+/// read or sync errors, which are simply skipped.
+pub(super) fn rip_range(
+	disc: &Disc,
+	state: &mut RipState,
+	label: &str,
+	opts: &RipOptions,
+	progress: &Progless,
+	killed: &KillSwitch,
+	save: &RangeSave,
+) -> Result<(), RipRipError> {
+	let mut opts = *opts;
+	let mut buf = RipBuffer::new();
+	let mut scratch = Vec::new();
+
+	for pass in 1..=opts.passes() {
+		// Make sure the disc is still the disc.
+		disc.check_toc()?;
+
+		let title = format!(
+			"{}{}{}…",
+			if pass == 1 && state.is_new() { "Ripping fresh" } else { "Re-ripping" },
+			if opts.passes() == 1 { String::new() } else { format!(", pass #{pass}") },
+			if opts.backwards() { ", backwards, and in heels" } else { "" },
+		);
+		progress.set_title(Some(Msg::custom(label, 199, &title)));
+
+		let before = state.quick_hash();
+		if let Some(max) = opts.decay() { state.decay(max.get(), &opts)?; }
+		if opts.forced_pass(pass) { state.mark_todo(&opts)?; }
+
+		let iter = state.offset_rip_iter(&opts)?;
+		let skipped = u32::try_from(iter.skipped()).map_err(|_| RipRipError::RipOverflow)?;
+		if skipped != 0 { progress.increment_n(skipped); }
+
+		for (read_lsn, sector, todo) in iter {
+			if killed.paused() {
+				progress.set_title(Some(Msg::custom(label, 199, "Paused; send SIGUSR1 again to resume…")));
+				killed.wait_while_paused();
+				progress.set_title(Some(Msg::custom(label, 199, &title)));
+			}
+			if killed.killed() {
+				progress.increment();
+				continue;
+			}
+
+			match buf.read_sector(disc.cdio(), read_lsn, &opts, pass) {
+				// Stage and commit, same as regular rips.
+				Ok(all_good) => if ! killed.killed() {
+					stage_sector(&mut scratch, sector, &buf, all_good, &opts);
+					if ! killed.killed() {
+						sector.clone_from_slice(&scratch);
+						*todo = sector_todo(sector, &opts);
+					}
+				},
+				// Silently skip generic read errors.
+				Err(RipRipError::CdRead | RipRipError::SubchannelDesync) => {},
+				// Abort for all other kinds of errors.
+				Err(e) => return Err(e),
+			}
+
+			warn_c2(&mut buf, progress);
+			progress.increment();
+		}
+
+		// Save the state if we changed any data.
+		if before != state.quick_hash() {
+			if ! matches!(save, RangeSave::No) {
+				progress.set_title(Some(Msg::custom(label, 199, "Saving the state…")));
+				state.save_state_soft()?;
+			}
+		}
+		// Or stop early if we're automating passes and nothing changed.
+		else if opts.auto_passes() && ! opts.forced_pass(pass + 1) && ! killed.killed() {
+			break;
+		}
+
+		// Stop early if there's nothing left to do.
+		if let RangeSave::Until(done) = save {
+			if ! killed.killed() && done(state) { break; }
+		}
+
+		// Flip the read order for next time?
+		if opts.flip_flop() { opts = opts.with_backwards(! opts.backwards()); }
+	}
+
+	warn_sync(&buf, progress);
+	Ok(())
+}
@@ -0,0 +1,164 @@
+/*!
+# Rip Rip Hooray: Read Watch
+*/
+
+use fyi_msg::Progless;
+use std::{
+	sync::{
+		Arc,
+		atomic::{
+			AtomicBool,
+			Ordering::{
+				Acquire,
+				Release,
+			},
+		},
+		Mutex,
+	},
+	thread::JoinHandle,
+	time::{
+		Duration,
+		Instant,
+	},
+};
+
+
+
+/// # Refresh Interval.
+///
+/// How often to check up on an in-progress read.
+const REFRESH: Duration = Duration::from_millis(250);
+
+/// # Slow Read.
+///
+/// How long a read has to take before we start talking about it.
+const SLOW: Duration = Duration::from_secs(2);
+
+
+
+/// # Read Watch.
+///
+/// The progress bar only moves once a sector read completes, so on very slow
+/// or struggling drives it can look frozen for seconds at a time.
+///
+/// This spawns a lightweight background thread that keeps an eye on the
+/// current read and, once it has dragged on for a while, refreshes the
+/// progress title with a running count so the user can tell the process is
+/// still alive. The original title is restored when the read finishes.
+///
+/// The thread stops when the watch is dropped, and leaves the title alone
+/// while the kill switch is thrown.
+pub(super) struct ReadWatch {
+	/// # Shared State.
+	inner: Arc<ReadWatchInner>,
+
+	/// # Thread Handle.
+	handle: Option<JoinHandle<()>>,
+}
+
+/// # Read Watch (Shared State).
+struct ReadWatchInner {
+	/// # Current Read (Started, LSN), and Whether the Title Was Changed.
+	read: Mutex<(Option<(Instant, i32)>, bool)>,
+
+	/// # Done?
+	done: AtomicBool,
+
+	/// # Killed?
+	killed: Arc<AtomicBool>,
+
+	/// # Progress Bar.
+	progress: Progless,
+
+	/// # Track Number.
+	idx: u8,
+
+	/// # Normal Title.
+	title: String,
+}
+
+impl Drop for ReadWatch {
+	fn drop(&mut self) {
+		self.inner.done.store(true, Release);
+		if let Some(handle) = self.handle.take() {
+			handle.thread().unpark();
+			let _res = handle.join();
+		}
+	}
+}
+
+impl ReadWatch {
+	/// # New.
+	///
+	/// Start watching reads for the given track, restoring `title` after any
+	/// slow ones.
+	pub(super) fn new(progress: &Progless, killed: Arc<AtomicBool>, idx: u8, title: &str)
+	-> Self {
+		let inner = Arc::new(ReadWatchInner {
+			read: Mutex::new((None, false)),
+			done: AtomicBool::new(false),
+			killed,
+			progress: progress.clone(),
+			idx,
+			title: title.to_owned(),
+		});
+
+		let handle = {
+			let inner = Arc::clone(&inner);
+			std::thread::Builder::new()
+				.spawn(move || inner.watch())
+				.ok()
+		};
+
+		Self { inner, handle }
+	}
+
+	/// # Watch a Read.
+	///
+	/// Run the read callback, keeping tabs on how long it takes.
+	pub(super) fn read<T, F: FnOnce() -> T>(&self, lsn: i32, cb: F) -> T {
+		if let Ok(mut read) = self.inner.read.lock() {
+			read.0.replace((Instant::now(), lsn));
+		}
+
+		let out = cb();
+
+		if let Ok(mut read) = self.inner.read.lock() {
+			read.0 = None;
+			if std::mem::take(&mut read.1) {
+				super::set_progress_title(&self.inner.progress, self.inner.idx, &self.inner.title);
+			}
+		}
+
+		out
+	}
+}
+
+impl ReadWatchInner {
+	/// # Watch.
+	///
+	/// This is the background thread's loop.
+	fn watch(&self) {
+		while ! self.done.load(Acquire) {
+			std::thread::park_timeout(REFRESH);
+			if self.done.load(Acquire) || self.killed.load(Acquire) { continue; }
+
+			let Ok(mut read) = self.read.lock() else { break; };
+			if let Some((start, lsn)) = read.0 {
+				let elapsed = start.elapsed();
+				if SLOW <= elapsed {
+					super::set_progress_title(
+						&self.progress,
+						self.idx,
+						&format!(
+							"{} (sector {lsn} has been reading for {}s…)",
+							self.title,
+							elapsed.as_secs(),
+						),
+					);
+					read.1 = true;
+				}
+			}
+		}
+	}
+}
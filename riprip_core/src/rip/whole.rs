@@ -0,0 +1,149 @@
+/*!
+# Rip Rip Hooray: Whole Disc Rips
+*/
+
+use cdtoc::Track;
+use crate::{
+	Disc,
+	HtoaMode,
+	KillSwitch,
+	RipOptions,
+	RipRipError,
+	RipSample,
+	RipState,
+	SAMPLE_OVERREAD,
+};
+use fyi_msg::{
+	Msg,
+	Progless,
+};
+use super::{
+	happy_track_msg,
+	htoa_tracks,
+	is_verified,
+	result::RipTrackResult,
+	sectors::{
+		init_range,
+		RangeSave,
+		rip_range,
+	},
+	track_too_short,
+	TrackQuality,
+	verify_samples,
+};
+
+
+
+/// # Rip Whole Disc.
+///
+/// Rip all of the audio on the disc — HTOA included — as a single continuous
+/// stream, then slice it up into the requested tracks, verifying and
+/// exporting each in turn.
+///
+/// Because the stream is continuous, the overlap between adjacent tracks is
+/// only ever read once, and the offset handling is identical across every
+/// boundary, which can help with gapless albums.
+///
+/// Any remaining passes are skipped once every (verifiable) track has been
+/// verified.
+///
+/// ## Errors
+///
+/// This will bubble up any errors encountered, except run-of-the-mill sector
+/// read or sync errors, which are simply skipped.
+pub(crate) fn rip_whole_disc(
+	disc: &Disc,
+	opts: &RipOptions,
+	progress: &Progless,
+	killed: &KillSwitch,
+) -> Result<Vec<RipTrackResult>, RipRipError> {
+	let toc = disc.toc();
+	let start = toc.htoa().map_or_else(
+		|| toc.audio_leadin_normalized(),
+		|t| t.sector_range_normalized().start,
+	);
+	let rng =
+		i32::try_from(start).map_err(|_| RipRipError::RipOverflow)?..
+		i32::try_from(toc.audio_leadout_normalized()).map_err(|_| RipRipError::RipOverflow)?;
+
+	let label = "Whole Disc";
+	let mut state = init_range(disc, rng, label, opts, progress)?;
+	let done = |state: &RipState| all_verified(state, opts);
+	rip_range(disc, &mut state, label, opts, progress, killed, &RangeSave::Until(&done))?;
+	progress.set_title(Some(Msg::custom(label, 199, "Verifying and exporting the tracks…")));
+
+	// Slice, verify, and export the tracks.
+	let mut out = Vec::new();
+	for idx in htoa_tracks(toc, *opts).tracks() {
+		let track =
+			if idx == 0 { toc.htoa() }
+			else { toc.audio_track(usize::from(idx)) };
+		let Some(track) = track else { continue; };
+		if track_too_short(track, opts) { continue; }
+		let (data, padded) = track_slices(&state, track).ok_or(RipRipError::NoTrack(idx))?;
+
+		// HTOA isn't verifiable. Boo.
+		let (ar, ctdb) =
+			if track.is_htoa() { (None, None) }
+			else {
+				let (ar, ctdb, _, _) = verify_samples(toc, track, data, padded, opts);
+				(ar, ctdb)
+			};
+
+		let mut quality = TrackQuality::new(data, opts.rereads(), track.position());
+		let verified = ! track.is_htoa() && is_verified(opts, ar, ctdb);
+		if verified {
+			quality = TrackQuality::new_confirmed(quality.total());
+			let _res = progress.push_msg(happy_track_msg(track));
+		}
+
+		// Unverified tracks might need to stay put, and a merged HTOA goes
+		// out with the first track instead.
+		let dst =
+			if
+				(verified || ! opts.verified_only()) &&
+				! (track.is_htoa() && matches!(opts.htoa_mode(), HtoaMode::Merge))
+			{
+				Some(state.save_disc_track(track, opts, (ar, ctdb))?)
+			}
+			else { None };
+
+		out.push(RipTrackResult {
+			track: idx,
+			dst,
+			ar,
+			ctdb,
+			quality: quality.as_array(),
+		});
+	}
+
+	progress.finish();
+	Ok(out)
+}
+
+/// # All Verified?
+///
+/// Returns `true` if every selected (regular) track has been verified, i.e.
+/// further passes would be pointless.
+fn all_verified(state: &RipState, opts: &RipOptions) -> bool {
+	let toc = state.toc();
+	htoa_tracks(toc, *opts).tracks()
+		.filter(|&idx| idx != 0)
+		.filter_map(|idx| toc.audio_track(usize::from(idx)))
+		.filter(|&t| ! track_too_short(t, opts))
+		.all(|t| track_slices(state, t).is_some_and(|(data, padded)| {
+			let (ar, ctdb, _, _) = verify_samples(toc, t, data, padded, opts);
+			is_verified(opts, ar, ctdb)
+		}))
+}
+
+/// # Track Slices.
+///
+/// Return the samples for the track, without and with the `SAMPLE_OVERREAD`
+/// padding, or `None` if the track isn't covered by the rip.
+fn track_slices(state: &RipState, track: Track) -> Option<(&[RipSample], &[RipSample])> {
+	let padded = state.disc_track_slice(track)?;
+	let pad = usize::from(SAMPLE_OVERREAD);
+	let data = padded.get(pad..padded.len().checked_sub(pad)?)?;
+	Some((data, padded))
+}
@@ -0,0 +1,178 @@
+/*!
+# Rip Rip Hooray: SHA-256
+*/
+
+
+
+/// # SHA-256 Round Constants.
+const SHA256_K: [u32; 64] = [
+	0x428a_2f98, 0x7137_4491, 0xb5c0_fbcf, 0xe9b5_dba5, 0x3956_c25b, 0x59f1_11f1, 0x923f_82a4, 0xab1c_5ed5,
+	0xd807_aa98, 0x1283_5b01, 0x2431_85be, 0x550c_7dc3, 0x72be_5d74, 0x80de_b1fe, 0x9bdc_06a7, 0xc19b_f174,
+	0xe49b_69c1, 0xefbe_4786, 0x0fc1_9dc6, 0x240c_a1cc, 0x2de9_2c6f, 0x4a74_84aa, 0x5cb0_a9dc, 0x76f9_88da,
+	0x983e_5152, 0xa831_c66d, 0xb003_27c8, 0xbf59_7fc7, 0xc6e0_0bf3, 0xd5a7_9147, 0x06ca_6351, 0x1429_2967,
+	0x27b7_0a85, 0x2e1b_2138, 0x4d2c_6dfc, 0x5338_0d13, 0x650a_7354, 0x766a_0abb, 0x81c2_c92e, 0x9272_2c85,
+	0xa2bf_e8a1, 0xa81a_664b, 0xc24b_8b70, 0xc76c_51a3, 0xd192_e819, 0xd699_0624, 0xf40e_3585, 0x106a_a070,
+	0x19a4_c116, 0x1e37_6c08, 0x2748_774c, 0x34b0_bcb5, 0x391c_0cb3, 0x4ed8_aa4a, 0x5b9c_ca4f, 0x682e_6ff3,
+	0x748f_82ee, 0x78a5_636f, 0x84c8_7814, 0x8cc7_0208, 0x90be_fffa, 0xa450_6ceb, 0xbef9_a3f7, 0xc671_78f2,
+];
+
+/// # SHA-256 Initial State.
+const SHA256_H: [u32; 8] = [
+	0x6a09_e667, 0xbb67_ae85, 0x3c6e_f372, 0xa54f_f53a,
+	0x510e_527f, 0x9b05_688c, 0x1f83_d9ab, 0x5be0_cd19,
+];
+
+
+
+/// # SHA-256.
+///
+/// A minimal streaming SHA-256 hasher, used for the optional checksum
+/// sidecars saved alongside exported tracks.
+pub(crate) struct Sha256 {
+	/// # Hash State.
+	state: [u32; 8],
+
+	/// # Pending Block.
+	buf: [u8; 64],
+
+	/// # Pending Length.
+	pending: usize,
+
+	/// # Total Length (in bytes).
+	total: u64,
+}
+
+impl Sha256 {
+	/// # New.
+	pub(crate) const fn new() -> Self {
+		Self {
+			state: SHA256_H,
+			buf: [0; 64],
+			pending: 0,
+			total: 0,
+		}
+	}
+
+	/// # Update.
+	///
+	/// Feed more data into the hash.
+	pub(crate) fn update(&mut self, mut src: &[u8]) {
+		self.total = self.total.wrapping_add(src.len() as u64);
+
+		// Top off the pending block first, if any.
+		if self.pending != 0 {
+			let len = (64 - self.pending).min(src.len());
+			self.buf[self.pending..self.pending + len].copy_from_slice(&src[..len]);
+			self.pending += len;
+			src = &src[len..];
+			if self.pending < 64 { return; }
+			sha256_compress(&mut self.state, &self.buf);
+			self.pending = 0;
+		}
+
+		// Whole blocks can be processed directly.
+		let mut chunks = src.chunks_exact(64);
+		for block in chunks.by_ref() {
+			sha256_compress(&mut self.state, block);
+		}
+
+		// Hold onto the rest for later.
+		let rem = chunks.remainder();
+		self.buf[..rem.len()].copy_from_slice(rem);
+		self.pending = rem.len();
+	}
+
+	/// # Finish.
+	///
+	/// Pad out the message and return the final hash.
+	pub(crate) fn finish(mut self) -> [u8; 32] {
+		let bits = self.total.wrapping_mul(8);
+		self.update(&[0x80]);
+		while self.pending != 56 { self.update(&[0]); }
+		self.update(&bits.to_be_bytes());
+
+		let mut out = [0_u8; 32];
+		for (dst, v) in out.chunks_exact_mut(4).zip(self.state) {
+			dst.copy_from_slice(&v.to_be_bytes());
+		}
+		out
+	}
+}
+
+/// # SHA-256 Compression.
+///
+/// Mix one 64-byte `block` into the `state`.
+fn sha256_compress(state: &mut [u32; 8], block: &[u8]) {
+	let mut w = [0_u32; 64];
+	for (dst, src) in w.iter_mut().zip(block.chunks_exact(4)) {
+		*dst = u32::from_be_bytes([src[0], src[1], src[2], src[3]]);
+	}
+	for i in 16..64 {
+		let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+		let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+		w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+	}
+
+	let mut v = *state;
+	for (k, w) in SHA256_K.into_iter().zip(w) {
+		let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+		let ch = (v[4] & v[5]) ^ (! v[4] & v[6]);
+		let t1 = v[7].wrapping_add(s1).wrapping_add(ch).wrapping_add(k).wrapping_add(w);
+		let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+		let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+
+		// Everything shifts down one, then A and E get the new bits.
+		v.rotate_right(1);
+		v[0] = t1.wrapping_add(s0.wrapping_add(maj));
+		v[4] = v[4].wrapping_add(t1);
+	}
+
+	for (dst, v) in state.iter_mut().zip(v) {
+		*dst = dst.wrapping_add(v);
+	}
+}
+
+
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn t_sha256() {
+		/// # Hex.
+		fn hex(src: [u8; 32]) -> String {
+			use std::fmt::Write;
+			src.iter().fold(String::new(), |mut acc, b| {
+				write!(&mut acc, "{b:02x}").unwrap();
+				acc
+			})
+		}
+
+		for (raw, expected) in [
+			(&b""[..], "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+			(b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+			(
+				b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+				"248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+			),
+		] {
+			let mut h = Sha256::new();
+			h.update(raw);
+			assert_eq!(hex(h.finish()), expected);
+
+			// Feeding it in dribs and drabs should come out the same.
+			let mut h = Sha256::new();
+			for b in raw.chunks(7) { h.update(b); }
+			assert_eq!(hex(h.finish()), expected);
+		}
+
+		// A million As spans plenty of blocks.
+		let mut h = Sha256::new();
+		for _ in 0..1000 { h.update(&[b'a'; 1000]); }
+		assert_eq!(
+			hex(h.finish()),
+			"cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+		);
+	}
+}